@@ -5,18 +5,31 @@ use {
             next_account_info
         },
 
-        program_error::ProgramError,
+        program_error::{
+            ProgramError,
+            PrintProgramError
+        },
+
+        decode_error::DecodeError,
 
         program::{
             invoke,
-            invoke_signed
+            invoke_signed,
+            set_return_data,
+            MAX_RETURN_DATA
         },
 
-        pubkey::Pubkey,
+        pubkey::{
+            Pubkey,
+            MAX_SEED_LEN
+        },
 
         entrypoint_no_alloc,
 
-        entrypoint::ProgramResult,
+        entrypoint::{
+            ProgramResult,
+            MAX_PERMITTED_DATA_INCREASE
+        },
 
         system_program::{
             check_id as check_system_program_id,
@@ -26,27 +39,61 @@ use {
         system_instruction::{
             transfer as transfer_lamports,
             assign as assign_new_owner,
-            allocate as allocate_memory
+            allocate as allocate_memory,
+            create_account as create_new_account
+        },
+
+        rent::{
+            Rent,
+            ACCOUNT_STORAGE_OVERHEAD
         },
 
         sysvar::{
             Sysvar,
-            clock::Clock
+            clock::Clock,
+            instructions::{
+                check_id as check_instructions_sysvar_id,
+                get_instruction_relative
+            }
         },
 
         program_memory::{
             sol_memcmp,
-            sol_memcpy
+            sol_memcpy,
+            sol_memset
+        },
+
+        keccak::{
+            hash as keccak_hash,
+            hashv as keccak_hashv
+        },
+
+        hash::{
+            Hash,
+            hashv
         },
 
         log::{
             sol_log,
             msg
+        },
+
+        bpf_loader_upgradeable::{
+            ID as BPF_LOADER_UPGRADEABLE_ID,
+            UpgradeableLoaderState
         }
     },
 
     thiserror::Error,
 
+    num_derive::FromPrimitive,
+    num_traits::FromPrimitive as FromPrimitiveTrait,
+
+    borsh::{
+        BorshDeserialize,
+        BorshSerialize
+    },
+
     std::mem::size_of,
 
     helper::*
@@ -59,21 +106,293 @@ entrypoint_no_alloc!(process_instruction);
 pub const CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 0;
 pub const EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 1;
 pub const CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 2;
+pub const LOG_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 3;
+pub const DUPLICATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 4;
+pub const RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR: u8 = 5;
+pub const APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 6;
+pub const LOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 7;
+pub const UNLOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 8;
+pub const SELF_TEST_INSTRUCTION_DISCRIMINATOR: u8 = 9;
+pub const TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR: u8 = 10;
+pub const SEAL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 11;
+pub const COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 12;
+pub const TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 13;
+pub const PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 14;
+pub const SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR: u8 = 15;
+pub const REPAIR_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR: u8 = 16;
+pub const IS_DATA_STORAGE_ACCOUNT_IMMUTABLE_INSTRUCTION_DISCRIMINATOR: u8 = 17;
+pub const FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 18;
+pub const VERIFY_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR: u8 = 19;
+pub const APPEND_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR: u8 = 20;
+pub const COMPACT_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR: u8 = 21;
+pub const SET_DATA_STORAGE_ACCOUNT_DELEGATE_INSTRUCTION_DISCRIMINATOR: u8 = 22;
+pub const ATTEST_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 23;
+pub const CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 24;
+pub const READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR: u8 = 25;
+pub const CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 26;
+pub const SOFT_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 27;
+pub const REVIVE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 28;
+pub const BATCH_CREATE_DATA_STORAGE_ACCOUNTS_INSTRUCTION_DISCRIMINATOR: u8 = 29;
+pub const MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT_INSTRUCTION_DISCRIMINATOR: u8 = 30;
+pub const CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 31;
+pub const COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 32;
+pub const TOP_UP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 33;
+pub const CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 34;
+pub const SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR: u8 = 35;
+pub const GET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR: u8 = 36;
+pub const REMOVE_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR: u8 = 37;
+pub const TOUCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 38;
+pub const RENAME_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 39;
+pub const ALLOCATE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR: u8 = 40;
+pub const WRITE_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR: u8 = 41;
+pub const READ_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR: u8 = 42;
+pub const SET_DATA_STORAGE_ACCOUNT_IMMUTABLE_WINDOW_INSTRUCTION_DISCRIMINATOR: u8 = 43;
+pub const INIT_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR: u8 = 44;
+pub const UPDATE_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR: u8 = 45;
+pub const CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR: u8 = 46;
+pub const GET_DATA_STORAGE_ACCOUNT_METADATA_INSTRUCTION_DISCRIMINATOR: u8 = 47;
+pub const CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR: u8 = 48;
+pub const FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR: u8 = 49;
+pub const EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR: u8 = 50;
+pub const CLOSE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR: u8 = 51;
+
+// Anchor-compatible instructions discriminator ('sha256("global:<instruction's name in snake_case>")[..8]'), only accepted
+// (instead of the 'u8' ones) when the 'anchor_discriminators' feature is enabled
+pub const ANCHOR_INSTRUCTION_DISCRIMINATORS: [([u8; 8], u8); 52] = [
+    ([ 186, 204, 30, 1, 133, 210, 221, 91 ], CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 211, 222, 3, 28, 128, 113, 57, 57 ], EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 76, 130, 238, 165, 185, 84, 243, 170 ], CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 160, 201, 117, 157, 213, 106, 203, 196 ], LOG_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 3, 116, 168, 201, 46, 201, 15, 147 ], DUPLICATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 5, 60, 148, 14, 48, 102, 109, 82 ], RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR),
+    ([ 145, 233, 203, 100, 250, 229, 86, 98 ], APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 178, 5, 144, 193, 242, 148, 13, 79 ], LOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 149, 5, 143, 162, 250, 132, 134, 68 ], UNLOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 100, 35, 221, 75, 6, 15, 206, 168 ], SELF_TEST_INSTRUCTION_DISCRIMINATOR),
+    ([ 77, 153, 168, 156, 88, 43, 82, 192 ], TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR),
+    ([ 4, 252, 134, 204, 95, 63, 45, 0 ], SEAL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 223, 50, 182, 230, 147, 149, 82, 187 ], COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 120, 186, 218, 30, 127, 237, 208, 129 ], TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 169, 3, 21, 84, 60, 193, 58, 152 ], PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 165, 235, 13, 104, 61, 62, 88, 136 ], SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR),
+    ([ 0, 156, 185, 119, 71, 22, 119, 177 ], REPAIR_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR),
+    ([ 191, 124, 170, 125, 70, 61, 193, 110 ], IS_DATA_STORAGE_ACCOUNT_IMMUTABLE_INSTRUCTION_DISCRIMINATOR),
+    ([ 159, 27, 143, 92, 34, 12, 204, 251 ], FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 29, 223, 84, 20, 224, 105, 43, 20 ], VERIFY_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR),
+    ([ 51, 147, 203, 166, 166, 134, 215, 74 ], APPEND_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR),
+    ([ 255, 40, 67, 28, 210, 211, 100, 232 ], COMPACT_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR),
+    ([ 30, 198, 174, 106, 251, 91, 212, 238 ], SET_DATA_STORAGE_ACCOUNT_DELEGATE_INSTRUCTION_DISCRIMINATOR),
+    ([ 145, 213, 68, 44, 202, 5, 199, 247 ], ATTEST_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 253, 7, 73, 64, 111, 68, 142, 16 ], CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 169, 151, 21, 82, 248, 147, 199, 147 ], READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR),
+    ([ 44, 45, 147, 80, 74, 142, 238, 144 ], CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 211, 42, 173, 37, 235, 18, 196, 36 ], SOFT_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 194, 157, 97, 226, 77, 236, 118, 194 ], REVIVE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 29, 170, 255, 171, 88, 18, 58, 37 ], BATCH_CREATE_DATA_STORAGE_ACCOUNTS_INSTRUCTION_DISCRIMINATOR),
+    ([ 208, 177, 236, 202, 41, 245, 167, 109 ], MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT_INSTRUCTION_DISCRIMINATOR),
+    ([ 242, 46, 153, 203, 160, 37, 182, 71 ], CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 33, 100, 141, 184, 136, 59, 189, 30 ], COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 28, 92, 216, 111, 107, 160, 127, 79 ], TOP_UP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 87, 90, 10, 169, 159, 58, 183, 136 ], CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 178, 93, 45, 84, 120, 194, 81, 224 ], SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR),
+    ([ 87, 90, 138, 78, 120, 167, 252, 100 ], GET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR),
+    ([ 191, 154, 30, 82, 30, 253, 87, 137 ], REMOVE_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR),
+    ([ 39, 158, 29, 246, 89, 217, 133, 193 ], TOUCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 232, 206, 41, 171, 35, 247, 184, 155 ], RENAME_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 218, 245, 193, 29, 149, 161, 19, 173 ], ALLOCATE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR),
+    ([ 22, 238, 206, 171, 119, 16, 143, 82 ], WRITE_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR),
+    ([ 115, 151, 55, 167, 193, 186, 66, 76 ], READ_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR),
+    ([ 56, 67, 69, 65, 209, 55, 255, 80 ], SET_DATA_STORAGE_ACCOUNT_IMMUTABLE_WINDOW_INSTRUCTION_DISCRIMINATOR),
+    ([ 156, 10, 60, 164, 143, 120, 231, 201 ], INIT_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR),
+    ([ 44, 164, 211, 59, 154, 63, 33, 58 ], UPDATE_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR),
+    ([ 63, 6, 201, 110, 25, 163, 100, 139 ], CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+    ([ 218, 172, 120, 9, 108, 253, 5, 194 ], GET_DATA_STORAGE_ACCOUNT_METADATA_INSTRUCTION_DISCRIMINATOR),
+    ([ 199, 196, 15, 175, 218, 247, 43, 31 ], CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR),
+    ([ 164, 156, 250, 103, 152, 123, 7, 233 ], FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR),
+    ([ 20, 136, 55, 128, 165, 218, 157, 107 ], EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR),
+    ([ 220, 2, 100, 105, 46, 164, 112, 214 ], CLOSE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR)
+];
+
+// "EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS" ix's flags
+pub const EDIT_FLAG_DISPLAY_LABEL: u8 = 1 << 0;
+pub const EDIT_FLAG_IDEMPOTENCY_KEY: u8 = 1 << 1;
+pub const EDIT_FLAG_EXPECTED_REVISION: u8 = 1 << 2;
+pub const EDIT_FLAG_COMPRESSED: u8 = 1 << 3;
 
 // Constants
 pub const MAX_LABEL_LENGTH: usize = 30;
+pub const MAX_VARIABLE_LABEL_LENGTH: usize = 64;
+/// Longest data-field the program accepts, the stored data-length is a 'u16'.
+/// Create, edit and append reject longer data-fields with 'DataTooLarge', so clients can validate before submitting.
+/// A create's data-field is also bounded by the transaction size, longer data-fields have to be appended.
+pub const MAX_DATA_LEN: usize = u16::MAX as usize;
+// apps that need a minimum payload (e.g. their own fixed-size header) can raise it, create/edit/truncate enforce it
+pub const MIN_DATA_LEN: usize = 0;
+pub const MAX_CPI_ALLOWLIST_LEN: usize = 3;
+/// Program-ids the program was deployed under before, whose accounts the read-only instructions (log, is-immutable, verify,
+/// attest, read & get-entry) still accept. Every other instruction requires the account to be owned by the current program.
+/// Migrating a deployment: list the old program-id here, read the accounts' data-fields through the new program, re-create
+/// them under it and close the old ones through the old program (which still owns them and their rent).
+pub const ALLOWED_LEGACY_PROGRAM_IDS: &[Pubkey] = &[
+    #[cfg(feature = "test-utils")]
+    test_utils::LEGACY_PROGRAM_ID
+];
+// every created account costs a pda search & 3 system program cpi-s, so batches are capped to fit the compute budget
+pub const MAX_BATCH_CREATE_LEN: usize = 5;
+pub const DATA_STORAGE_SEED_PREFIX: &[u8] = b"data_storage_account";
+// same as 'DATA_STORAGE_SEED_PREFIX', kept for existing clients
+pub const DSA_SEED_PREFIX: &[u8] = DATA_STORAGE_SEED_PREFIX;
+pub const FUNDING_LEDGER_SEED_PREFIX: &[u8] = b"funding_ledger";
+pub const FUNDING_LEDGER_ENTRY_LEN: usize = size_of::<Pubkey>() + size_of::<u64>();
+pub const PATCH_LOG_SEED_PREFIX: &[u8] = b"patch_log";
+pub const PATCH_LOG_CAPACITY: usize = 8;
+pub const MAX_PATCH_LOG_ENTRY_DATA_LEN: usize = 32;
+pub const PATCH_LOG_ENTRY_LEN: usize = size_of::<u16>() + size_of::<u8>() + MAX_PATCH_LOG_ENTRY_DATA_LEN;
+// the data-storage account's '(created-at, revision, data-hash)' the pending patches have been queued on, see '//? patch-log'
+pub const PATCH_LOG_BASE_OFFSET: usize = 2 * size_of::<u8>() + PATCH_LOG_CAPACITY * PATCH_LOG_ENTRY_LEN;
+pub const PATCH_LOG_BASE_LEN: usize = size_of::<i64>() + size_of::<u32>() + size_of::<[u8; 32]>();
+pub const PATCH_LOG_LEN: usize = PATCH_LOG_BASE_OFFSET + PATCH_LOG_BASE_LEN;
+pub const CHUNK_SEED_PREFIX: &[u8] = b"chunk";
+// accounts created through a cpi can be at most 10 KiB big
+pub const CHUNK_LEN: usize = MAX_PERMITTED_DATA_INCREASE;
+pub const CHUNKS_METADATA_LEN: usize = size_of::<u32>() + size_of::<u16>();
+pub const CONFIG_SEED_PREFIX: &[u8] = b"config";
+// config's fields' offsets, see '//? config'
+pub const CONFIG_ADMIN_OFFSET: usize = 0;
+pub const CONFIG_MAX_DATA_LEN_OFFSET: usize = 32;
+pub const CONFIG_CREATION_FEE_OFFSET: usize = 36;
+pub const CONFIG_TREASURY_OFFSET: usize = 44;
+pub const CONFIG_BUMP_OFFSET: usize = 76;
+pub const CONFIG_LEN: usize = size_of::<Pubkey>() + size_of::<u32>() + size_of::<u64>() + size_of::<Pubkey>() + size_of::<u8>();
+// header's layout-version, decides which label the pda is derived from (see '//? data storage account PDA's seeds')
+pub const LAYOUT_VERSION_FIXED_LABEL: u8 = 0;
+pub const LAYOUT_VERSION_VARIABLE_LABEL: u8 = 1;
+// header's account-kind, decides how the data-field is laid out (see '//? key-value entries')
+pub const ACCOUNT_KIND_BYTES: u8 = 0;
+pub const ACCOUNT_KIND_KEY_VALUE: u8 = 1;
+pub const ACCOUNT_KIND_CHUNKED: u8 = 2;
+// header's account-discriminator, tells this program's data-storage accounts apart from any other account it owns (e.g. for
+// a 'getProgramAccounts' memcmp filter at 'ACCOUNT_DISCRIMINATOR_OFFSET')
+pub const DATA_STORAGE_ACCOUNT_DISCRIMINATOR: u8 = 0xD5;
+// header's version, bumped whenever the header's layout changes (new fields are appended to it), "MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT"
+// upgrades older accounts. Version '1' is the original header (authority, label, last-updated, bump, is-initialized &
+// data-length), which has no header-version byte.
+pub const HEADER_VERSION: u8 = 2;
+// header's length of version '1' accounts, their data-field starts right after it
+pub const V1_HEADER_LEN: usize = DATA_LEN_OFFSET + size_of::<u16>();
+pub const HEADER_LEN: usize = size_of::<Pubkey>() +
+    MAX_LABEL_LENGTH +
+    size_of::<i64>() +
+    size_of::<u8>() +
+    size_of::<bool>() +
+    size_of::<u16>() +
+    size_of::<u32>() +
+    size_of::<Pubkey>() +
+    size_of::<i64>() +
+    MAX_LABEL_LENGTH +
+    size_of::<u64>() +
+    MAX_CPI_ALLOWLIST_LEN * size_of::<Pubkey>() +
+    size_of::<bool>() +
+    size_of::<i64>() +
+    size_of::<u32>() +
+    size_of::<Hash>() +
+    size_of::<Pubkey>() +
+    size_of::<i64>() +
+    size_of::<Pubkey>() +
+    size_of::<u8>() +
+    size_of::<u8>() +
+    MAX_VARIABLE_LABEL_LENGTH +
+    size_of::<bool>() +
+    size_of::<i64>() +
+    size_of::<u8>() +
+    size_of::<bool>() +
+    size_of::<u8>() +
+    size_of::<i64>() +
+    size_of::<u8>();
+
+// header fields' offsets, see '//? program's data account'
+pub const OWNER_OFFSET: usize = 0;
+pub const LABEL_OFFSET: usize = 32;
+pub const LAST_UPDATED_OFFSET: usize = 62;
+pub const BUMP_OFFSET: usize = 70;
+pub const INIT_OFFSET: usize = 71;
+pub const DATA_LEN_OFFSET: usize = 72;
+pub const MAX_SIZE_OFFSET: usize = 74;
+pub const LOCK_HOLDER_OFFSET: usize = 78;
+pub const LOCK_EXPIRY_OFFSET: usize = 110;
+pub const DISPLAY_LABEL_OFFSET: usize = 118;
+pub const IDEMPOTENCY_KEY_OFFSET: usize = 148;
+pub const CPI_ALLOWLIST_OFFSET: usize = 156;
+pub const HAS_BEEN_EDITED_OFFSET: usize = 252;
+pub const CREATED_AT_OFFSET: usize = 253;
+pub const REVISION_OFFSET: usize = 261;
+pub const DATA_HASH_OFFSET: usize = 265;
+pub const DELEGATE_OFFSET: usize = 297;
+pub const EXPIRES_AT_OFFSET: usize = 329;
+pub const LAST_EDITOR_OFFSET: usize = 337;
+pub const LAYOUT_VERSION_OFFSET: usize = 369;
+pub const VARIABLE_LABEL_LEN_OFFSET: usize = 370;
+pub const VARIABLE_LABEL_OFFSET: usize = 371;
+pub const TOMBSTONE_OFFSET: usize = 435;
+pub const CLOSED_AT_OFFSET: usize = 436;
+pub const HEADER_VERSION_OFFSET: usize = 444;
+pub const COMPRESSED_OFFSET: usize = 445;
+pub const KIND_OFFSET: usize = 446;
+pub const LOCK_UNTIL_OFFSET: usize = 447;
+pub const ACCOUNT_DISCRIMINATOR_OFFSET: usize = 455;
+// the data-field starts right after the (current) header
+pub const DATA_OFFSET: usize = HEADER_LEN;
+
+// keep in sync with the '//? program's data account' layout (and the self-test's header table)
+const _: () = assert!(HEADER_LEN == 456);
 
 // event emitter
+// NOTE: Events are borsh-serialized and logged with 'sol_log_data' (a base64 "Program data: " line), decode them with
+//       'Events::try_from_slice'. The 'legacy_log_events' feature logs them as "Program log: {:?}" (Debug) strings instead.
+// NOTE: Building with the 'no-events' feature compiles 'emit!' to a no-op to save compute-units.
+#[cfg(not(any(feature = "no-events", feature = "legacy_log_events")))]
+macro_rules! emit {
+    ($event: ident) => {
+        solana_program::log::sol_log_data(&[ &borsh::to_vec(&$event).unwrap() ]);
+    };
+}
+
+#[cfg(all(feature = "legacy_log_events", not(feature = "no-events")))]
 macro_rules! emit {
     ($event: ident) => {
         msg!("{:?}", $event);
     };
 }
 
+#[cfg(feature = "no-events")]
+macro_rules! emit {
+    ($event: ident) => {
+        let _ = $event;
+    };
+}
+
+// narrative logger
+// NOTE: The narrative log lines ("⚙️ Instruction: ...", "Mutable", "... successfully. ✅", ...) are only logged with the
+//       'verbose_logs' feature (on by default), build without it ('--no-default-features') to save their compute-units.
+//       Events are emitted either way, and so are the lines an instruction exists to log ("LOG_DATA_STORAGE_ACCOUNT", "SELF_TEST").
+#[cfg(feature = "verbose_logs")]
+macro_rules! verbose_log {
+    ($message: expr) => {
+        solana_program::log::sol_log($message);
+    };
+}
+
+#[cfg(not(feature = "verbose_logs"))]
+macro_rules! verbose_log {
+    ($message: expr) => {
+        let _ = $message;
+    };
+}
+
 
 //? program's instructions
 
-/// NOTE: r readonly, w writable, s signer, x program
+// NOTE: r readonly, w writable, s signer, x program
 
 // "CREATE_NEW_DATA_STORAGE_ACCOUNT" ix
 //  > instruction-data :  
@@ -85,7 +404,93 @@ macro_rules! emit {
 //      1. data-storage account's authority - If authority is SYSTEM_PROGRAM "r" otherwise "rs"
 //      2. funding account - rws
 //      3. system program account - rx
+//      4. config pda - r (see '//? config', also before it's initialized)
+//      5. treasury account - w (only when the config has a creation-fee)
 //  NOTE: We can make a data-storage account immutable with passing system-program-account as authority account.
+//  NOTE: The authority (and delegate, lock-holder) can be a pda of another program (e.g. a multisig), that program signs
+//        for it with 'invoke_signed' when cpi-ing into this one. The authority's owner-program is never checked, a pda
+//        signed for by it's program is a signer like any keypair (applies to every instruction with an authority).
+//  NOTE: Immutable data-storage accounts must be created with a max-size of '0'.
+//  NOTE: The display-label starts out as the label, edits can change it without moving the pda.
+//  NOTE: An edit whose (non-zero) idempotency-key equals the last applied one is a no-op, so clients can safely retry it.
+//  NOTE: An edit with an expected-revision fails unless it matches the account's current revision (optimistic concurrency).
+//  NOTE: Edits that grow the data-field beyond the account's max-size will fail.
+//  NOTE: Data-fields shorter than 'MIN_DATA_LEN' are rejected by create, edit & truncate.
+//  NOTE: When the account has a cpi-allowlist, edits must be invoked via CPI from one of the allowed programs and
+//        the instructions sysvar account has to be passed as the last account (applies to append, truncate & patch too).
+//  NOTE: The funding account can be a pda of the calling program, which signs for it with 'invoke_signed' (it's own seeds)
+//        when invoking this program. The signature carries over to the system program's transfer, so no seeds are passed
+//        to this program, the pda only has to be system-owned & data-less (applies to every instruction with a funding account).
+//  NOTE: The account has neither a max-size nor an expires-at, use "CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS" for those.
+
+// "CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS" ix
+//  > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. '[u8; 30]' as data-account's label (utf-8)
+//      2. 'u32' as data-account's max-size (0 means 'MAX_DATA_LEN')
+//      3. 'i64' as data-account's expires-at (unix-timestamp, 0 means never)
+//      4. '[u8; n]' as data-account's data-field
+//  > instruction-accounts : same as "CREATE_NEW_DATA_STORAGE_ACCOUNT"
+//  NOTE: A payload too short for the max-size & expires-at fails with 'InvalidData'.
+
+// "FACTORY_CREATE_DATA_STORAGE_ACCOUNT" ix
+//  > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. '[u8; 30]' as data-account's label (utf-8)
+//      2. 'u32' as data-account's max-size (0 means 'MAX_DATA_LEN')
+//      3. '[u8; n]' as data-account's data-field
+//  > instruction-accounts : same as "CREATE_NEW_DATA_STORAGE_ACCOUNT", but the authority is always "r"
+//  NOTE: Lets a factory (the funding account) create accounts for users, the pda is still derived from the user's (authority's) pubkey
+//        so the user can manage it afterwards, only the funding account has to sign.
+//  NOTE: The account never expires, use "FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS" for an expires-at.
+
+// "FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS" ix
+//  > instruction-data : same as "CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS"
+//  > instruction-accounts : same as "FACTORY_CREATE_DATA_STORAGE_ACCOUNT"
+
+// "CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT" ix
+//  > instruction-data & instruction-accounts : same as "CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS"
+//  NOTE: The data-field is already compressed by the client, it's stored as-is (the program never inflates it, that would
+//        cost too many compute-units) and the compressed flag is set so readers know to inflate it. The data-length, max-size
+//        and rent are those of the compressed bytes.
+
+// "CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT" ix
+//  > instruction-data & instruction-accounts : same as "CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS"
+//  NOTE: Creates an 'ACCOUNT_KIND_KEY_VALUE' account, the data-field (usually empty) must be a well-formed entry list
+//        (see '//? key-value entries'), otherwise it fails with 'InvalidData'.
+
+// "CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT" ix
+//  > instruction-data & instruction-accounts : same as "CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS"
+//  NOTE: Creates the account like "CREATE_NEW_DATA_STORAGE_ACCOUNT" when it doesn't exist yet. When it already exists (e.g.
+//        a client's retry after a network failure) with the same authority, label and data-field, it's a no-op success
+//        that doesn't modify the account (nor pays the config's creation-fee again), otherwise it fails with 'DataMismatch'.
+
+// "CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT" ix
+//  > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u8' as label's length (1..=64)
+//      2. '[u8; label-length]' as data-account's label (utf-8), not padded
+//      3. 'u32' as data-account's max-size (0 means 'MAX_DATA_LEN')
+//      4. 'i64' as data-account's expires-at (unix-timestamp, 0 means never)
+//      5. '[u8; n]' as data-account's data-field
+//  > instruction-accounts : same as "CREATE_NEW_DATA_STORAGE_ACCOUNT"
+//  NOTE: Creates a 'LAYOUT_VERSION_VARIABLE_LABEL' account, whose pda is derived from the label's exact bytes (see
+//        'find_variable_label_data_storage_pda'), so labels longer than 30 bytes aren't truncated.
+//  NOTE: The display-label starts out as the label's first (up to) 30 bytes, cut at a char boundary.
+
+// "BATCH_CREATE_DATA_STORAGE_ACCOUNTS" ix
+//  > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u8' as accounts count (1..=5, 'MAX_BATCH_CREATE_LEN')
+//      2. '[([u8; 30], u16, [u8; data-length]); count]' as each account's '(label, data-length, data-field)'
+//  > instruction-accounts :
+//      0. funding account - rws
+//      1. system program account - rx
+//      2. config pda - r (see '//? config', also before it's initialized)
+//      3. treasury account - w (only when the config has a creation-fee)
+//      4. '(new data storage account pda - rw, data-storage account's authority - "rs", "r" for SYSTEM_PROGRAM)' per account
+//  NOTE: Runs "CREATE_NEW_DATA_STORAGE_ACCOUNT" for every account (with a max-size & expires-at of '0'), all in one go. The
+//        creation-fee is paid once per account.
 
 // "EDIT_DATA_STORAGE_ACCOUNT" ix
 // > instruction-data :
@@ -99,11 +504,40 @@ macro_rules! emit {
 //          0. data-storage account pda - rw
 //          1. data-storage authority account - rs
 //          2. rent-receiver account info - rw
-//     > if new_data_length > old_data_length :
+//     > if new_data_length > old_data_length (and bigger than the reserved capacity) :
 //          0. data-storage account pda - rw
 //          1. data-storage authority account - rs
 //          2. funding account - rws
-//          3. system program account - rx            
+//          3. system program account - rx
+//          4. funding-ledger pda - rw (optional)
+//  NOTE: When the funding-ledger pda is passed, the grow's '(funder, lamports)' is appended to it (see '//? funding-ledger').
+//  NOTE: The compressed flag is cleared, since the new data-field replaces the old one. Append, truncate & patch work on the
+//        stored bytes and keep the flag as-is.
+//  NOTE: Only 'ACCOUNT_KIND_BYTES' accounts, others fail with 'NotBytesAccount' (applies to append, truncate, patch & the
+//        patch-log's append & compact too), key-value & chunked accounts are written to by their own instructions.
+
+// "EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS" ix
+// > instruction-data :
+//     0. 'u8' as instruction's discriminator
+//     1. 'u8' as edit-flags ('EDIT_FLAG_DISPLAY_LABEL' | 'EDIT_FLAG_IDEMPOTENCY_KEY' | 'EDIT_FLAG_EXPECTED_REVISION' |
+//        'EDIT_FLAG_COMPRESSED')
+//     2. '[u8; 30]' as new display-label (utf-8, validated like the account-label), only when 'EDIT_FLAG_DISPLAY_LABEL' is set
+//     3. 'u64' as idempotency-key, only when 'EDIT_FLAG_IDEMPOTENCY_KEY' is set
+//     4. 'u32' as expected-revision, only when 'EDIT_FLAG_EXPECTED_REVISION' is set
+//     5. '[u8; n]' as new data-field
+// > instruction-accounts : same as "EDIT_DATA_STORAGE_ACCOUNT"
+//  NOTE: The compressed flag is set to 'EDIT_FLAG_COMPRESSED'.
+
+// "COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//     0. 'u8' as instruction's discriminator
+//     1. '[u8; 32]' as expected sha-256 hash of the current data-field
+//     2. '[u8; n]' as new data-field
+// > instruction-accounts : same as "EDIT_DATA_STORAGE_ACCOUNT"
+//  NOTE: An edit (without edit-flags) that only writes the new data-field when the current one's hash matches the expected one,
+//        otherwise it fails with 'HashMismatch' and the account is left as-is. Cheaper than 'EDIT_FLAG_EXPECTED_REVISION' for
+//        clients that already have the current data-field (or it's stored hash).
+//  NOTE: The compressed flag is left as-is, the new data-field must be in the current one's encoding.
 
 // "CLOSE_DATA_STORAGE_ACCOUNT" ix
 // > instruction-data :
@@ -112,393 +546,7907 @@ macro_rules! emit {
 //      0. data-storage account pda - rw
 //      1. data-storage authority account - rs
 //      2. rent-exempt receiver account - rw
+//      3. excess-lamports receiver account - rw (optional)
+//      4. patch-log pda - rw (optional, see '//? patch-log')
+//  NOTE: The rent-receiver only gets the account's rent-exempt minimum, lamports sent directly to the pda go to the
+//        excess-receiver, closing such an account without one fails with 'UnexpectedLamports'.
+//  NOTE: The patch-log is closed alongside the account, it's lamports go to the rent-receiver. Pass the rent-receiver as
+//        the excess-receiver when there's no excess.
 
-//? program's instructions
+// "LOG_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - r
+//  NOTE: Logs the account's header as a single human-readable line, use the emitted events for indexing.
 
+// "DUPLICATE_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. '[u8; 30]' as new data-account's label (utf-8)
+// > instruction-accounts :
+//      0. source data-storage account pda - r
+//      1. new data-storage account pda - rw
+//      2. data-storage authority account - rs
+//      3. funding account - rws
+//      4. system program account - rx
+//      5. config pda - r (see '//? config', also before it's initialized)
+//      6. treasury account - w (only when the config has a creation-fee)
+//      7. source's patch-log pda - r (optional, see '//? patch-log', the 6th account without a creation-fee)
+//  NOTE: The new account gets the same authority, max-size and data-field as the source account.
+//  NOTE: The source's pending patches are folded into the new account's data-field, pass the patch-log when there may be some.
 
-//? program's data account
-//      0. 'Pubkey ([u8; 32])' as data-account's owner (..32)
-//      1. '[u8; 30]' as data-account's label (utf-8) (32..62)
-//      2. 'i64' as last-updated (62..70)
-//      3. 'u8' as canonical_bump (70)
-//      4. 'bool' as is-initialized (71)
-//      5. 'u16' as data-account's data-field length (72..74)
-//      6. '[u8; n]' as data-account's data-field (74..)
+// "RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u16' as additional data-field capacity
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. funding account - rws
+//      3. system program account - rx
+//      4. funding-ledger pda - rw (optional)
+//  NOTE: Only grows the account's buffer, the data-field's length and data stay the same.
+//  NOTE: An account can grow by at most 10 KiB ('MAX_PERMITTED_DATA_INCREASE') per instruction, edit & append included.
 
-/// NOTE
-/// - Authority account can be a zero-account (system-program-id), to make the data storage account immutable
-/// - When initializing a new account 'last-updated' will be '0'
-//? program's data account
+// "APPEND_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. '[u8; n]' as data to append to the data-field
+// > instruction-accounts :
+//     > if the appended data fits in the reserved capacity :
+//          0. data-storage account pda - rw
+//          1. data-storage authority account - rs
+//     > otherwise :
+//          0. data-storage account pda - rw
+//          1. data-storage authority account - rs
+//          2. funding account - rws
+//          3. system program account - rx
+//          4. funding-ledger pda - rw (optional)
+//  NOTE: Appends that grow the data-field beyond the account's max-size fail before any rent is transferred.
 
+// "LOCK_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'Pubkey ([u8; 32])' as lock-holder
+//      2. 'i64' as lock-expiry (unix-timestamp)
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account (or the current lock-holder) - rs
+//  NOTE: Until the lock expires, only the lock-holder can edit/append the data-field and the authority can't close the account.
 
-//? data storage account PDA's seeds
-//      0. "data_storage_account"
-//      1. authority's Pubkey
-//      2. account's label
-//? data storage account PDA's seeds
+// "UNLOCK_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account (or the current lock-holder) - rs
 
-//? data storage account == dsa
-///! NOTE: Do not send lamports directly to the data_storage PDAs !
-pub fn process_instruction(
-    program_id: &Pubkey,
-    accounts_info: &[AccountInfo],
-    instruction_data: &[u8]
-) -> ProgramResult {
-    let (
-        ix_discriminator,
-        ix_data
-    ) = instruction_data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+// "SELF_TEST" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts : -
+//  NOTE: Checks (at runtime) that the header fields are contiguous and add up to 'HEADER_LEN', so a deployed binary can be verified with a simulation.
 
-    let accounts_info = &mut accounts_info.iter();
+// "TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. new authority account - r
+//      3. new data-storage account pda (derived from the new authority & the same label) - rw
+//      4. system program account - rx
+//      5. patch-log pda - rw (optional, see '//? patch-log')
+//      6. rent-exempt receiver account - rw (only with the patch-log)
+//  NOTE: Since the authority is one of the pda's seeds, the account (lamports & data) moves to the new authority's pda and the old one gets closed.
+//  NOTE: The patch-log is derived from the old pda, so the pending patches are folded into the account before it moves and
+//        the patch-log is closed, it's lamports go to the rent-receiver. Pass it when there may be pending patches.
 
-    match *ix_discriminator {
-        CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
-            sol_log("⚙️ Instruction: CreateNewDataStorageAccount");
+// "SEAL_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. sealed data-storage account pda (derived from SYSTEM_PROGRAM & the same label) - rw
+//      3. system program account - rx
+//      4. patch-log pda - rw (optional, see '//? patch-log')
+//      5. rent-exempt receiver account - rw (only with the patch-log)
+//  NOTE: Makes a mutable account immutable, same as transferring it's authority to SYSTEM_PROGRAM, so the account moves to a new pda too.
+//  NOTE: The patch-log is handled the same way as in "TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY".
 
-            let new_data_storage_pda_account_info = next_account_info(accounts_info)?;
-            let authority_account_info =  next_account_info(accounts_info)?; 
-            let funding_account_info = next_account_info(accounts_info)?;
-            let system_program_account_info = next_account_info(accounts_info)?;
+// "COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. '[u8; 32]' as expected keccak-256 hash of the data-field
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. rent-exempt receiver account - rw
+//      3. excess-lamports receiver account - rw (optional)
+//      4. patch-log pda - rw (optional, see '//? patch-log')
+//  NOTE: Same as "CLOSE_DATA_STORAGE_ACCOUNT", but fails when the data-field has changed since the client read it.
 
-            check_system_program_account(system_program_account_info.key)?;
+// "TRUNCATE_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u16' as target data-field length (<= current length)
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. rent-receiver account - rw
+//  NOTE: Truncating to the current length only bumps 'last-updated'.
 
-            if authority_account_info.key != &SYSTEM_PROGRAM_ID {
-                check_account_is_signer(authority_account_info)?;
-                sol_log("Mutable");
-            } else {
-                sol_log("Immutable");
-            };
+// "PATCH_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u16' as offset into the data-field
+//      2. '[u8; n]' as replacement bytes, 'offset + n' must be <= current data-field length
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//  NOTE: Overwrites the bytes in place, the data-field's length and the account's size never change.
 
-            // validate instruction-data
-            if ix_data.len() < MAX_LABEL_LENGTH {
-                return Err(
-                    ProgramError::Custom(
-                        DataStorageError::InvalidData as u32
-                    )
-                );
-            };
+// "SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. '[Pubkey; n]' as allowed caller programs (n <= 3), an empty list clears the allowlist
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//  NOTE: While the allowlist isn't empty, direct (non-CPI) edits are rejected.
 
-            // deserialize instruction's data
-            let (
-                account_label,
-                account_data
-            ) = ix_data.split_at(30);
+// "REPAIR_DATA_STORAGE_ACCOUNT_AUTHORITY" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. original authority account - If it is SYSTEM_PROGRAM "r" otherwise "rs"
+//  NOTE: Restores a corrupted authority (and bump), only when the pda re-derives from the original authority & the stored seed-label.
+//  NOTE: Fails when the stored authority isn't corrupted, or when the seed-label is corrupted too (the original authority can't be proven then).
 
-            // validate label
-            if let Err(_) = String::from_utf8(account_label.to_vec()) {
-                return Err(
-                    ProgramError::Custom(
-                        DataStorageError::InvalidLabel as u32
-                    )
-                );
-            };
+// "IS_DATA_STORAGE_ACCOUNT_IMMUTABLE" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - r
+//  NOTE: Returns a single 'bool' byte (1 when immutable) as the return-data, instead of failing like the mutating ix-s do.
 
-            // get pda's bump and validate the pda's pubkey
-            let (
-                dsa_address,
-                dsa_bump
-            ) = Pubkey::try_find_program_address(
-                &[
-                    b"data_storage_account",
-                    authority_account_info.key.as_ref(),
-                    account_label
-                ],
-                program_id
-            ).ok_or::<ProgramError>(ProgramError::Custom(DataStorageError::FailedToFindProgramAddress as u32))?;
-            if &dsa_address != new_data_storage_pda_account_info.key {
-                return Err(
-                    ProgramError::InvalidSeeds
-                );
-            };
+// "VERIFY_DATA_STORAGE_ACCOUNT_DATA" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - r
+//  NOTE: Fails with 'HashMismatch' when the data-field's sha-256 hash doesn't match the stored one (e.g. after a partial write).
 
-            // create the account
-            let account_size = size_of::<Pubkey>() +
-                size_of::<[u8; 30]>() +
-                size_of::<i64>() +
-                size_of::<u8>() +
-                size_of::<bool>() +
-                size_of::<u16>() +
-                account_data.len();
+// "APPEND_DATA_STORAGE_ACCOUNT_PATCH_LOG" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u16' as offset into the data-field
+//      2. '[u8; n]' as replacement bytes (1 <= n <= 'MAX_PATCH_LOG_ENTRY_DATA_LEN'), 'offset + n' must be <= current data-field length
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. patch-log pda - rw
+//      3. funding account - rws, only when the patch-log doesn't exist yet
+//      4. system program account - rx, only when the patch-log doesn't exist yet
+//  NOTE: Same as "PATCH_DATA_STORAGE_ACCOUNT", but the patch is queued in the patch-log (see '//? patch-log') instead of
+//        rewriting & re-hashing the data-field.
 
-            let seeds: &[&[u8]] = &[
-                b"data_storage_account",
-                authority_account_info.key.as_ref(),
-                account_label,
-                &[ dsa_bump ]
-            ];
+// "COMPACT_DATA_STORAGE_ACCOUNT_PATCH_LOG" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. patch-log pda - rw
+//  NOTE: Folds the pending patches (oldest first) into the data-field and empties the patch-log.
 
-            create_pda_account(
-                new_data_storage_pda_account_info,
-                funding_account_info,
-                account_size,
-                program_id,
-                seeds
-            )?;
-            sol_log("New data storage account created.");
+// "SET_DATA_STORAGE_ACCOUNT_DELEGATE" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'Pubkey ([u8; 32])' as the new delegate, SYSTEM_PROGRAM removes the delegate
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//  NOTE: The delegate can edit/append/truncate/patch the data-field (in the authority's place), but can't close the account,
+//        transfer it's authority or change it's settings. Transferring the authority (or sealing) removes the delegate.
 
-            // initialize the account
-            // 1. set account-authority
-            sol_memcpy(
-                new_data_storage_pda_account_info
-                    .data
-                    .try_borrow_mut()
-                    .unwrap()
-                    .get_mut(..32)
-                    .unwrap(),
-                authority_account_info.key.as_ref(),
-                size_of::<Pubkey>()
-            );
-            // 2. set account-label
-            sol_memcpy(
-                new_data_storage_pda_account_info
-                    .data
-                    .try_borrow_mut()
-                    .unwrap()
-                    .get_mut(32..62)
-                    .unwrap(),
-                    account_label,
-                size_of::<[u8; 30]>()
-            );
-            // 3. skip 'last-updated'
-            // 4. set account-bump
-            let mut das_data = new_data_storage_pda_account_info
-                .data
-                .try_borrow_mut()
-                .unwrap();
-            *das_data
-                .get_mut(70)
-                .unwrap() = dsa_bump;
-            // 5. set is_initialized flag
-            *das_data
-                .get_mut(71)
-                .unwrap() = true as u8;
+// "ATTEST_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - r
+//  NOTE: Returns '[u8; 32]' as keccak-256 hash of '(pda || authority || data-field || last-updated (le))', followed by
+//        'u64' as the current slot, as the return-data. Off-chain verifiers recompute the hash from the account at that slot.
 
-            drop(das_data);
+// "CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. rent-exempt receiver account - rw
+//      2. excess-lamports receiver account - rw (optional)
+//      3. patch-log pda - rw (optional, see '//? patch-log')
+//  NOTE: Permissionless (no signer), so cranks can garbage-collect accounts whose expires-at has passed. Immutable and
+//        locked accounts expire too.
+//  NOTE: Lamports above the rent-exempt minimum go to the excess-receiver and the patch-log is closed alongside the account,
+//        as with "CLOSE_DATA_STORAGE_ACCOUNT".
 
-            // 6. set account-data length and data
-            let account_data_len = (account_data.len() as u16).to_le_bytes();
-            // 1. set length
-            sol_memcpy(
-                new_data_storage_pda_account_info
-                    .data
-                    .try_borrow_mut()
-                    .unwrap()
-                    .get_mut(72..74)
-                    .unwrap(),
-                    &account_data_len,
-                size_of::<u16>()
-            );
-            if account_data.len() > 0 {
-                // 2. set data
-                sol_memcpy(
-                    new_data_storage_pda_account_info
-                        .data
-                        .try_borrow_mut()
-                        .unwrap()
-                        .get_mut(74..)
+// "READ_DATA_STORAGE_ACCOUNT_DATA" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - r
+//      1. patch-log pda - r (optional, see '//? patch-log')
+//  NOTE: Returns the compressed flag ('bool') followed by the data-field as the return-data, so calling programs can read it
+//        with 'get_return_data' instead of deserializing the account. Fails with 'InvalidData' when the data-field is bigger
+//        than 'MAX_RETURN_DATA' (1024 bytes) minus the flag.
+//  NOTE: When the patch-log is passed, the pending patches are applied over the data-field (same as 'read_materialized_data'),
+//        otherwise the base is returned as-is.
+
+// "GET_DATA_STORAGE_ACCOUNT_METADATA" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - r
+//  NOTE: Returns the borsh-encoded 'DataStorageAccountMetadata' (authority, label, last-updated, created-at, revision and
+//        data-length) as the return-data, without the data-field, so it works for accounts of any size (unlike
+//        "READ_DATA_STORAGE_ACCOUNT_DATA").
+
+// "SOFT_CLOSE_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. rent-exempt receiver account - rw
+//  NOTE: Unlike "CLOSE_DATA_STORAGE_ACCOUNT" the account is kept as a tombstone, so clients can tell "closed" apart from
+//        "never created": the data-field is dropped, the header (but the authority, labels & bump) is wiped, the tombstone
+//        flag & closed-at are set, and everything above the header-only account's rent-exempt minimum is refunded.
+//  NOTE: Tombstones are uninitialized, create rejects them with 'AccountTombstoned', use "REVIVE_DATA_STORAGE_ACCOUNT" instead.
+
+// "REVIVE_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u32' as data-account's max-size (0 means 'MAX_DATA_LEN')
+//      2. 'i64' as data-account's expires-at (unix-timestamp, 0 means never)
+//      3. '[u8; n]' as data-account's data-field
+// > instruction-accounts :
+//      0. tombstoned data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. funding account - rws
+//      3. system program account - rx
+//  NOTE: Initializes a tombstone again, as create would (same label, display-label & pda), the funding account pays the
+//        data-field's rent. The data-field can be at most 10 KiB ('MAX_PERMITTED_DATA_INCREASE') long.
+
+// "MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda (or tombstone) - rw
+//      1. data-storage authority account - If authority is SYSTEM_PROGRAM "r" otherwise "rs"
+//      2. funding account - rws
+//      3. system program account - rx
+//  NOTE: Rewrites an older account's header into the current ('HEADER_VERSION') layout, new fields get their defaults and the
+//        data-field (and reserved capacity) is kept, the funding account pays the extra bytes' rent. A no-op for current accounts.
+//  NOTE: Migrated accounts get the account-discriminator, every other instruction rejects accounts without it.
+//  NOTE: Version '1' accounts (the original 'V1_HEADER_LEN' bytes long header) are the only ones exactly as long as their
+//        header & data-field, later versions store their header-version at 'HEADER_VERSION_OFFSET'. Migrated version '1'
+//        accounts get their label as display-label and their data-field's hash, the other new fields are zeroed.
+
+// "TOP_UP_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda (or tombstone) - rw
+//      1. funder account - rws
+//      2. system program account - rx
+//  NOTE: Permissionless (any funder), transfers the account's shortfall from it's current size's rent-exempt minimum, so an
+//        underfunded account (e.g. after a rent parameters change) can be edited again. A no-op for rent-exempt accounts.
+
+// "SET_DATA_STORAGE_ACCOUNT_ENTRY" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u8' as key's length (non-zero)
+//      2. '[u8; key_len]' as key
+//      3. '[u8; n]' as value
+// > instruction-accounts : same as "EDIT_DATA_STORAGE_ACCOUNT"
+//  NOTE: Overwrites the key's entry in place, or appends a new entry when the key isn't set yet. The data-field grows or
+//        shrinks by the entry's difference, exactly like an edit to the spliced data-field (same quota, rent & accounts).
+//  NOTE: Only 'ACCOUNT_KIND_KEY_VALUE' accounts, others fail with 'NotKeyValueAccount'.
+
+// "GET_DATA_STORAGE_ACCOUNT_ENTRY" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. '[u8; n]' as key
+// > instruction-accounts :
+//      0. data-storage account pda - r
+//  NOTE: Returns the key's value as the return-data, fails with 'EntryNotFound' when the key isn't set and with 'InvalidData'
+//        when the value is bigger than 'MAX_RETURN_DATA' (1024 bytes).
+
+// "REMOVE_DATA_STORAGE_ACCOUNT_ENTRY" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. '[u8; n]' as key
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. rent-exempt receiver account - rw
+//  NOTE: Removes the key's entry and refunds it's rent, fails with 'EntryNotFound' when the key isn't set.
+
+// "TOUCH_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//  NOTE: Only refreshes 'last-updated' (heartbeats), the data-field, it's hash, revision & has-been-edited are left as-is, so
+//        it costs the same compute-units whatever the data-field's size. Immutable accounts can't be touched.
+
+// "RENAME_DATA_STORAGE_ACCOUNT" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. '[u8; 30]' as data-account's new label (utf-8)
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. new data-storage account pda - rw
+//      2. data-storage authority account - rs
+//      3. funding account - rws
+//      4. system program account - rx
+//      5. patch-log pda - rw (optional, see '//? patch-log')
+//  NOTE: Moves the account to the new label's pda in a single instruction: the new account gets the whole account (header,
+//        data-field & reserved capacity) with the new label (and display-label), then the old one is closed and it's
+//        lamports go to the funding account. Variable-label accounts become fixed-label ones.
+//  NOTE: The pending patches are folded into the account before it moves and the patch-log is closed, it's lamports go to
+//        the funding account too.
+
+// "ALLOCATE_DATA_STORAGE_ACCOUNT_CHUNKS" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u32' as payload's total length (non-zero)
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. funding account - rws
+//      3. system program account - rx
+//      4.. chunk pdas - rw, one per chunk ('total length / CHUNK_LEN' rounded up) in their index order
+//  NOTE: Turns a (bytes) account with an empty data-field into an 'ACCOUNT_KIND_CHUNKED' one and creates it's (zeroed)
+//        chunks, see '//? chunks'. Payloads are bounded by how many chunk accounts fit a transaction.
+
+// "WRITE_DATA_STORAGE_ACCOUNT_CHUNK" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u16' as chunk's index
+//      2. 'u16' as offset into the chunk
+//      3. '[u8; n]' as bytes (non-empty), 'offset + n' must be <= the chunk's length
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. chunk pda - rw
+//  NOTE: Same editors, lock & cpi-allowlist as "EDIT_DATA_STORAGE_ACCOUNT", bumps the account's revision.
+
+// "READ_DATA_STORAGE_ACCOUNT_CHUNK" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u16' as chunk's index
+//      2. 'u16' as offset into the chunk
+// > instruction-accounts :
+//      0. data-storage account pda - r
+//      1. chunk pda - r
+//  NOTE: Returns the chunk's bytes from the offset as the return-data, at most 'MAX_RETURN_DATA' (1024 bytes) of them.
+
+// "CLOSE_DATA_STORAGE_ACCOUNT_CHUNKS" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//      2. rent-receiver account - rw
+//      3.. chunk pdas - rw, all of them ('chunk count') in their index order
+//  NOTE: Closes the chunks (their lamports go to the rent-receiver) and turns the account back into a (bytes) account with
+//        an empty data-field, the chunks metadata's rent goes to the rent-receiver too.
+
+// "INIT_DATA_STORAGE_CONFIG" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'u32' as max data-length (0 means 'MAX_DATA_LEN')
+//      2. 'u64' as creation-fee (lamports, 0 means none)
+//      3. 'Pubkey ([u8; 32])' as treasury
+// > instruction-accounts :
+//      0. config pda - rw
+//      1. admin account - rs
+//      2. funding account - rws
+//      3. system program account - rx
+//      4. program's program-data account - r (of the upgradeable bpf loader)
+//  NOTE: Creates the (single) config pda, it can only be initialized once, by the program's upgrade-authority (the admin
+//        account), which becomes the config's admin.
+
+// "UPDATE_DATA_STORAGE_CONFIG" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'Pubkey ([u8; 32])' as new admin
+//      2. 'u32' as max data-length (0 means 'MAX_DATA_LEN')
+//      3. 'u64' as creation-fee (lamports, 0 means none)
+//      4. 'Pubkey ([u8; 32])' as treasury
+// > instruction-accounts :
+//      0. config pda - rw
+//      1. admin account - rs
+//  NOTE: Replaces every field of the config, pass the current admin to keep it.
+
+// "SET_DATA_STORAGE_ACCOUNT_IMMUTABLE_WINDOW" ix
+// > instruction-data :
+//      0. 'u8' as instruction's discriminator
+//      1. 'i64' as lock-until (unix-timestamp)
+// > instruction-accounts :
+//      0. data-storage account pda - rw
+//      1. data-storage authority account - rs
+//  NOTE: Until 'lock-until' the data-field can't be edited/appended/truncated/patched (nor it's entries & chunks written)
+//        and the account can't be closed, not even by the authority, it fails with 'TimeLocked'. Unlike a system-program
+//        authority it's temporary, the account becomes editable again once the window is over. An active window can only
+//        be extended, never shortened.
+
+//? program's instructions
+
+
+//? program's data account
+//      0. 'Pubkey ([u8; 32])' as data-account's owner (..32)
+//      1. '[u8; 30]' as data-account's seed-label (utf-8) (32..62), immutable since it's the pda's seed
+//      2. 'i64' as last-updated (62..70)
+//      3. 'u8' as canonical_bump (70)
+//      4. 'bool' as is-initialized (71)
+//      5. 'u16' as data-account's data-field length (72..74)
+//      6. 'u32' as data-account's max-size (74..78)
+//      7. 'Pubkey ([u8; 32])' as lock-holder (78..110), zero-account when unlocked
+//      8. 'i64' as lock-expiry (110..118)
+//      9. '[u8; 30]' as data-account's display-label (utf-8) (118..148)
+//     10. 'u64' as last applied edit's idempotency-key (148..156), '0' when none
+//     11. '[Pubkey; 3]' as cpi-allowlist (156..252), zero-accounts are empty slots
+//     12. 'bool' as has-been-edited (252), set by the first edit/append/truncate/patch
+//     13. 'i64' as created-at (253..261), set once by create (and duplicate)
+//     14. 'u32' as revision (261..265), incremented by every edit/append/truncate/patch
+//     15. '[u8; 32]' as sha-256 hash of the data-field (265..297), recomputed by every create/edit/append/truncate/patch
+//     16. 'Pubkey ([u8; 32])' as delegate (297..329), SYSTEM_PROGRAM when there is no delegate
+//     17. 'i64' as expires-at (329..337), '0' when the account never expires
+//     18. 'Pubkey ([u8; 32])' as last-editor (337..369), the authority on create, then the signer (authority or delegate)
+//         of the latest edit/append/truncate/patch
+//     19. 'u8' as layout-version (369), 'LAYOUT_VERSION_FIXED_LABEL' or 'LAYOUT_VERSION_VARIABLE_LABEL'
+//     20. 'u8' as variable label's length (370), '0' for fixed-label accounts
+//     21. '[u8; 64]' as variable label (utf-8) (371..435), only the first label-length bytes are used, the seed-label
+//         (and it's 32..62 slot) is zero for variable-label accounts
+//     22. 'bool' as tombstone (435), set by soft-close (see "SOFT_CLOSE_DATA_STORAGE_ACCOUNT")
+//     23. 'i64' as closed-at (436..444), '0' unless the account is a tombstone
+//     24. 'u8' as header-version (444), 'HEADER_VERSION' (see "MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT")
+//     25. 'bool' as compressed (445), the client stored the data-field compressed (see "CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT")
+//     26. 'u8' as account-kind (446), 'ACCOUNT_KIND_BYTES', 'ACCOUNT_KIND_KEY_VALUE' (see '//? key-value entries') or
+//         'ACCOUNT_KIND_CHUNKED' (see '//? chunks')
+//     27. 'i64' as lock-until (447..455), the data-field can't be written before it, '0' when there is no immutable window
+//         (see "SET_DATA_STORAGE_ACCOUNT_IMMUTABLE_WINDOW")
+//     28. 'u8' as account-discriminator (455), always 'DATA_STORAGE_ACCOUNT_DISCRIMINATOR' (0xD5)
+//     29. '[u8; n]' as data-account's data-field (456..)
+//  NOTE: The data-field's capacity (data_len() - 456) can be bigger than it's length when capacity has been reserved.
+//  NOTE: Every instruction rejects an account without the account-discriminator with 'InvalidAccountData', so indexers can
+//        find all data-storage accounts with a 'getProgramAccounts' memcmp filter of '[0xD5]' at offset 455.
+//  NOTE: Every field's offset is exported as a '<FIELD>_OFFSET' constant (e.g. 'DATA_LEN_OFFSET'), the data-field's as 'DATA_OFFSET'.
+
+// NOTE
+// - Authority account can be a zero-account (system-program-id), to make the data storage account immutable
+// - When initializing a new account 'last-updated' will be '0' and 'has-been-edited' false, use the latter to tell "never edited" apart
+// - 'created-at' never changes after create, transferring or sealing an account keeps it
+//? program's data account
+
+
+//? data storage account PDA's seeds
+//      0. "data_storage_account" ('DATA_STORAGE_SEED_PREFIX', see 'find_data_storage_pda')
+//      1. authority's Pubkey
+//      2. account's label, printable utf-8 zero-padded at the end, can't be the seed-prefix itself
+//  NOTE: Labels are unique per-authority, creating an account under an already used label fails with 'LabelAlreadyUsed'
+//        (the existing account is never re-targeted), the same label of another authority derives a different pda.
+//  NOTE: 'LAYOUT_VERSION_VARIABLE_LABEL' accounts use the label's length ('u8') followed by the (unpadded) label instead of 2.,
+//        split in 32-byte seeds ('MAX_SEED_LEN'). Both forms could only derive the same pda if a fixed label started with
+//        the length byte (a control character), which fixed labels can't.
+//? data storage account PDA's seeds
+
+
+//? funding-ledger
+//  > seeds : "funding_ledger" ('FUNDING_LEDGER_SEED_PREFIX'), data-storage account's Pubkey
+//  > data  : '[(Pubkey, u64); n]' as '(funder, lamports)' entries, one per grow, oldest first
+//  NOTE: Created by the first grow that passes it, each entry records the rent-exempt lamports the funder paid for the data-storage account's extra bytes.
+//? funding-ledger
+
+
+//? patch-log
+//  > seeds : "patch_log" ('PATCH_LOG_SEED_PREFIX'), data-storage account's Pubkey
+//  > data  :
+//      0. 'u8' as head, the oldest patch's slot (0)
+//      1. 'u8' as pending patches count (1)
+//      2. '[(u16, u8, [u8; 32]); 8]' as a ring of '(offset, len, bytes)' patches (2..282)
+//      3. '(i64, u32, [u8; 32])' as the base's '(created-at, revision, data-hash)' after the last append (282..326)
+//  NOTE: The data-storage account's data-field is the base, readers apply the pending patches over it (see 'read_materialized_data').
+//  NOTE: When the ring is full, the oldest patch is folded into the base to make room for the new one.
+//  NOTE: The base's hash doesn't cover the pending patches. Any other change of the data-storage account (an edit, truncate,
+//        close & re-create...) changes it's '(created-at, revision, data-hash)', the pending patches are stale from
+//        then on: they're dropped by the next append & compact, and readers ignore them.
+//  NOTE: Transfer, seal & rename fold the pending patches into the account before it moves to it's new pda and close the
+//        patch-log, duplicate folds them into the copy. They're only seen when the patch-log is passed.
+//? patch-log
+
+
+//? key-value entries
+//  > data-field : '[(u8, [u8; key_len], u16, [u8; val_len]); n]' as '(key_len, key, val_len, val)' entries, back to back
+//  NOTE: Only in 'ACCOUNT_KIND_KEY_VALUE' accounts, many small values share one account (and it's header's rent). Keys are
+//        non-empty & unique, decode the entries with 'read_entries'.
+//  NOTE: The byte-level instructions (edit, append, truncate, patch, ...) still work on the data-field, it's up to them to keep
+//        it well-formed. The entry instructions fail with 'MalformedAccount' on a malformed one.
+//? key-value entries
+
+
+//? chunks
+//  > seeds      : "chunk" ('CHUNK_SEED_PREFIX'), data-storage account's Pubkey, chunk's index ('u16', little-endian)
+//  > data       : '[u8; n]' as the payload's 'index * CHUNK_LEN..' bytes, 'CHUNK_LEN' of them but in the last chunk
+//  > data-field :
+//      0. 'u32' as payload's total length (0..4)
+//      1. 'u16' as chunk count (4..6)
+//  NOTE: Only in 'ACCOUNT_KIND_CHUNKED' accounts, for payloads an account can't hold (or be grown to in a few instructions).
+//        The header account keeps the metadata, the payload lives in the chunk pdas (see 'find_chunk_pda').
+//  NOTE: The chunk pdas are derived from the data-storage account's Pubkey, so a chunked account can't be moved (transferred,
+//        sealed, renamed or duplicated), soft-closed nor closed: these fail with 'ChunkedAccount' until it's chunks are
+//        closed by "CLOSE_DATA_STORAGE_ACCOUNT_CHUNKS".
+//? chunks
+
+
+//? config
+//  > seeds : "config" ('CONFIG_SEED_PREFIX')
+//  > data  :
+//      0. 'Pubkey ([u8; 32])' as admin (..32)
+//      1. 'u32' as max data-length (32..36), '0' means 'MAX_DATA_LEN'
+//      2. 'u64' as creation-fee (36..44), lamports, '0' means none
+//      3. 'Pubkey ([u8; 32])' as treasury (44..76), receives the creation-fees
+//      4. 'u8' as canonical_bump (76)
+//  NOTE: Enforced by every create instruction (create, batch-create & duplicate), which all take the config pda: the
+//        data-field (and a non-zero max-size) can't be longer than the max data-length, and the funding account pays the
+//        creation-fee to the treasury. Until it's initialized the config pda is still passed, but nothing is enforced.
+//        Later edits aren't bound by it, an account's max-size is it's quota.
+//? config
+
+//? data storage account == dsa
+// NOTE: Do not send lamports directly to the data_storage PDAs !
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts_info: &[AccountInfo],
+    instruction_data: &[u8]
+) -> ProgramResult {
+    let (
+        ix_discriminator,
+        ix_data
+    ) = decode_instruction_discriminator(instruction_data)?;
+
+    // CPIs get the instruction's accounts as-is (the runtime picks the ones it needs by their key), so no 'AccountInfo' is cloned
+    let cpi_accounts_info = accounts_info;
+    let accounts_info = &mut accounts_info.iter();
+
+    match ix_discriminator {
+        CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR |
+        CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR |
+        FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR |
+        FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR |
+        CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR |
+        CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR |
+        CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR |
+        CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: CreateNewDataStorageAccount");
+
+            // factory-created accounts are paid for (and signed) by the funding account alone, the authority doesn't have to sign
+            let factory = ix_discriminator == FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ||
+                ix_discriminator == FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR;
+            if factory {
+                verbose_log!("Factory");
+            };
+
+            // variable-label accounts are derived from the label's exact bytes, see '//? data storage account PDA's seeds'
+            let variable_label = ix_discriminator == CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR;
+            if variable_label {
+                verbose_log!("Variable label");
+            };
+
+            // the data-field is stored as-is, only the compressed flag is set
+            let compressed = ix_discriminator == CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR;
+            if compressed {
+                verbose_log!("Compressed");
+            };
+
+            // the data-field is an entry list, see '//? key-value entries'
+            let key_value = ix_discriminator == CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR;
+            if key_value {
+                verbose_log!("Key-value");
+            };
+
+            // an already existing account is left as-is, as long as it matches the instruction
+            let idempotent = ix_discriminator == CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR;
+            if idempotent {
+                verbose_log!("Idempotent");
+            };
+
+            let new_data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info =  next_account_info(accounts_info)?; 
+            let funding_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            // fail early with a clean error, instead of deep inside the system program's cpi
+            check_account_is_signer(funding_account_info)?;
+
+            if authority_account_info.key != &SYSTEM_PROGRAM_ID {
+                if !factory {
+                    check_account_is_signer(authority_account_info)?;
+                };
+                verbose_log!("Mutable");
+            } else {
+                verbose_log!("Immutable");
+            };
+
+            // validate instruction-data
+            let (
+                account_label_len,
+                ix_data
+            ) = if variable_label {
+                let (
+                    account_label_len,
+                    ix_data
+                ) = ix_data
+                    .split_first()
+                    .ok_or(DataStorageError::InvalidData)?;
+
+                (*account_label_len as usize, ix_data)
+            } else {
+                (MAX_LABEL_LENGTH, ix_data)
+            };
+            // 1. the label
+            if ix_data.len() < account_label_len {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+            let (
+                account_label,
+                ix_data
+            ) = ix_data.split_at(account_label_len);
+            // 2. max-size & expires-at, the legacy "CREATE_NEW_DATA_STORAGE_ACCOUNT" payload has neither and the legacy
+            //    "FACTORY_CREATE_DATA_STORAGE_ACCOUNT" one has no expires-at (missing ones are '0')
+            let has_max_size = ix_discriminator != CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR;
+            let has_expires_at = has_max_size && ix_discriminator != FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR;
+            let options_len = if has_max_size { size_of::<u32>() } else { 0 } + if has_expires_at { size_of::<i64>() } else { 0 };
+            if ix_data.len() < options_len {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // deserialize instruction's data
+            let (
+                account_max_size,
+                ix_data
+            ) = ix_data.split_at(if has_max_size { size_of::<u32>() } else { 0 });
+            let (
+                account_expires_at,
+                account_data
+            ) = ix_data.split_at(if has_expires_at { size_of::<i64>() } else { 0 });
+
+            let account_max_size = if has_max_size {
+                u32::from_le_bytes(account_max_size.try_into().unwrap())
+            } else {
+                0
+            };
+            let account_expires_at = if has_expires_at {
+                i64::from_le_bytes(account_expires_at.try_into().unwrap())
+            } else {
+                0
+            };
+
+            // 3. the data-field, whatever is left (possibly nothing) must fit the stored 'u16' data-length
+            check_data_length_fits_header(account_data.len())?;
+            // 4. a key-value account's data-field must be a well-formed entry list
+            if key_value && read_entries(account_data).is_err() {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+
+            // a retried idempotent create, checked before the config so the creation-fee isn't paid twice
+            if idempotent && new_data_storage_pda_account_info.owner == program_id {
+                check_dsa_account_is_not_tombstone(
+                    new_data_storage_pda_account_info,
+                    program_id
+                )?;
+
+                check_dsa_account_is_initialized(new_data_storage_pda_account_info)?;
+
+                let (
+                    dsa_address,
+                    _
+                ) = find_data_storage_pda(
+                    authority_account_info.key,
+                    account_label
+                        .try_into()
                         .unwrap(),
-                    account_data,
-                    account_data.len()
+                    program_id
+                );
+                if &dsa_address != new_data_storage_pda_account_info.key {
+                    return Err(
+                        ProgramError::InvalidSeeds
+                    );
+                };
+
+                let dsa_data = new_data_storage_pda_account_info
+                    .data
+                    .try_borrow()
+                    .unwrap();
+                let dsa_view = DataStorageAccountView(&dsa_data);
+                if &dsa_view.owner()? != authority_account_info.key || dsa_view.data()? != account_data {
+                    return Err(
+                        DataStorageError::DataMismatch.into()
+                    );
+                };
+
+                verbose_log!("Data storage account already exists, skipping.");
+
+                return Ok(());
+            };
+
+            // the config's limits & creation-fee (see '//? config')
+            let config_max_data_len = apply_dsa_config(
+                accounts_info,
+                funding_account_info,
+                1,
+                program_id,
+                cpi_accounts_info
+            )?;
+            if account_data.len() > config_max_data_len || account_max_size as usize > config_max_data_len {
+                return Err(
+                    DataStorageError::DataTooLarge.into()
+                );
+            };
+
+            let display_label = create_and_initialize_dsa_account(
+                new_data_storage_pda_account_info,
+                authority_account_info,
+                funding_account_info,
+                account_label,
+                variable_label,
+                compressed,
+                if key_value { ACCOUNT_KIND_KEY_VALUE } else { ACCOUNT_KIND_BYTES },
+                account_max_size,
+                account_expires_at,
+                account_data,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            let event = Events::NewDataStorageAccountCreated {
+                data_storage_account: *new_data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key,
+                account_label: display_label
+            };
+            emit!(event);
+
+            verbose_log!("New data storage account has been initialized successfully. ✅");
+        },
+
+        EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR |
+        EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR |
+        COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: EditDataStorageAccount");
+
+            // compare-and-swap edits carry the expected data-field hash instead of the edit-flags
+            let compare_and_swap = ix_discriminator == COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR;
+            if compare_and_swap {
+                verbose_log!("Compare and swap");
+            };
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_editor(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_bytes(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            // deserialize instruction's data
+            let (
+                expected_data_hash,
+                edit_flags,
+                ix_data
+            ) = if compare_and_swap {
+                let (
+                    expected_data_hash,
+                    ix_data
+                ) = ix_data
+                    .split_at_checked(size_of::<Hash>())
+                    .ok_or(DataStorageError::InvalidData)?;
+
+                (Some(expected_data_hash), &0, ix_data)
+            } else if ix_discriminator == EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR {
+                let (
+                    edit_flags,
+                    ix_data
+                ) = ix_data
+                    .split_first()
+                    .ok_or(DataStorageError::InvalidData)?;
+
+                (None, edit_flags, ix_data)
+            } else {
+                // the legacy "EDIT_DATA_STORAGE_ACCOUNT" payload is the raw data-field
+                (None, &0, ix_data)
+            };
+            if edit_flags & !(EDIT_FLAG_DISPLAY_LABEL | EDIT_FLAG_IDEMPOTENCY_KEY | EDIT_FLAG_EXPECTED_REVISION | EDIT_FLAG_COMPRESSED) != 0 {
+                return Err(
+                    DataStorageError::InvalidData.into()
                 );
             };
 
-            let event = Events::NewDataStorageAccountCreated {
-                data_storage_account: *new_data_storage_pda_account_info.key,
-                authority_account: *authority_account_info.key,
-                account_label: account_label
-                    .try_into()
-                    .unwrap()
-            };
-            emit!(event);
+            let (
+                display_label,
+                ix_data
+            ) = if edit_flags & EDIT_FLAG_DISPLAY_LABEL != 0 {
+                if ix_data.len() < MAX_LABEL_LENGTH {
+                    return Err(
+                        DataStorageError::InvalidData.into()
+                    );
+                };
+
+                let (
+                    display_label,
+                    ix_data
+                ) = ix_data.split_at(MAX_LABEL_LENGTH);
+
+                (Some(display_label), ix_data)
+            } else {
+                (None, ix_data)
+            };
+
+            let (
+                idempotency_key,
+                ix_data
+            ) = if edit_flags & EDIT_FLAG_IDEMPOTENCY_KEY != 0 {
+                if ix_data.len() < size_of::<u64>() {
+                    return Err(
+                        DataStorageError::InvalidData.into()
+                    );
+                };
+
+                let (
+                    idempotency_key,
+                    ix_data
+                ) = ix_data.split_at(size_of::<u64>());
+
+                (
+                    u64::from_le_bytes(
+                        idempotency_key
+                            .try_into()
+                            .unwrap()
+                    ),
+                    ix_data
+                )
+            } else {
+                (0, ix_data)
+            };
+
+            let (
+                expected_revision,
+                ix_data
+            ) = if edit_flags & EDIT_FLAG_EXPECTED_REVISION != 0 {
+                if ix_data.len() < size_of::<u32>() {
+                    return Err(
+                        DataStorageError::InvalidData.into()
+                    );
+                };
+
+                let (
+                    expected_revision,
+                    ix_data
+                ) = ix_data.split_at(size_of::<u32>());
+
+                (
+                    Some(
+                        u32::from_le_bytes(
+                            expected_revision
+                                .try_into()
+                                .unwrap()
+                        )
+                    ),
+                    ix_data
+                )
+            } else {
+                (None, ix_data)
+            };
+
+            // the stored data-length is a 'u16', so longer data-fields would be silently truncated
+            check_data_length_fits_header(ix_data.len())?;
+
+            check_data_length_above_minimum(ix_data.len())?;
+
+            // a retried edit (same non-zero idempotency-key as the last applied one) is a no-op
+            if idempotency_key != 0 && read_idempotency_key(data_storage_pda_account_info) == idempotency_key {
+                verbose_log!("Edit with this idempotency-key has already been applied, skipping.");
+
+                return Ok(());
+            };
+
+            // compare-and-swap, the edit was built against a revision that is no longer the current one
+            if let Some(expected_revision) = expected_revision {
+                if read_revision(data_storage_pda_account_info) != expected_revision {
+                    return Err(
+                        DataStorageError::RevisionMismatch.into()
+                    );
+                };
+            };
+
+            // compare-and-swap, the data-field has been modified since the client read it
+            if let Some(expected_data_hash) = expected_data_hash {
+                let data_hash = hash_dsa_data(
+                    &data_storage_pda_account_info
+                        .data
+                        .try_borrow()
+                        .unwrap()
+                )?;
+
+                if sol_memcmp(&data_hash, expected_data_hash, size_of::<Hash>()) != 0 {
+                    return Err(
+                        DataStorageError::HashMismatch.into()
+                    );
+                };
+            };
+
+            if idempotency_key != 0 {
+                sol_memcpy(
+                    data_storage_pda_account_info
+                        .data
+                        .try_borrow_mut()
+                        .unwrap()
+                        .get_mut(IDEMPOTENCY_KEY_OFFSET..IDEMPOTENCY_KEY_OFFSET + size_of::<u64>())
+                        .unwrap(),
+                    &idempotency_key.to_le_bytes(),
+                    size_of::<u64>()
+                );
+            };
+
+            // update data-storage account
+            // update 'display-label' field, the seed-label (and so the pda) stays the same
+            if let Some(display_label) = display_label {
+                check_account_label(display_label)?;
+
+                sol_memcpy(
+                    data_storage_pda_account_info
+                        .data
+                        .try_borrow_mut()
+                        .unwrap()
+                        .get_mut(DISPLAY_LABEL_OFFSET..DISPLAY_LABEL_OFFSET + MAX_LABEL_LENGTH)
+                        .unwrap(),
+                    display_label,
+                    MAX_LABEL_LENGTH
+                );
+            };
+
+            // update 'compressed' field, it describes the new data-field (compare-and-swap keeps the current encoding)
+            if !compare_and_swap {
+                *data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(COMPRESSED_OFFSET)
+                    .unwrap() = (edit_flags & EDIT_FLAG_COMPRESSED != 0) as u8;
+            };
+
+            // update 'last-updated' & 'has-been-edited' fields
+            let current_time = (Clock::get()?).unix_timestamp;
+            sol_memcpy(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &current_time.to_le_bytes(),
+                size_of::<i64>()
+            );
+            *data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap()
+                .get_mut(HAS_BEEN_EDITED_OFFSET)
+                .unwrap() = true as u8;
+
+            let new_revision = increment_dsa_revision(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+            )?;
+            set_dsa_last_editor(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap(),
+                authority_account_info.key
+            );
+
+            let old_data_length = read_data_len(data_storage_pda_account_info)?;
+
+            let new_data_length = ix_data.len();
+
+            write_dsa_data_field(
+                data_storage_pda_account_info,
+                accounts_info,
+                old_data_length,
+                ix_data,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            update_dsa_data_hash(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+            )?;
+
+            let event = Events::DataStorageAccountEdited {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key,
+                old_data_len: old_data_length,
+                new_data_len: new_data_length,
+                new_revision,
+                last_editor: *authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been updated successfully. ✅");
+        },
+        
+        RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: ReserveDataStorageAccountCapacity");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let funding_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+            let funding_ledger_account_info = next_account_info(accounts_info).ok();
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            // deserialize instruction's data
+            let additional_capacity = u16::from_le_bytes(
+                ix_data
+                    .try_into()
+                    .map_err(|_| DataStorageError::InvalidData)?
+            ) as usize;
+
+            let old_capacity = read_data_capacity(data_storage_pda_account_info);
+            let new_capacity = old_capacity
+                .checked_add(additional_capacity)
+                .unwrap();
+
+            // validate new capacity against the account's quota
+            check_data_length_within_quota(
+                new_capacity,
+                read_max_size(data_storage_pda_account_info)
+            )?;
+
+            grow_dsa_capacity(
+                data_storage_pda_account_info,
+                funding_account_info,
+                funding_ledger_account_info,
+                new_capacity,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            let event = Events::DataStorageAccountCapacityReserved {
+                data_storage_account: *data_storage_pda_account_info.key,
+                added_capacity: additional_capacity,
+                total_capacity: new_capacity
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's capacity has been reserved successfully. ✅");
+        },
+
+        APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: AppendDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_editor(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_bytes(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            let old_data_length = read_data_len(data_storage_pda_account_info)?;
+            let new_data_length = old_data_length
+                .checked_add(ix_data.len())
+                .unwrap();
+
+            // the stored data-length is a 'u16', so longer data-fields would be silently truncated
+            check_data_length_fits_header(new_data_length)?;
+
+            // validate new data-length against the account's quota
+            check_data_length_within_quota(
+                new_data_length,
+                read_max_size(data_storage_pda_account_info)
+            )?;
+
+            // use the reserved capacity first, only grow the account when the appended data doesn't fit in it
+            if new_data_length > read_data_capacity(data_storage_pda_account_info) {
+                let funding_account_info = next_account_info(accounts_info)?;
+                let system_program_account_info = next_account_info(accounts_info)?;
+
+                check_system_program_account(system_program_account_info.key)?;
+
+                let funding_ledger_account_info = next_account_info(accounts_info)
+                    .ok()
+                    .filter(|account_info| !check_instructions_sysvar_id(account_info.key));
+
+                grow_dsa_capacity(
+                    data_storage_pda_account_info,
+                    funding_account_info,
+                    funding_ledger_account_info,
+                    new_data_length,
+                    program_id,
+                    cpi_accounts_info
+                )?;
+            };
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+
+            // update 'last-updated' & 'has-been-edited' fields
+            let current_time = (Clock::get()?).unix_timestamp;
+            sol_memcpy(
+                dsa_data
+                    .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &current_time.to_le_bytes(),
+                size_of::<i64>()
+            );
+            *dsa_data
+                .get_mut(HAS_BEEN_EDITED_OFFSET)
+                .unwrap() = true as u8;
+
+            let new_revision = increment_dsa_revision(&mut dsa_data)?;
+            set_dsa_last_editor(&mut dsa_data, authority_account_info.key);
+
+            // write new data-length
+            sol_memcpy(
+                dsa_data
+                    .get_mut(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
+                    .unwrap(),
+                &u16::to_le_bytes(new_data_length as u16),
+                size_of::<u16>()
+            );
+
+            // append new data
+            sol_memcpy(
+                dsa_data
+                    .get_mut(DATA_OFFSET + old_data_length..)
+                    .unwrap(),
+                ix_data,
+                ix_data.len()
+            );
+
+            update_dsa_data_hash(&mut dsa_data)?;
+
+            drop(dsa_data);
+
+            let event = Events::DataStorageAccountEdited {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key,
+                old_data_len: old_data_length,
+                new_data_len: new_data_length,
+                new_revision,
+                last_editor: *authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data has been appended to the data storage account successfully. ✅");
+        },
+
+        LOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: LockDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let signer_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_lock_manager(
+                data_storage_pda_account_info,
+                signer_account_info,
+                program_id
+            )?;
+
+            // validate & deserialize instruction's data
+            if ix_data.len() != size_of::<Pubkey>() + size_of::<i64>() {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            let (
+                lock_holder,
+                lock_expiry
+            ) = ix_data.split_at(size_of::<Pubkey>());
+            let lock_holder = Pubkey::new_from_array(
+                lock_holder
+                    .try_into()
+                    .unwrap()
+            );
+            let lock_expiry = i64::from_le_bytes(
+                lock_expiry
+                    .try_into()
+                    .unwrap()
+            );
+
+            let current_time = (Clock::get()?).unix_timestamp;
+            if lock_holder == Pubkey::default() || lock_expiry <= current_time {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+
+            // 1. set lock-holder
+            sol_memcpy(
+                dsa_data
+                    .get_mut(LOCK_HOLDER_OFFSET..LOCK_HOLDER_OFFSET + size_of::<Pubkey>())
+                    .unwrap(),
+                lock_holder.as_ref(),
+                size_of::<Pubkey>()
+            );
+            // 2. set lock-expiry
+            sol_memcpy(
+                dsa_data
+                    .get_mut(LOCK_EXPIRY_OFFSET..LOCK_EXPIRY_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &lock_expiry.to_le_bytes(),
+                size_of::<i64>()
+            );
+
+            drop(dsa_data);
+
+            let event = Events::DataStorageAccountLocked {
+                data_storage_account: *data_storage_pda_account_info.key,
+                lock_holder,
+                lock_expiry
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been locked successfully. ✅");
+        },
+
+        UNLOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: UnlockDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let signer_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_lock_manager(
+                data_storage_pda_account_info,
+                signer_account_info,
+                program_id
+            )?;
+
+            // clear lock-holder & lock-expiry
+            sol_memset(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(LOCK_HOLDER_OFFSET..DISPLAY_LABEL_OFFSET)
+                    .unwrap(),
+                0,
+                size_of::<Pubkey>() + size_of::<i64>()
+            );
+
+            let event = Events::DataStorageAccountUnlocked {
+                data_storage_account: *data_storage_pda_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been unlocked successfully. ✅");
+        },
+
+        CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: CloseDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let rent_receiver_account_info = next_account_info(accounts_info)?;
+            let excess_receiver_account_info = next_account_info(accounts_info).ok();
+            let patch_log_account_info = next_account_info(accounts_info).ok();
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_not_chunked(data_storage_pda_account_info)?;
+
+            if let Some(patch_log_account_info) = patch_log_account_info {
+                close_patch_log_account(
+                    patch_log_account_info,
+                    data_storage_pda_account_info,
+                    rent_receiver_account_info,
+                    program_id
+                )?;
+            };
+
+            close_dsa_account(
+                data_storage_pda_account_info,
+                rent_receiver_account_info,
+                excess_receiver_account_info
+            )?;
+
+            let event = Events::DataStorageAccountClosed {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been closed successfully. ✅");
+        },
+        DUPLICATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: DuplicateDataStorageAccount");
+
+            let source_data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let new_data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let funding_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            check_dsa_account_and_authority(
+                source_data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_chunked(source_data_storage_pda_account_info)?;
+
+            // validate instruction-data
+            if ix_data.len() != MAX_LABEL_LENGTH {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+            let account_label = ix_data;
+
+            // validate label
+            check_account_label(account_label)?;
+
+            // reject an already existing destination account
+            if new_data_storage_pda_account_info.owner != &SYSTEM_PROGRAM_ID || !new_data_storage_pda_account_info.data_is_empty() {
+                return Err(
+                    ProgramError::AccountAlreadyInitialized
+                );
+            };
+
+            // get pda's bump and validate the pda's pubkey
+            let (
+                dsa_address,
+                dsa_bump
+            ) = Pubkey::try_find_program_address(
+                &[
+                    DATA_STORAGE_SEED_PREFIX,
+                    authority_account_info.key.as_ref(),
+                    account_label
+                ],
+                program_id
+            ).ok_or(DataStorageError::FailedToFindProgramAddress)?;
+            if &dsa_address != new_data_storage_pda_account_info.key {
+                return Err(
+                    ProgramError::InvalidSeeds
+                );
+            };
+
+            check_dsa_label_is_unused(
+                new_data_storage_pda_account_info,
+                program_id
+            )?;
+
+            // the config's limits & creation-fee, the copy's data-field & max-size included (see '//? config')
+            let config_max_data_len = apply_dsa_config(
+                accounts_info,
+                funding_account_info,
+                1,
+                program_id,
+                cpi_accounts_info
+            )?;
+            let patch_log_account_info = next_account_info(accounts_info).ok();
+
+            let source_data_len = read_data_len(source_data_storage_pda_account_info)?;
+            if source_data_len > config_max_data_len || read_max_size(source_data_storage_pda_account_info) as usize > config_max_data_len {
+                return Err(
+                    DataStorageError::DataTooLarge.into()
+                );
+            };
+
+            // create the account
+            let account_size = total_account_size(source_data_len);
+
+            let seeds: &[&[u8]] = &[
+                DATA_STORAGE_SEED_PREFIX,
+                authority_account_info.key.as_ref(),
+                account_label,
+                &[ dsa_bump ]
+            ];
+
+            // make sure the stored bump re-creates the same address, edit & close rely on it
+            create_and_check_program_address(
+                seeds,
+                program_id,
+                new_data_storage_pda_account_info.key
+            )?;
+
+            create_pda_account(
+                new_data_storage_pda_account_info,
+                funding_account_info,
+                account_size,
+                program_id,
+                seeds,
+                cpi_accounts_info
+            )?;
+            verbose_log!("New data storage account created.");
+
+            // initialize the account
+            // 1. copy the source account's header and data-field
+            let source_dsa_data = source_data_storage_pda_account_info
+                .data
+                .try_borrow()
+                .unwrap();
+            let mut new_dsa_data = new_data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            sol_memcpy(
+                &mut new_dsa_data,
+                source_dsa_data
+                    .get(..account_size)
+                    .unwrap(),
+                account_size
+            );
+            // 2. set account-label
+            sol_memcpy(
+                new_dsa_data
+                    .get_mut(LABEL_OFFSET..LABEL_OFFSET + MAX_LABEL_LENGTH)
+                    .unwrap(),
+                account_label,
+                size_of::<[u8; 30]>()
+            );
+            // 3. reset 'last-updated'
+            sol_memcpy(
+                new_dsa_data
+                    .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &i64::to_le_bytes(0),
+                size_of::<i64>()
+            );
+            // 4. set account-bump
+            *new_dsa_data
+                .get_mut(BUMP_OFFSET)
+                .unwrap() = dsa_bump;
+            // 5. the source account's lock isn't carried over
+            sol_memset(
+                new_dsa_data
+                    .get_mut(LOCK_HOLDER_OFFSET..DISPLAY_LABEL_OFFSET)
+                    .unwrap(),
+                0,
+                size_of::<Pubkey>() + size_of::<i64>()
+            );
+            // 6. set display-label
+            sol_memcpy(
+                new_dsa_data
+                    .get_mut(DISPLAY_LABEL_OFFSET..DISPLAY_LABEL_OFFSET + MAX_LABEL_LENGTH)
+                    .unwrap(),
+                account_label,
+                MAX_LABEL_LENGTH
+            );
+            // 7. reset the last applied idempotency-key
+            sol_memset(
+                new_dsa_data
+                    .get_mut(IDEMPOTENCY_KEY_OFFSET..IDEMPOTENCY_KEY_OFFSET + size_of::<u64>())
+                    .unwrap(),
+                0,
+                size_of::<u64>()
+            );
+            // 8. the copy hasn't been edited yet
+            *new_dsa_data
+                .get_mut(HAS_BEEN_EDITED_OFFSET)
+                .unwrap() = false as u8;
+            // 9. the copy is a new account
+            sol_memcpy(
+                new_dsa_data
+                    .get_mut(CREATED_AT_OFFSET..CREATED_AT_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &(Clock::get()?).unix_timestamp.to_le_bytes(),
+                size_of::<i64>()
+            );
+            // 10. and starts at revision '0'
+            sol_memset(
+                new_dsa_data
+                    .get_mut(REVISION_OFFSET..REVISION_OFFSET + size_of::<u32>())
+                    .unwrap(),
+                0,
+                size_of::<u32>()
+            );
+            // 11. without a delegate
+            sol_memset(
+                new_dsa_data
+                    .get_mut(DELEGATE_OFFSET..DELEGATE_OFFSET + size_of::<Pubkey>())
+                    .unwrap(),
+                0,
+                size_of::<Pubkey>()
+            );
+            // 12. last edited (created) by it's authority
+            set_dsa_last_editor(&mut new_dsa_data, authority_account_info.key);
+            // 13. the copy's label is a (fixed) seed-label, even when the source's is a variable one
+            sol_memset(
+                new_dsa_data
+                    .get_mut(LAYOUT_VERSION_OFFSET..TOMBSTONE_OFFSET)
+                    .unwrap(),
+                0,
+                2 * size_of::<u8>() + MAX_VARIABLE_LABEL_LENGTH
+            );
+            // 14. fold the source's pending patches into the copy
+            if let Some(patch_log_account_info) = patch_log_account_info {
+                check_patch_log_address(
+                    patch_log_account_info,
+                    source_data_storage_pda_account_info,
+                    program_id
+                )?;
+
+                if patch_log_account_info.owner == program_id {
+                    let patch_log_data = patch_log_account_info
+                        .data
+                        .try_borrow()
+                        .unwrap();
+
+                    let (
+                        head,
+                        count
+                    ) = read_pending_patches(&patch_log_data, &source_dsa_data)?;
+
+                    fold_patch_log_entries(
+                        new_dsa_data
+                            .get_mut(DATA_OFFSET..DATA_OFFSET + source_data_len)
+                            .unwrap(),
+                        &patch_log_data,
+                        head,
+                        count
+                    );
+                } else if patch_log_account_info.owner != &SYSTEM_PROGRAM_ID {
+                    return Err(
+                        ProgramError::IllegalOwner
+                    );
+                };
+
+                update_dsa_data_hash(&mut new_dsa_data)?;
+            };
+
+            drop(new_dsa_data);
+            drop(source_dsa_data);
+
+            let event = Events::DataStorageAccountDuplicated {
+                source_data_storage_account: *source_data_storage_pda_account_info.key,
+                data_storage_account: *new_data_storage_pda_account_info.key,
+                account_label: account_label
+                    .try_into()
+                    .unwrap()
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been duplicated successfully. ✅");
+        },
+        LOG_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: LogDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+
+            // validate account's owner-program
+            check_dsa_account_owner_or_legacy(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            // check that account is initialized
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            let label = read_display_label(data_storage_pda_account_info);
+            msg!(
+                "dsa authority={} label={} len={} updated={} edited={}",
+                read_authority(data_storage_pda_account_info),
+                core::str::from_utf8(&label)
+                    .unwrap_or_default()
+                    .trim_end_matches('\0'),
+                read_data_len(data_storage_pda_account_info)?,
+                read_last_updated(data_storage_pda_account_info),
+                read_has_been_edited(data_storage_pda_account_info)
+            );
+        },
+        SELF_TEST_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: SelfTest");
+
+            // (field, start, end) of each header field, from the offset constants & the fields' sizes
+            let header_layout: [(&str, usize, usize); 29] = [
+                ("authority", OWNER_OFFSET, OWNER_OFFSET + size_of::<Pubkey>()),
+                ("label", LABEL_OFFSET, LABEL_OFFSET + MAX_LABEL_LENGTH),
+                ("last_updated", LAST_UPDATED_OFFSET, LAST_UPDATED_OFFSET + size_of::<i64>()),
+                ("bump", BUMP_OFFSET, BUMP_OFFSET + size_of::<u8>()),
+                ("is_initialized", INIT_OFFSET, INIT_OFFSET + size_of::<bool>()),
+                ("data_len", DATA_LEN_OFFSET, DATA_LEN_OFFSET + size_of::<u16>()),
+                ("max_size", MAX_SIZE_OFFSET, MAX_SIZE_OFFSET + size_of::<u32>()),
+                ("lock_holder", LOCK_HOLDER_OFFSET, LOCK_HOLDER_OFFSET + size_of::<Pubkey>()),
+                ("lock_expiry", LOCK_EXPIRY_OFFSET, LOCK_EXPIRY_OFFSET + size_of::<i64>()),
+                ("display_label", DISPLAY_LABEL_OFFSET, DISPLAY_LABEL_OFFSET + MAX_LABEL_LENGTH),
+                ("idempotency_key", IDEMPOTENCY_KEY_OFFSET, IDEMPOTENCY_KEY_OFFSET + size_of::<u64>()),
+                ("cpi_allowlist", CPI_ALLOWLIST_OFFSET, CPI_ALLOWLIST_OFFSET + MAX_CPI_ALLOWLIST_LEN * size_of::<Pubkey>()),
+                ("has_been_edited", HAS_BEEN_EDITED_OFFSET, HAS_BEEN_EDITED_OFFSET + size_of::<bool>()),
+                ("created_at", CREATED_AT_OFFSET, CREATED_AT_OFFSET + size_of::<i64>()),
+                ("revision", REVISION_OFFSET, REVISION_OFFSET + size_of::<u32>()),
+                ("data_hash", DATA_HASH_OFFSET, DATA_HASH_OFFSET + size_of::<Hash>()),
+                ("delegate", DELEGATE_OFFSET, DELEGATE_OFFSET + size_of::<Pubkey>()),
+                ("expires_at", EXPIRES_AT_OFFSET, EXPIRES_AT_OFFSET + size_of::<i64>()),
+                ("last_editor", LAST_EDITOR_OFFSET, LAST_EDITOR_OFFSET + size_of::<Pubkey>()),
+                ("layout_version", LAYOUT_VERSION_OFFSET, LAYOUT_VERSION_OFFSET + size_of::<u8>()),
+                ("variable_label_len", VARIABLE_LABEL_LEN_OFFSET, VARIABLE_LABEL_LEN_OFFSET + size_of::<u8>()),
+                ("variable_label", VARIABLE_LABEL_OFFSET, VARIABLE_LABEL_OFFSET + MAX_VARIABLE_LABEL_LENGTH),
+                ("tombstone", TOMBSTONE_OFFSET, TOMBSTONE_OFFSET + size_of::<bool>()),
+                ("closed_at", CLOSED_AT_OFFSET, CLOSED_AT_OFFSET + size_of::<i64>()),
+                ("header_version", HEADER_VERSION_OFFSET, HEADER_VERSION_OFFSET + size_of::<u8>()),
+                ("compressed", COMPRESSED_OFFSET, COMPRESSED_OFFSET + size_of::<bool>()),
+                ("kind", KIND_OFFSET, KIND_OFFSET + size_of::<u8>()),
+                ("lock_until", LOCK_UNTIL_OFFSET, LOCK_UNTIL_OFFSET + size_of::<i64>()),
+                ("account_discriminator", ACCOUNT_DISCRIMINATOR_OFFSET, ACCOUNT_DISCRIMINATOR_OFFSET + size_of::<u8>())
+            ];
+
+            let mut header_end = 0;
+            for (field, start, end) in header_layout {
+                if start != header_end || end <= start {
+                    msg!("Self-test failed: '{}' ({}..{}) is not contiguous with the previous field", field, start, end);
+                    return Err(
+                        ProgramError::InvalidAccountData
+                    );
+                };
+
+                header_end = end;
+            }
+
+            if header_end != HEADER_LEN {
+                msg!("Self-test failed: header ends at {} but HEADER_LEN is {}", header_end, HEADER_LEN);
+                return Err(
+                    ProgramError::InvalidAccountData
+                );
+            };
+
+            sol_log("Self-test passed. ✅");
+        },
+        TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: TransferDataStorageAccountAuthority");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let new_authority_account_info = next_account_info(accounts_info)?;
+            let new_data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+            let patch_log_account_info = next_account_info(accounts_info).ok();
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            check_dsa_account_is_not_chunked(data_storage_pda_account_info)?;
+
+            // transferring to the same authority would re-derive the same pda
+            if new_authority_account_info.key == authority_account_info.key {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // the patch-log is derived from the old pda, fold the pending patches before moving
+            if let Some(patch_log_account_info) = patch_log_account_info {
+                let rent_receiver_account_info = next_required_account_info(accounts_info, DataStorageError::MissingRentReceiver)?;
+
+                compact_and_close_patch_log_account(
+                    patch_log_account_info,
+                    data_storage_pda_account_info,
+                    rent_receiver_account_info,
+                    program_id
+                )?;
+            };
+
+            migrate_dsa_account(
+                data_storage_pda_account_info,
+                new_data_storage_pda_account_info,
+                new_authority_account_info.key,
+                program_id
+            )?;
+
+            let event = Events::DataStorageAccountAuthorityTransferred {
+                old_data_storage_account: *data_storage_pda_account_info.key,
+                new_data_storage_account: *new_data_storage_pda_account_info.key,
+                old_authority_account: *authority_account_info.key,
+                new_authority_account: *new_authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's authority has been transferred successfully. ✅");
+        },
+        SEAL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: SealDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let sealed_data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+            let patch_log_account_info = next_account_info(accounts_info).ok();
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            check_dsa_account_is_not_chunked(data_storage_pda_account_info)?;
+
+            // the patch-log is derived from the old pda, fold the pending patches before moving
+            if let Some(patch_log_account_info) = patch_log_account_info {
+                let rent_receiver_account_info = next_required_account_info(accounts_info, DataStorageError::MissingRentReceiver)?;
+
+                compact_and_close_patch_log_account(
+                    patch_log_account_info,
+                    data_storage_pda_account_info,
+                    rent_receiver_account_info,
+                    program_id
+                )?;
+            };
+
+            migrate_dsa_account(
+                data_storage_pda_account_info,
+                sealed_data_storage_pda_account_info,
+                &SYSTEM_PROGRAM_ID,
+                program_id
+            )?;
+
+            let event = Events::DataStorageAccountSealed {
+                old_data_storage_account: *data_storage_pda_account_info.key,
+                data_storage_account: *sealed_data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been sealed successfully. ✅");
+        },
+        COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: CompareAndCloseDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let rent_receiver_account_info = next_account_info(accounts_info)?;
+            let excess_receiver_account_info = next_account_info(accounts_info).ok();
+            let patch_log_account_info = next_account_info(accounts_info).ok();
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_not_chunked(data_storage_pda_account_info)?;
+
+            // deserialize instruction's data
+            let expected_data_hash: [u8; 32] = ix_data
+                .try_into()
+                .map_err(|_| DataStorageError::InvalidData)?;
+
+            // compare the current data-field's hash with the expected one
+            let data_length = read_data_len(data_storage_pda_account_info)?;
+            let data_hash = keccak_hash(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow()
+                    .unwrap()
+                    .get(DATA_OFFSET..DATA_OFFSET + data_length)
+                    .unwrap()
+            );
+            if data_hash.to_bytes() != expected_data_hash {
+                return Err(
+                    DataStorageError::ConcurrentModification.into()
+                );
+            };
+
+            if let Some(patch_log_account_info) = patch_log_account_info {
+                close_patch_log_account(
+                    patch_log_account_info,
+                    data_storage_pda_account_info,
+                    rent_receiver_account_info,
+                    program_id
+                )?;
+            };
+
+            close_dsa_account(
+                data_storage_pda_account_info,
+                rent_receiver_account_info,
+                excess_receiver_account_info
+            )?;
+
+            let event = Events::DataStorageAccountClosed {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been closed successfully. ✅");
+        },
+        TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: TruncateDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let rent_receiver_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_editor(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_bytes(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            // deserialize instruction's data
+            let new_data_length = u16::from_le_bytes(
+                ix_data
+                    .try_into()
+                    .map_err(|_| DataStorageError::InvalidData)?
+            ) as usize;
+
+            let old_data_length = read_data_len(data_storage_pda_account_info)?;
+            if new_data_length > old_data_length {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            check_data_length_above_minimum(new_data_length)?;
+
+            // update 'last-updated' & 'has-been-edited' fields
+            let current_time = (Clock::get()?).unix_timestamp;
+            sol_memcpy(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &current_time.to_le_bytes(),
+                size_of::<i64>()
+            );
+            *data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap()
+                .get_mut(HAS_BEEN_EDITED_OFFSET)
+                .unwrap() = true as u8;
+
+            let new_revision = increment_dsa_revision(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+            )?;
+            set_dsa_last_editor(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap(),
+                authority_account_info.key
+            );
+
+            if new_data_length < old_data_length {
+                // write new data-length
+                sol_memcpy(
+                    data_storage_pda_account_info
+                        .data
+                        .try_borrow_mut()
+                        .unwrap()
+                        .get_mut(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
+                        .unwrap(),
+                    &u16::to_le_bytes(new_data_length as u16),
+                    size_of::<u16>()
+                );
+
+                shrink_dsa_capacity(
+                    data_storage_pda_account_info,
+                    new_data_length,
+                    old_data_length
+                )?;
+
+                refund_freed_rent_exempt_lamports(
+                    data_storage_pda_account_info,
+                    rent_receiver_account_info,
+                    new_data_length,
+                    old_data_length
+                )?;
+            };
+
+            update_dsa_data_hash(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+            )?;
+
+            let event = Events::DataStorageAccountEdited {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key,
+                old_data_len: old_data_length,
+                new_data_len: new_data_length,
+                new_revision,
+                last_editor: *authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been truncated successfully. ✅");
+        },
+
+        PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: PatchDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_editor(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_bytes(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            // deserialize instruction's data
+            let (
+                offset,
+                patch
+            ) = ix_data
+                .split_first_chunk::<2>()
+                .ok_or(DataStorageError::InvalidData)?;
+            let offset = u16::from_le_bytes(*offset) as usize;
+
+            // patches can't grow the data-field
+            let data_length = read_data_len(data_storage_pda_account_info)?;
+            if offset
+                .checked_add(patch.len())
+                .unwrap() > data_length
+            {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+
+            // update 'last-updated' & 'has-been-edited' fields
+            let current_time = (Clock::get()?).unix_timestamp;
+            sol_memcpy(
+                dsa_data
+                    .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &current_time.to_le_bytes(),
+                size_of::<i64>()
+            );
+            *dsa_data
+                .get_mut(HAS_BEEN_EDITED_OFFSET)
+                .unwrap() = true as u8;
+
+            increment_dsa_revision(&mut dsa_data)?;
+            set_dsa_last_editor(&mut dsa_data, authority_account_info.key);
+
+            // write the patch
+            sol_memcpy(
+                dsa_data
+                    .get_mut(DATA_OFFSET + offset..)
+                    .unwrap(),
+                patch,
+                patch.len()
+            );
+
+            update_dsa_data_hash(&mut dsa_data)?;
+
+            let event = Events::DataStorageAccountPatched {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key,
+                offset,
+                len: patch.len()
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been patched successfully. ✅");
+        },
+
+        SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: SetDataStorageAccountCpiAllowlist");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            // deserialize instruction's data
+            if ix_data.len() % size_of::<Pubkey>() != 0 || ix_data.len() > MAX_CPI_ALLOWLIST_LEN * size_of::<Pubkey>() {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            let mut cpi_allowlist = [Pubkey::default(); MAX_CPI_ALLOWLIST_LEN];
+            for (allowed_program, program_key) in cpi_allowlist
+                .iter_mut()
+                .zip(ix_data.chunks_exact(size_of::<Pubkey>()))
+            {
+                *allowed_program = Pubkey::new_from_array(program_key.try_into().unwrap());
+
+                // the program itself is the direct caller, allowing it would let anyone bypass the allowlist
+                if allowed_program == program_id {
+                    return Err(
+                        DataStorageError::InvalidData.into()
+                    );
+                };
+            };
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+
+            sol_memset(
+                dsa_data
+                    .get_mut(CPI_ALLOWLIST_OFFSET..CPI_ALLOWLIST_OFFSET + MAX_CPI_ALLOWLIST_LEN * size_of::<Pubkey>())
+                    .unwrap(),
+                0,
+                MAX_CPI_ALLOWLIST_LEN * size_of::<Pubkey>()
+            );
+            sol_memcpy(
+                dsa_data
+                    .get_mut(CPI_ALLOWLIST_OFFSET..CPI_ALLOWLIST_OFFSET + MAX_CPI_ALLOWLIST_LEN * size_of::<Pubkey>())
+                    .unwrap(),
+                ix_data,
+                ix_data.len()
+            );
+
+            drop(dsa_data);
+
+            let event = Events::DataStorageAccountCpiAllowlistSet {
+                data_storage_account: *data_storage_pda_account_info.key,
+                cpi_allowlist
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's cpi-allowlist has been set successfully. ✅");
+        },
+
+        REPAIR_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: RepairDataStorageAccountAuthority");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let original_authority_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_owner(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            // nothing to repair while the stored authority, label & bump still derive the pda
+            if check_dsa_account_address(data_storage_pda_account_info, program_id).is_ok() {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // SYSTEM_PROGRAM can't sign, restoring it only makes the account immutable again
+            if original_authority_account_info.key != &SYSTEM_PROGRAM_ID {
+                check_account_is_signer(original_authority_account_info)?;
+            };
+
+            // the pda is derived from the authority, so it proves which authority is the original one
+            let label_seed = read_label_seed(data_storage_pda_account_info)?;
+            let [
+                label_seed_0,
+                label_seed_1,
+                label_seed_2
+            ] = split_label_seed(&label_seed);
+            let (
+                dsa_address,
+                dsa_bump
+            ) = Pubkey::try_find_program_address(
+                &[
+                    DATA_STORAGE_SEED_PREFIX,
+                    original_authority_account_info.key.as_ref(),
+                    label_seed_0,
+                    label_seed_1,
+                    label_seed_2
+                ],
+                program_id
+            ).ok_or(DataStorageError::FailedToFindProgramAddress)?;
+            if &dsa_address != data_storage_pda_account_info.key {
+                return Err(
+                    ProgramError::InvalidSeeds
+                );
+            };
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+
+            // restore authority & bump
+            sol_memcpy(
+                dsa_data
+                    .get_mut(OWNER_OFFSET..OWNER_OFFSET + size_of::<Pubkey>())
+                    .unwrap(),
+                original_authority_account_info.key.as_ref(),
+                size_of::<Pubkey>()
+            );
+            *dsa_data
+                .get_mut(BUMP_OFFSET)
+                .unwrap() = dsa_bump;
+
+            drop(dsa_data);
+
+            let event = Events::DataStorageAccountAuthorityRepaired {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *original_authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's authority has been repaired successfully. ✅");
+        },
+
+        IS_DATA_STORAGE_ACCOUNT_IMMUTABLE_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: IsDataStorageAccountImmutable");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_owner_or_legacy(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            let is_immutable = read_authority(data_storage_pda_account_info) == SYSTEM_PROGRAM_ID;
+
+            set_return_data(&[ is_immutable as u8 ]);
+        },
+
+        VERIFY_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: VerifyDataStorageAccountData");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_owner_or_legacy(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_header_len(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            let dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow()
+                .unwrap();
+
+            if hash_dsa_data(&dsa_data)? != *dsa_data.get(DATA_HASH_OFFSET..DATA_HASH_OFFSET + size_of::<Hash>()).unwrap() {
+                return Err(
+                    DataStorageError::HashMismatch.into()
+                );
+            };
+
+            verbose_log!("Data storage account's data-field matches it's hash. ✅");
+        },
+
+        APPEND_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: AppendDataStorageAccountPatchLog");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let patch_log_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_editor(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_bytes(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            // deserialize instruction's data
+            let (
+                offset,
+                patch
+            ) = ix_data
+                .split_first_chunk::<2>()
+                .ok_or(DataStorageError::InvalidData)?;
+            let offset = u16::from_le_bytes(*offset) as usize;
+
+            if patch.is_empty() || patch.len() > MAX_PATCH_LOG_ENTRY_DATA_LEN {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // patches can't grow the data-field
+            let data_length = read_data_len(data_storage_pda_account_info)?;
+            if offset
+                .checked_add(patch.len())
+                .unwrap() > data_length
+            {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            let patch_log_bump = check_patch_log_address(
+                patch_log_account_info,
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            // the first patch creates the patch-log pda
+            if patch_log_account_info.owner == &SYSTEM_PROGRAM_ID {
+                let funding_account_info = next_account_info(accounts_info)?;
+                let system_program_account_info = next_account_info(accounts_info)?;
+
+                check_system_program_account(system_program_account_info.key)?;
+
+                create_pda_account(
+                    patch_log_account_info,
+                    funding_account_info,
+                    PATCH_LOG_LEN,
+                    program_id,
+                    &[
+                        PATCH_LOG_SEED_PREFIX,
+                        data_storage_pda_account_info.key.as_ref(),
+                        &[ patch_log_bump ]
+                    ],
+                    cpi_accounts_info
+                )?;
+            } else if patch_log_account_info.owner != program_id {
+                return Err(
+                    ProgramError::IllegalOwner
+                );
+            };
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            let mut patch_log_data = patch_log_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+
+            let (
+                mut head,
+                mut count
+            ) = read_pending_patches(&patch_log_data, &dsa_data)?;
+
+            // the ring is full, fold the oldest patch into the base to make room
+            if count == PATCH_LOG_CAPACITY {
+                apply_patch_log_entry(
+                    dsa_data
+                        .get_mut(DATA_OFFSET..DATA_OFFSET + data_length)
+                        .unwrap(),
+                    patch_log_entry(&patch_log_data, head)
+                );
+                update_dsa_data_hash(&mut dsa_data)?;
+
+                head = (head + 1) % PATCH_LOG_CAPACITY;
+                count -= 1;
+            };
+
+            // queue the patch
+            let queued_patch_log_entry = patch_log_entry_mut(
+                &mut patch_log_data,
+                (head + count) % PATCH_LOG_CAPACITY
+            );
+            sol_memset(
+                queued_patch_log_entry,
+                0,
+                PATCH_LOG_ENTRY_LEN
+            );
+            sol_memcpy(
+                queued_patch_log_entry
+                    .get_mut(..2)
+                    .unwrap(),
+                &(offset as u16).to_le_bytes(),
+                size_of::<u16>()
+            );
+            *queued_patch_log_entry
+                .get_mut(2)
+                .unwrap() = patch.len() as u8;
+            sol_memcpy(
+                queued_patch_log_entry
+                    .get_mut(3..)
+                    .unwrap(),
+                patch,
+                patch.len()
+            );
+            count += 1;
+
+            *patch_log_data
+                .get_mut(0)
+                .unwrap() = head as u8;
+            *patch_log_data
+                .get_mut(1)
+                .unwrap() = count as u8;
+
+            // update 'last-updated' & 'has-been-edited' fields
+            let current_time = (Clock::get()?).unix_timestamp;
+            sol_memcpy(
+                dsa_data
+                    .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &current_time.to_le_bytes(),
+                size_of::<i64>()
+            );
+            *dsa_data
+                .get_mut(HAS_BEEN_EDITED_OFFSET)
+                .unwrap() = true as u8;
+
+            increment_dsa_revision(&mut dsa_data)?;
+            set_dsa_last_editor(&mut dsa_data, authority_account_info.key);
+
+            // the patches are pending on the account as it is now
+            sol_memcpy(
+                patch_log_data
+                    .get_mut(PATCH_LOG_BASE_OFFSET..PATCH_LOG_LEN)
+                    .unwrap(),
+                &patch_log_base(&dsa_data),
+                PATCH_LOG_BASE_LEN
+            );
+
+            let event = Events::DataStorageAccountPatched {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key,
+                offset,
+                len: patch.len()
+            };
+            emit!(event);
+
+            verbose_log!("Patch has been appended to the data storage account's patch-log successfully. ✅");
+        },
+
+        COMPACT_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: CompactDataStorageAccountPatchLog");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let patch_log_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_editor(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_bytes(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            check_patch_log_address(
+                patch_log_account_info,
+                data_storage_pda_account_info,
+                program_id
+            )?;
+            if patch_log_account_info.owner != program_id {
+                return Err(
+                    ProgramError::IllegalOwner
+                );
+            };
+
+            let data_length = read_data_len(data_storage_pda_account_info)?;
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            let mut patch_log_data = patch_log_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+
+            let (
+                head,
+                count
+            ) = read_pending_patches(&patch_log_data, &dsa_data)?;
+
+            // fold the pending patches into the base, oldest first
+            fold_patch_log_entries(
+                dsa_data
+                    .get_mut(DATA_OFFSET..DATA_OFFSET + data_length)
+                    .unwrap(),
+                &patch_log_data,
+                head,
+                count
+            );
+
+            update_dsa_data_hash(&mut dsa_data)?;
+
+            // empty the patch-log
+            sol_memset(
+                &mut patch_log_data,
+                0,
+                PATCH_LOG_LEN
+            );
+
+            let event = Events::DataStorageAccountPatchLogCompacted {
+                data_storage_account: *data_storage_pda_account_info.key,
+                compacted_patches: count
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's patch-log has been compacted successfully. ✅");
+        },
+
+        SET_DATA_STORAGE_ACCOUNT_DELEGATE_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: SetDataStorageAccountDelegate");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            // deserialize instruction's data
+            let delegate: [u8; 32] = ix_data
+                .try_into()
+                .map_err(|_| DataStorageError::InvalidData)?;
+
+            sol_memcpy(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(DELEGATE_OFFSET..DELEGATE_OFFSET + size_of::<Pubkey>())
+                    .unwrap(),
+                &delegate,
+                size_of::<Pubkey>()
+            );
+
+            let event = Events::DataStorageAccountDelegateSet {
+                data_storage_account: *data_storage_pda_account_info.key,
+                delegate: Pubkey::new_from_array(delegate)
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's delegate has been set successfully. ✅");
+        },
+
+        ATTEST_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: AttestDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_owner_or_legacy(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_header_len(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            let data_length = read_data_len(data_storage_pda_account_info)?;
+            let dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow()
+                .unwrap();
+
+            let attestation = keccak_hashv(
+                &[
+                    data_storage_pda_account_info.key.as_ref(),
+                    dsa_data
+                        .get(OWNER_OFFSET..OWNER_OFFSET + size_of::<Pubkey>())
+                        .unwrap(),
+                    dsa_data
+                        .get(DATA_OFFSET..DATA_OFFSET + data_length)
+                        .unwrap(),
+                    dsa_data
+                        .get(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                        .unwrap()
+                ]
+            );
+            let current_slot = (Clock::get()?).slot;
+
+            set_return_data(
+                &[
+                    attestation.to_bytes().as_slice(),
+                    current_slot.to_le_bytes().as_slice()
+                ].concat()
+            );
+        },
+
+        READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: ReadDataStorageAccountData");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let patch_log_account_info = next_account_info(accounts_info).ok();
+
+            check_dsa_account_owner_or_legacy(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_header_len(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            check_dsa_account_address(
+                data_storage_pda_account_info,
+                data_storage_pda_account_info.owner
+            )?;
+
+            let data_length = read_data_len(data_storage_pda_account_info)?;
+            if data_length > MAX_RETURN_DATA - size_of::<bool>() {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // the compressed flag comes first, so callers know whether to inflate the data-field
+            let mut return_data = [0; MAX_RETURN_DATA];
+            let dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow()
+                .unwrap();
+            return_data[0] = *dsa_data
+                .get(COMPRESSED_OFFSET)
+                .unwrap();
+            sol_memcpy(
+                return_data
+                    .get_mut(1..)
+                    .unwrap(),
+                dsa_data
+                    .get(DATA_OFFSET..DATA_OFFSET + data_length)
+                    .unwrap(),
+                data_length
+            );
+
+            // apply the pending patches over the base
+            if let Some(patch_log_account_info) = patch_log_account_info {
+                check_patch_log_address(
+                    patch_log_account_info,
+                    data_storage_pda_account_info,
+                    program_id
+                )?;
+
+                if patch_log_account_info.owner == program_id {
+                    let patch_log_data = patch_log_account_info
+                        .data
+                        .try_borrow()
+                        .unwrap();
+
+                    let (
+                        head,
+                        count
+                    ) = read_pending_patches(&patch_log_data, &dsa_data)?;
+
+                    fold_patch_log_entries(
+                        return_data
+                            .get_mut(1..1 + data_length)
+                            .unwrap(),
+                        &patch_log_data,
+                        head,
+                        count
+                    );
+                } else if patch_log_account_info.owner != &SYSTEM_PROGRAM_ID {
+                    return Err(
+                        ProgramError::IllegalOwner
+                    );
+                };
+            };
+
+            set_return_data(
+                return_data
+                    .get(..size_of::<bool>() + data_length)
+                    .unwrap()
+            );
+        },
+
+        GET_DATA_STORAGE_ACCOUNT_METADATA_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: GetDataStorageAccountMetadata");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_owner_or_legacy(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_header_len(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            check_dsa_account_address(
+                data_storage_pda_account_info,
+                data_storage_pda_account_info.owner
+            )?;
+
+            let dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow()
+                .unwrap();
+            let metadata = DataStorageAccountMetadata::from_view(&DataStorageAccountView(&dsa_data))?;
+
+            set_return_data(&borsh::to_vec(&metadata).unwrap());
+        },
+
+        BATCH_CREATE_DATA_STORAGE_ACCOUNTS_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: BatchCreateDataStorageAccounts");
+
+            let funding_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            check_account_is_signer(funding_account_info)?;
+
+            // validate instruction-data
+            let (
+                accounts_count,
+                mut ix_data
+            ) = ix_data
+                .split_first()
+                .ok_or(DataStorageError::InvalidData)?;
+            if *accounts_count == 0 || *accounts_count as usize > MAX_BATCH_CREATE_LEN {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // the config's limits & creation-fee, for every account (see '//? config')
+            let config_max_data_len = apply_dsa_config(
+                accounts_info,
+                funding_account_info,
+                *accounts_count as u64,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            for _ in 0..*accounts_count {
+                let new_data_storage_pda_account_info = next_account_info(accounts_info)?;
+                let authority_account_info = next_account_info(accounts_info)?;
+
+                if authority_account_info.key != &SYSTEM_PROGRAM_ID {
+                    check_account_is_signer(authority_account_info)?;
+                };
+
+                // deserialize the account's '(label, data-length, data-field)'
+                let (
+                    account_label,
+                    account_ix_data
+                ) = ix_data
+                    .split_at_checked(MAX_LABEL_LENGTH)
+                    .ok_or(DataStorageError::InvalidData)?;
+                let (
+                    account_data_len,
+                    account_ix_data
+                ) = account_ix_data
+                    .split_at_checked(size_of::<u16>())
+                    .ok_or(DataStorageError::InvalidData)?;
+                let (
+                    account_data,
+                    account_ix_data
+                ) = account_ix_data
+                    .split_at_checked(
+                        u16::from_le_bytes(
+                            account_data_len
+                                .try_into()
+                                .unwrap()
+                        ) as usize
+                    )
+                    .ok_or(DataStorageError::InvalidData)?;
+                ix_data = account_ix_data;
+
+                if account_data.len() > config_max_data_len {
+                    return Err(
+                        DataStorageError::DataTooLarge.into()
+                    );
+                };
+
+                let display_label = create_and_initialize_dsa_account(
+                    new_data_storage_pda_account_info,
+                    authority_account_info,
+                    funding_account_info,
+                    account_label,
+                    false,
+                    false,
+                    ACCOUNT_KIND_BYTES,
+                    0,
+                    0,
+                    account_data,
+                    program_id,
+                    cpi_accounts_info
+                )?;
+
+                let event = Events::NewDataStorageAccountCreated {
+                    data_storage_account: *new_data_storage_pda_account_info.key,
+                    authority_account: *authority_account_info.key,
+                    account_label: display_label
+                };
+                emit!(event);
+            };
+
+            // the count must match the tuples, the whole batch fails otherwise
+            if !ix_data.is_empty() {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            verbose_log!("New data storage accounts have been initialized successfully. ✅");
+        },
+
+        SOFT_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: SoftCloseDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let rent_receiver_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_not_chunked(data_storage_pda_account_info)?;
+
+            // drop the data-field (and any reserved capacity)
+            calculate_new_dsa_size_and_realloc(
+                0,
+                read_data_capacity(data_storage_pda_account_info),
+                data_storage_pda_account_info,
+                false
+            )?;
+
+            // refund everything above the header-only account's rent-exempt minimum
+            let refund_lamports = data_storage_pda_account_info
+                .lamports()
+                .saturating_sub(rent_for_data(0, &Rent::get()?));
+
+            **data_storage_pda_account_info.try_borrow_mut_lamports()? = data_storage_pda_account_info
+                .lamports()
+                .checked_sub(refund_lamports)
+                .unwrap();
+
+            **rent_receiver_account_info.try_borrow_mut_lamports()? = rent_receiver_account_info
+                .lamports()
+                .checked_add(refund_lamports)
+                .unwrap();
+
+            // leave the tombstone
+            let closed_at = (Clock::get()?).unix_timestamp;
+            tombstone_dsa_account_data(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap(),
+                closed_at
+            );
+
+            let event = Events::DataStorageAccountSoftClosed {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key,
+                closed_at
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been soft-closed successfully. ✅");
+        },
+
+        REVIVE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: ReviveDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let funding_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            check_account_is_signer(funding_account_info)?;
+
+            check_account_is_signer(authority_account_info)?;
+
+            check_dsa_account_owner(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_header_len(data_storage_pda_account_info)?;
+
+            // only tombstones can be revived, any other program-owned account is alive
+            if !read_tombstone(data_storage_pda_account_info) {
+                return Err(
+                    ProgramError::AccountAlreadyInitialized
+                );
+            };
+
+            check_dsa_account_discriminator(data_storage_pda_account_info)?;
+
+            check_dsa_account_authority(
+                data_storage_pda_account_info,
+                authority_account_info.key
+            )?;
+
+            check_dsa_account_address(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            // validate instruction-data
+            if ix_data.len() < size_of::<u32>() + size_of::<i64>() {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // deserialize instruction's data
+            let (
+                account_max_size,
+                ix_data
+            ) = ix_data.split_at(4);
+            let (
+                account_expires_at,
+                account_data
+            ) = ix_data.split_at(8);
+
+            let account_expires_at = i64::from_le_bytes(
+                account_expires_at
+                    .try_into()
+                    .unwrap()
+            );
+            if account_expires_at < 0 {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            let account_max_size = u32::from_le_bytes(
+                account_max_size
+                    .try_into()
+                    .unwrap()
+            );
+
+            check_data_length_fits_header(account_data.len())?;
+
+            check_data_length_above_minimum(account_data.len())?;
+
+            check_data_length_within_quota(
+                account_data.len(),
+                account_max_size
+            )?;
+
+            // grow the account back from it's header and write the data-field
+            grow_and_write(
+                data_storage_pda_account_info,
+                Some((funding_account_info, None)),
+                0,
+                account_data,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            // initialize the account again
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            // 1. set is_initialized flag and clear the tombstone
+            *dsa_data
+                .get_mut(INIT_OFFSET)
+                .unwrap() = true as u8;
+            *dsa_data
+                .get_mut(TOMBSTONE_OFFSET)
+                .unwrap() = false as u8;
+            sol_memset(
+                dsa_data
+                    .get_mut(CLOSED_AT_OFFSET..CLOSED_AT_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                0,
+                size_of::<i64>()
+            );
+            // 2. set max-size
+            sol_memcpy(
+                dsa_data
+                    .get_mut(MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + size_of::<u32>())
+                    .unwrap(),
+                &account_max_size.to_le_bytes(),
+                size_of::<u32>()
+            );
+            // 3. set created-at
+            sol_memcpy(
+                dsa_data
+                    .get_mut(CREATED_AT_OFFSET..CREATED_AT_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &(Clock::get()?).unix_timestamp.to_le_bytes(),
+                size_of::<i64>()
+            );
+            // 4. set expires-at
+            sol_memcpy(
+                dsa_data
+                    .get_mut(EXPIRES_AT_OFFSET..EXPIRES_AT_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &account_expires_at.to_le_bytes(),
+                size_of::<i64>()
+            );
+            // 5. set last-editor
+            set_dsa_last_editor(&mut dsa_data, authority_account_info.key);
+            // 6. set data-hash
+            update_dsa_data_hash(&mut dsa_data)?;
+
+            drop(dsa_data);
+
+            let event = Events::DataStorageAccountRevived {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been revived successfully. ✅");
+        },
+
+        MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: MigrateDataStorageAccountLayout");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let funding_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            check_account_is_signer(funding_account_info)?;
+
+            check_authority_is_not_data_storage_account(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            // immutable accounts can't sign, migrating doesn't change their content so they can be migrated anyway
+            if authority_account_info.key != &SYSTEM_PROGRAM_ID {
+                check_account_is_signer(authority_account_info)?;
+            };
+
+            check_dsa_account_owner(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            // the version '1' header is shorter than 'HEADER_LEN', so 'check_dsa_account_header_len' would reject it
+            if data_storage_pda_account_info.data_len() < V1_HEADER_LEN {
+                return Err(
+                    ProgramError::InvalidAccountData
+                );
+            };
+
+            let header_version = read_header_version(data_storage_pda_account_info)?;
+
+            // tombstones are migrated too, so they can still be revived (version '1' accounts predate them)
+            if header_version == 1 || !read_tombstone(data_storage_pda_account_info) {
+                check_dsa_account_is_initialized_flag(data_storage_pda_account_info)?;
+            };
+
+            check_dsa_account_authority(
+                data_storage_pda_account_info,
+                authority_account_info.key
+            )?;
+
+            if header_version == HEADER_VERSION {
+                check_dsa_account_address(
+                    data_storage_pda_account_info,
+                    program_id
+                )?;
+
+                verbose_log!("Data storage account's layout is already up to date.");
+
+                return Ok(());
+            };
+
+            // version '1' accounts are always derived from their (fixed) label
+            {
+                let dsa_data = data_storage_pda_account_info
+                    .data
+                    .try_borrow()
+                    .unwrap();
+
+                create_and_check_program_address(
+                    &[
+                        DATA_STORAGE_SEED_PREFIX,
+                        dsa_data
+                            .get(OWNER_OFFSET..OWNER_OFFSET + size_of::<Pubkey>())
+                            .unwrap(),
+                        dsa_data
+                            .get(LABEL_OFFSET..LABEL_OFFSET + MAX_LABEL_LENGTH)
+                            .unwrap(),
+                        &[ *dsa_data.get(BUMP_OFFSET).unwrap() ]
+                    ],
+                    program_id,
+                    data_storage_pda_account_info.key
+                )?;
+            };
+
+            // grow the account by the header fields added since version '1'
+            let added_header_len = HEADER_LEN - V1_HEADER_LEN;
+            let old_account_size = data_storage_pda_account_info.data_len();
+
+            let extra_rent_lamports = calculate_extra_rent_exempt_lamports(
+                old_account_size,
+                old_account_size + added_header_len
+            )?;
+
+            invoke(
+                &transfer_lamports(
+                    funding_account_info.key,
+                    data_storage_pda_account_info.key,
+                    extra_rent_lamports
+                ),
+                cpi_accounts_info
+            )?;
+
+            calculate_new_dsa_size_and_realloc(
+                added_header_len,
+                0,
+                data_storage_pda_account_info,
+                true
+            )?;
+
+            {
+                let mut dsa_data = data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap();
+                // 1. move the data-field behind the new header
+                dsa_data.copy_within(V1_HEADER_LEN..old_account_size, HEADER_LEN);
+                // 2. the added fields get their defaults
+                sol_memset(
+                    dsa_data
+                        .get_mut(V1_HEADER_LEN..HEADER_LEN)
+                        .unwrap(),
+                    0,
+                    added_header_len
+                );
+                // 3. set display-label (the label, like a create does)
+                dsa_data.copy_within(LABEL_OFFSET..LABEL_OFFSET + MAX_LABEL_LENGTH, DISPLAY_LABEL_OFFSET);
+                // 4. set header-version & account-discriminator
+                *dsa_data
+                    .get_mut(HEADER_VERSION_OFFSET)
+                    .unwrap() = HEADER_VERSION;
+                *dsa_data
+                    .get_mut(ACCOUNT_DISCRIMINATOR_OFFSET)
+                    .unwrap() = DATA_STORAGE_ACCOUNT_DISCRIMINATOR;
+                // 5. set data-hash
+                update_dsa_data_hash(&mut dsa_data)?;
+            };
+
+            let event = Events::DataStorageAccountLayoutMigrated {
+                data_storage_account: *data_storage_pda_account_info.key,
+                header_version: HEADER_VERSION
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's layout has been migrated successfully. ✅");
+        },
+
+        TOP_UP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: TopUpDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let funder_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            // only adds lamports, so anyone can pay, the authority doesn't have to sign
+            check_account_is_signer(funder_account_info)?;
+
+            check_dsa_account_owner(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_header_len(data_storage_pda_account_info)?;
+
+            check_dsa_account_discriminator(data_storage_pda_account_info)?;
+
+            check_dsa_account_address(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            let shortfall_lamports = Rent::get()?
+                .minimum_balance(data_storage_pda_account_info.data_len())
+                .saturating_sub(data_storage_pda_account_info.lamports());
+            if shortfall_lamports == 0 {
+                verbose_log!("Data storage account is already rent-exempt.");
+
+                return Ok(());
+            };
+
+            invoke(
+                &transfer_lamports(
+                    funder_account_info.key,
+                    data_storage_pda_account_info.key,
+                    shortfall_lamports
+                ),
+                cpi_accounts_info
+            )?;
+
+            let event = Events::DataStorageAccountToppedUp {
+                data_storage_account: *data_storage_pda_account_info.key,
+                funder_account: *funder_account_info.key,
+                lamports: shortfall_lamports
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been topped up successfully. ✅");
+        },
+
+        CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: CloseExpiredDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let rent_receiver_account_info = next_account_info(accounts_info)?;
+            let excess_receiver_account_info = next_account_info(accounts_info).ok();
+            let patch_log_account_info = next_account_info(accounts_info).ok();
+
+            check_dsa_account_owner(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_header_len(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            check_dsa_account_address(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_chunked(data_storage_pda_account_info)?;
+
+            let expires_at = read_expires_at(data_storage_pda_account_info);
+            if expires_at == 0 || (Clock::get()?).unix_timestamp < expires_at {
+                return Err(
+                    DataStorageError::NotExpired.into()
+                );
+            };
+
+            let authority = read_authority(data_storage_pda_account_info);
+
+            if let Some(patch_log_account_info) = patch_log_account_info {
+                close_patch_log_account(
+                    patch_log_account_info,
+                    data_storage_pda_account_info,
+                    rent_receiver_account_info,
+                    program_id
+                )?;
+            };
+
+            close_dsa_account(
+                data_storage_pda_account_info,
+                rent_receiver_account_info,
+                excess_receiver_account_info
+            )?;
+
+            let event = Events::DataStorageAccountClosed {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: authority
+            };
+            emit!(event);
+
+            verbose_log!("Expired data storage account has been closed successfully. ✅");
+        },
+
+        SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR |
+        REMOVE_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR => {
+            // removes share the set's splice, with no value
+            let remove = ix_discriminator == REMOVE_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR;
+            if remove {
+                verbose_log!("⚙️ Instruction: RemoveDataStorageAccountEntry");
+            } else {
+                verbose_log!("⚙️ Instruction: SetDataStorageAccountEntry");
+            };
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_editor(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            check_dsa_account_is_key_value(data_storage_pda_account_info)?;
+
+            // deserialize instruction's data
+            let (
+                key,
+                value
+            ) = if remove {
+                (ix_data, None)
+            } else {
+                let (
+                    key_len,
+                    ix_data
+                ) = ix_data
+                    .split_first()
+                    .ok_or(DataStorageError::InvalidData)?;
+                let (
+                    key,
+                    value
+                ) = ix_data
+                    .split_at_checked(*key_len as usize)
+                    .ok_or(DataStorageError::InvalidData)?;
+
+                (key, Some(value))
+            };
+            if key.is_empty() || key.len() > u8::MAX as usize || value.is_some_and(|value| value.len() > u16::MAX as usize) {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            let old_data_length = read_data_len(data_storage_pda_account_info)?;
+            let new_data = splice_entry(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow()
+                    .unwrap()
+                    .get(DATA_OFFSET..DATA_OFFSET + old_data_length)
+                    .unwrap(),
+                key,
+                value
+            )?;
+            let new_data_length = new_data.len();
+
+            // the stored data-length is a 'u16', so longer data-fields would be silently truncated
+            check_data_length_fits_header(new_data_length)?;
+
+            check_data_length_above_minimum(new_data_length)?;
+
+            // update 'last-updated' & 'has-been-edited' fields
+            let current_time = (Clock::get()?).unix_timestamp;
+            sol_memcpy(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &current_time.to_le_bytes(),
+                size_of::<i64>()
+            );
+            *data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap()
+                .get_mut(HAS_BEEN_EDITED_OFFSET)
+                .unwrap() = true as u8;
+
+            let new_revision = increment_dsa_revision(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+            )?;
+            set_dsa_last_editor(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap(),
+                authority_account_info.key
+            );
+
+            write_dsa_data_field(
+                data_storage_pda_account_info,
+                accounts_info,
+                old_data_length,
+                &new_data,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            update_dsa_data_hash(
+                &mut data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+            )?;
+
+            let event = Events::DataStorageAccountEdited {
+                data_storage_account: *data_storage_pda_account_info.key,
+                authority_account: *authority_account_info.key,
+                old_data_len: old_data_length,
+                new_data_len: new_data_length,
+                new_revision,
+                last_editor: *authority_account_info.key
+            };
+            emit!(event);
+
+            if remove {
+                verbose_log!("Entry has been removed from the data storage account successfully. ✅");
+            } else {
+                verbose_log!("Entry has been set in the data storage account successfully. ✅");
+            };
+        },
+
+        GET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: GetDataStorageAccountEntry");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_owner_or_legacy(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_header_len(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            check_dsa_account_address(
+                data_storage_pda_account_info,
+                data_storage_pda_account_info.owner
+            )?;
+
+            check_dsa_account_is_key_value(data_storage_pda_account_info)?;
+
+            let data_length = read_data_len(data_storage_pda_account_info)?;
+            let dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow()
+                .unwrap();
+            let (_, value) = read_entries(
+                dsa_data
+                    .get(DATA_OFFSET..DATA_OFFSET + data_length)
+                    .unwrap()
+            )
+                .map_err(|_| DataStorageError::MalformedAccount)?
+                .into_iter()
+                .find(|(key, _)| *key == ix_data)
+                .ok_or(DataStorageError::EntryNotFound)?;
+
+            if value.len() > MAX_RETURN_DATA {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            set_return_data(value);
+        },
+
+        TOUCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: TouchDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            // update 'last-updated' field only
+            let current_time = (Clock::get()?).unix_timestamp;
+            sol_memcpy(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &current_time.to_le_bytes(),
+                size_of::<i64>()
+            );
+
+            let event = Events::DataStorageAccountTouched {
+                data_storage_account: *data_storage_pda_account_info.key,
+                last_updated: current_time
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been touched successfully. ✅");
+        },
+
+        RENAME_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: RenameDataStorageAccount");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let new_data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let funding_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+            let patch_log_account_info = next_account_info(accounts_info).ok();
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            check_dsa_account_is_not_chunked(data_storage_pda_account_info)?;
+
+            // validate instruction-data
+            if ix_data.len() != MAX_LABEL_LENGTH {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+            let account_label = ix_data;
+
+            // validate label
+            check_account_label(account_label)?;
+
+            // reject an already existing destination account
+            if new_data_storage_pda_account_info.owner != &SYSTEM_PROGRAM_ID || !new_data_storage_pda_account_info.data_is_empty() {
+                return Err(
+                    ProgramError::AccountAlreadyInitialized
+                );
+            };
+
+            // get pda's bump and validate the pda's pubkey
+            let (
+                dsa_address,
+                dsa_bump
+            ) = Pubkey::try_find_program_address(
+                &[
+                    DATA_STORAGE_SEED_PREFIX,
+                    authority_account_info.key.as_ref(),
+                    account_label
+                ],
+                program_id
+            ).ok_or(DataStorageError::FailedToFindProgramAddress)?;
+            if &dsa_address != new_data_storage_pda_account_info.key {
+                return Err(
+                    ProgramError::InvalidSeeds
+                );
+            };
+
+            // create the new account, reserved capacity included
+            let account_size = data_storage_pda_account_info.data_len();
+
+            let seeds: &[&[u8]] = &[
+                DATA_STORAGE_SEED_PREFIX,
+                authority_account_info.key.as_ref(),
+                account_label,
+                &[ dsa_bump ]
+            ];
+
+            // make sure the stored bump re-creates the same address, edit & close rely on it
+            create_and_check_program_address(
+                seeds,
+                program_id,
+                new_data_storage_pda_account_info.key
+            )?;
+
+            create_pda_account(
+                new_data_storage_pda_account_info,
+                funding_account_info,
+                account_size,
+                program_id,
+                seeds,
+                cpi_accounts_info
+            )?;
+            verbose_log!("New data storage account created.");
+
+            // the patch-log is derived from the old pda, fold the pending patches before moving
+            if let Some(patch_log_account_info) = patch_log_account_info {
+                compact_and_close_patch_log_account(
+                    patch_log_account_info,
+                    data_storage_pda_account_info,
+                    funding_account_info,
+                    program_id
+                )?;
+            };
+
+            // move the account
+            // 1. copy the whole account
+            let mut new_dsa_data = new_data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            sol_memcpy(
+                &mut new_dsa_data,
+                &data_storage_pda_account_info
+                    .data
+                    .try_borrow()
+                    .unwrap(),
+                account_size
+            );
+            // 2. set account-label & display-label
+            sol_memcpy(
+                new_dsa_data
+                    .get_mut(LABEL_OFFSET..LABEL_OFFSET + MAX_LABEL_LENGTH)
+                    .unwrap(),
+                account_label,
+                MAX_LABEL_LENGTH
+            );
+            sol_memcpy(
+                new_dsa_data
+                    .get_mut(DISPLAY_LABEL_OFFSET..DISPLAY_LABEL_OFFSET + MAX_LABEL_LENGTH)
+                    .unwrap(),
+                account_label,
+                MAX_LABEL_LENGTH
+            );
+            // 3. set account-bump
+            *new_dsa_data
+                .get_mut(BUMP_OFFSET)
+                .unwrap() = dsa_bump;
+            // 4. the new label is a (fixed) seed-label, even when the old one is a variable one
+            sol_memset(
+                new_dsa_data
+                    .get_mut(LAYOUT_VERSION_OFFSET..TOMBSTONE_OFFSET)
+                    .unwrap(),
+                0,
+                2 * size_of::<u8>() + MAX_VARIABLE_LABEL_LENGTH
+            );
+
+            drop(new_dsa_data);
+
+            // close the old account
+            close_dsa_account(
+                data_storage_pda_account_info,
+                funding_account_info,
+                Some(funding_account_info)
+            )?;
+
+            let event = Events::DataStorageAccountRenamed {
+                old_data_storage_account: *data_storage_pda_account_info.key,
+                data_storage_account: *new_data_storage_pda_account_info.key,
+                account_label: account_label
+                    .try_into()
+                    .unwrap()
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account has been renamed successfully. ✅");
+        },
+
+        ALLOCATE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: AllocateDataStorageAccountChunks");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let funding_account_info = next_account_info(accounts_info)?;
+            let system_program_account_info = next_account_info(accounts_info)?;
+
+            check_system_program_account(system_program_account_info.key)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            // only an empty bytes account can become a chunked one
+            if read_data_len(data_storage_pda_account_info)? != 0 || *data_storage_pda_account_info
+                .data
+                .try_borrow()
+                .unwrap()
+                .get(KIND_OFFSET)
+                .unwrap() != ACCOUNT_KIND_BYTES
+            {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // deserialize instruction's data
+            let total_len: [u8; 4] = ix_data
+                .try_into()
+                .map_err(|_| DataStorageError::InvalidData)?;
+            let total_len = u32::from_le_bytes(total_len) as usize;
+
+            let chunk_count = total_len.div_ceil(CHUNK_LEN);
+            if chunk_count == 0 || chunk_count > u16::MAX as usize {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // create the chunks
+            for chunk_index in 0..chunk_count as u16 {
+                let chunk_account_info = next_account_info(accounts_info)?;
+
+                let chunk_bump = check_chunk_address(
+                    chunk_account_info,
+                    data_storage_pda_account_info,
+                    chunk_index,
+                    program_id
+                )?;
+
+                create_pda_account(
+                    chunk_account_info,
+                    funding_account_info,
+                    chunk_len(total_len, chunk_index as usize),
+                    program_id,
+                    &[
+                        CHUNK_SEED_PREFIX,
+                        data_storage_pda_account_info.key.as_ref(),
+                        &chunk_index.to_le_bytes(),
+                        &[ chunk_bump ]
+                    ],
+                    cpi_accounts_info
+                )?;
+            };
+
+            // the data-field holds the chunks' metadata
+            write_dsa_data_field(
+                data_storage_pda_account_info,
+                &mut [ funding_account_info, system_program_account_info ].into_iter(),
+                0,
+                &[
+                    (total_len as u32).to_le_bytes().as_slice(),
+                    (chunk_count as u16).to_le_bytes().as_slice()
+                ].concat(),
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            *dsa_data
+                .get_mut(KIND_OFFSET)
+                .unwrap() = ACCOUNT_KIND_CHUNKED;
+            update_dsa_data_hash(&mut dsa_data)?;
+
+            drop(dsa_data);
+
+            let event = Events::DataStorageAccountChunksAllocated {
+                data_storage_account: *data_storage_pda_account_info.key,
+                total_len: total_len as u32,
+                chunk_count: chunk_count as u16
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's chunks have been allocated successfully. ✅");
+        },
+
+        CLOSE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: CloseDataStorageAccountChunks");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let rent_receiver_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_lock(
+                data_storage_pda_account_info,
+                authority_account_info
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            let (
+                _,
+                chunk_count
+            ) = read_chunks_metadata(data_storage_pda_account_info)?;
+
+            // close the chunks
+            for chunk_index in 0..chunk_count as u16 {
+                let chunk_account_info = next_account_info(accounts_info)?;
+
+                close_chunk_account(
+                    chunk_account_info,
+                    data_storage_pda_account_info,
+                    chunk_index,
+                    rent_receiver_account_info,
+                    program_id
+                )?;
+            };
+
+            // drop the chunks' metadata, the account is an empty bytes one again
+            write_dsa_data_field(
+                data_storage_pda_account_info,
+                &mut [ rent_receiver_account_info ].into_iter(),
+                CHUNKS_METADATA_LEN,
+                &[],
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            *dsa_data
+                .get_mut(KIND_OFFSET)
+                .unwrap() = ACCOUNT_KIND_BYTES;
+            update_dsa_data_hash(&mut dsa_data)?;
+
+            drop(dsa_data);
+
+            let event = Events::DataStorageAccountChunksClosed {
+                data_storage_account: *data_storage_pda_account_info.key,
+                chunk_count: chunk_count as u16
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's chunks have been closed successfully. ✅");
+        },
+
+        WRITE_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: WriteDataStorageAccountChunk");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+            let chunk_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_editor(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_is_not_time_locked(data_storage_pda_account_info)?;
+
+            check_dsa_account_caller(
+                data_storage_pda_account_info,
+                program_id,
+                cpi_accounts_info
+            )?;
+
+            let (
+                total_len,
+                chunk_count
+            ) = read_chunks_metadata(data_storage_pda_account_info)?;
+
+            // deserialize instruction's data
+            let (
+                chunk_index,
+                ix_data
+            ) = ix_data
+                .split_first_chunk::<2>()
+                .ok_or(DataStorageError::InvalidData)?;
+            let (
+                offset,
+                bytes
+            ) = ix_data
+                .split_first_chunk::<2>()
+                .ok_or(DataStorageError::InvalidData)?;
+            let chunk_index = u16::from_le_bytes(*chunk_index);
+            let offset = u16::from_le_bytes(*offset) as usize;
+
+            if bytes.is_empty() || chunk_index as usize >= chunk_count || offset
+                .checked_add(bytes.len())
+                .unwrap() > chunk_len(total_len, chunk_index as usize)
+            {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            check_chunk_address(
+                chunk_account_info,
+                data_storage_pda_account_info,
+                chunk_index,
+                program_id
+            )?;
+            if chunk_account_info.owner != program_id {
+                return Err(
+                    ProgramError::IllegalOwner
+                );
+            };
+
+            // write the bytes
+            sol_memcpy(
+                chunk_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(offset..offset + bytes.len())
+                    .unwrap(),
+                bytes,
+                bytes.len()
+            );
+
+            // update 'last-updated' & 'has-been-edited' fields
+            let current_time = (Clock::get()?).unix_timestamp;
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            sol_memcpy(
+                dsa_data
+                    .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &current_time.to_le_bytes(),
+                size_of::<i64>()
+            );
+            *dsa_data
+                .get_mut(HAS_BEEN_EDITED_OFFSET)
+                .unwrap() = true as u8;
+
+            let new_revision = increment_dsa_revision(&mut dsa_data)?;
+            set_dsa_last_editor(&mut dsa_data, authority_account_info.key);
+
+            drop(dsa_data);
+
+            let event = Events::DataStorageAccountChunkWritten {
+                data_storage_account: *data_storage_pda_account_info.key,
+                chunk_index,
+                new_revision,
+                last_editor: *authority_account_info.key
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's chunk has been written successfully. ✅");
+        },
+
+        READ_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: ReadDataStorageAccountChunk");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let chunk_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_owner_or_legacy(
+                data_storage_pda_account_info,
+                program_id
+            )?;
+
+            check_dsa_account_header_len(data_storage_pda_account_info)?;
+
+            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+
+            let (
+                total_len,
+                chunk_count
+            ) = read_chunks_metadata(data_storage_pda_account_info)?;
+
+            // deserialize instruction's data
+            let ix_data: [u8; 4] = ix_data
+                .try_into()
+                .map_err(|_| DataStorageError::InvalidData)?;
+            let chunk_index = u16::from_le_bytes([ ix_data[0], ix_data[1] ]);
+            let offset = u16::from_le_bytes([ ix_data[2], ix_data[3] ]) as usize;
+
+            let chunk_length = chunk_len(total_len, chunk_index as usize);
+            if chunk_index as usize >= chunk_count || offset > chunk_length {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // chunks of a legacy account are derived from it's owner, like the account itself
+            check_chunk_address(
+                chunk_account_info,
+                data_storage_pda_account_info,
+                chunk_index,
+                data_storage_pda_account_info.owner
+            )?;
+            if chunk_account_info.owner != data_storage_pda_account_info.owner {
+                return Err(
+                    ProgramError::IllegalOwner
+                );
+            };
+
+            let read_len = (chunk_length - offset).min(MAX_RETURN_DATA);
+            set_return_data(
+                chunk_account_info
+                    .data
+                    .try_borrow()
+                    .unwrap()
+                    .get(offset..offset + read_len)
+                    .ok_or(DataStorageError::MalformedAccount)?
+            );
+        },
+
+        SET_DATA_STORAGE_ACCOUNT_IMMUTABLE_WINDOW_INSTRUCTION_DISCRIMINATOR => {
+            verbose_log!("⚙️ Instruction: SetDataStorageAccountImmutableWindow");
+
+            let data_storage_pda_account_info = next_account_info(accounts_info)?;
+            let authority_account_info = next_account_info(accounts_info)?;
+
+            check_dsa_account_and_authority(
+                data_storage_pda_account_info,
+                authority_account_info,
+                program_id
+            )?;
+
+            // validate & deserialize instruction's data
+            if ix_data.len() != size_of::<i64>() {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            let lock_until = i64::from_le_bytes(
+                ix_data
+                    .try_into()
+                    .unwrap()
+            );
+
+            let current_time = (Clock::get()?).unix_timestamp;
+            if lock_until <= current_time {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            // an active window can only be extended (an expired one is always before 'lock_until')
+            if lock_until < read_lock_until(data_storage_pda_account_info) {
+                return Err(
+                    DataStorageError::TimeLocked.into()
+                );
+            };
+
+            sol_memcpy(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(LOCK_UNTIL_OFFSET..LOCK_UNTIL_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &lock_until.to_le_bytes(),
+                size_of::<i64>()
+            );
+
+            let event = Events::DataStorageAccountImmutableWindowSet {
+                data_storage_account: *data_storage_pda_account_info.key,
+                lock_until
+            };
+            emit!(event);
+
+            verbose_log!("Data storage account's immutable window has been set successfully. ✅");
+        },
+
+        INIT_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR |
+        UPDATE_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR => {
+            let init = ix_discriminator == INIT_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR;
+            if init {
+                verbose_log!("⚙️ Instruction: InitDataStorageConfig");
+            } else {
+                verbose_log!("⚙️ Instruction: UpdateDataStorageConfig");
+            };
+
+            let config_pda_account_info = next_account_info(accounts_info)?;
+            let admin_account_info = next_account_info(accounts_info)?;
+
+            check_account_is_signer(admin_account_info)?;
+
+            // validate & deserialize instruction's data, an update also carries the new admin
+            let (
+                admin,
+                ix_data
+            ) = if init {
+                (*admin_account_info.key, ix_data)
+            } else {
+                let (
+                    admin,
+                    ix_data
+                ) = ix_data
+                    .split_at_checked(size_of::<Pubkey>())
+                    .ok_or(DataStorageError::InvalidData)?;
+
+                (Pubkey::new_from_array(admin.try_into().unwrap()), ix_data)
+            };
+            if ix_data.len() != size_of::<u32>() + size_of::<u64>() + size_of::<Pubkey>() {
+                return Err(
+                    DataStorageError::InvalidData.into()
+                );
+            };
+
+            let (
+                max_data_len,
+                ix_data
+            ) = ix_data.split_at(size_of::<u32>());
+            let (
+                creation_fee,
+                treasury
+            ) = ix_data.split_at(size_of::<u64>());
+            let max_data_len = u32::from_le_bytes(
+                max_data_len
+                    .try_into()
+                    .unwrap()
+            );
+            let creation_fee = u64::from_le_bytes(
+                creation_fee
+                    .try_into()
+                    .unwrap()
+            );
+            let treasury = Pubkey::new_from_array(
+                treasury
+                    .try_into()
+                    .unwrap()
+            );
+
+            check_data_length_fits_header(max_data_len as usize)?;
+
+            let bump = if init {
+                let funding_account_info = next_account_info(accounts_info)?;
+                let system_program_account_info = next_account_info(accounts_info)?;
+                let program_data_account_info = next_account_info(accounts_info)?;
+
+                check_system_program_account(system_program_account_info.key)?;
+                check_account_is_signer(funding_account_info)?;
+
+                check_program_upgrade_authority(
+                    program_data_account_info,
+                    admin_account_info,
+                    program_id
+                )?;
+
+                let (
+                    config_pda_addr,
+                    config_bump
+                ) = find_config_pda(program_id);
+                if config_pda_account_info.key != &config_pda_addr {
+                    return Err(
+                        ProgramError::InvalidSeeds
+                    );
+                };
+
+                // the config can only be initialized once
+                if config_pda_account_info.owner != &SYSTEM_PROGRAM_ID || !config_pda_account_info.data_is_empty() {
+                    return Err(
+                        ProgramError::AccountAlreadyInitialized
+                    );
+                };
+
+                create_pda_account(
+                    config_pda_account_info,
+                    funding_account_info,
+                    CONFIG_LEN,
+                    program_id,
+                    &[
+                        CONFIG_SEED_PREFIX,
+                        &[ config_bump ]
+                    ],
+                    cpi_accounts_info
+                )?;
+
+                config_bump
+            } else {
+                check_config_account(
+                    config_pda_account_info,
+                    program_id
+                )?;
+
+                let config = DataStorageConfig::unpack(
+                    &config_pda_account_info
+                        .data
+                        .try_borrow()
+                        .unwrap()
+                )?;
+                if admin_account_info.key != &config.admin {
+                    return Err(
+                        ProgramError::IncorrectAuthority
+                    );
+                };
+
+                config.bump
+            };
+
+            let config = DataStorageConfig {
+                admin,
+                max_data_len,
+                creation_fee,
+                treasury,
+                bump
+            };
+            sol_memcpy(
+                &mut config_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap(),
+                &config.pack(),
+                CONFIG_LEN
+            );
+
+            let event = if init {
+                Events::DataStorageConfigInitialized {
+                    config_account: *config_pda_account_info.key,
+                    admin,
+                    max_data_len,
+                    creation_fee,
+                    treasury
+                }
+            } else {
+                Events::DataStorageConfigUpdated {
+                    config_account: *config_pda_account_info.key,
+                    admin,
+                    max_data_len,
+                    creation_fee,
+                    treasury
+                }
+            };
+            emit!(event);
+
+            verbose_log!("Data storage config has been saved successfully. ✅");
+        },
+        _ => return Err(
+            ProgramError::InvalidInstructionData
+        )
+    };
+
+    Ok(())
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq, FromPrimitive)]
+pub enum DataStorageError {
+    #[error("immutable data storage account.")]
+    ImmutableDataStorage = 70,
+    #[error("find_program_address failed!")]
+    FailedToFindProgramAddress,
+    #[error("invalid account-label (invalid utf-8 or non-printable characters)")]
+    InvalidLabel,
+    #[error("invalid data")]
+    InvalidData,
+    #[error("data length exceeds the account's max-size")]
+    QuotaExceeded,
+    #[error("data storage account is locked by another account")]
+    AccountLocked,
+    #[error("data-field exceeds 'MAX_DATA_LEN' or solana's 10 MiB account limit")]
+    DataTooLarge,
+    #[error("stored data length exceeds the account's data-field")]
+    MalformedAccount,
+    #[error("data-field has been modified since it was read")]
+    ConcurrentModification,
+    #[error("caller program is not in the account's cpi-allowlist")]
+    UnauthorizedCaller,
+    #[error("accounts can only grow by 10 KiB per instruction")]
+    ReallocTooLarge,
+    #[error("data length is below the minimum data length")]
+    DataTooSmall,
+    #[error("account's revision doesn't match the expected revision")]
+    RevisionMismatch,
+    #[error("data-field doesn't match it's stored hash")]
+    HashMismatch,
+    #[error("data storage account hasn't expired")]
+    NotExpired,
+    #[error("funding account is missing")]
+    MissingFundingAccount,
+    #[error("rent-receiver account is missing")]
+    MissingRentReceiver,
+    #[error("system program account is missing")]
+    MissingSystemProgram,
+    #[error("data storage account has been soft-closed, revive it instead")]
+    AccountTombstoned,
+    #[error("data storage account isn't a key-value account")]
+    NotKeyValueAccount,
+    #[error("key-value entry not found")]
+    EntryNotFound,
+    #[error("data storage account holds lamports above it's rent-exempt minimum")]
+    UnexpectedLamports,
+    #[error("data storage account isn't a chunked account")]
+    NotChunkedAccount,
+    #[error("data storage account's data-field is time-locked")]
+    TimeLocked,
+    #[error("treasury account is missing")]
+    MissingTreasury,
+    #[error("data storage account already exists with a different authority or data-field")]
+    DataMismatch,
+    #[error("label is already used by another data storage account of the authority")]
+    LabelAlreadyUsed,
+    #[error("config account is missing")]
+    MissingConfig,
+    #[error("data storage account isn't a bytes account")]
+    NotBytesAccount,
+    #[error("data storage account has chunks, close them first")]
+    ChunkedAccount
+}
+
+impl From<DataStorageError> for ProgramError {
+    fn from(error: DataStorageError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl<T> DecodeError<T> for DataStorageError {
+    fn type_of() -> &'static str {
+        "DataStorageError"
+    }
+}
+
+impl PrintProgramError for DataStorageError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitiveTrait
+    {
+        msg!("Error: {}", self);
+    }
+}
+
+/// Logs `error` by it's name if it's one of `DataStorageError`s, otherwise by it's `ProgramError` name.
+pub fn print_program_error(error: &ProgramError) {
+    error.print::<DataStorageError>();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum Events {
+    NewDataStorageAccountCreated {
+        data_storage_account: Pubkey,
+        authority_account: Pubkey,
+        account_label: [u8; 30]
+    },
+    DataStorageAccountEdited {
+        data_storage_account: Pubkey,
+        authority_account: Pubkey,
+        old_data_len: usize,
+        new_data_len: usize,
+        new_revision: u32,
+        last_editor: Pubkey
+    },
+    DataStorageAccountClosed {
+        data_storage_account: Pubkey,
+        authority_account: Pubkey
+    },
+    DataStorageAccountCapacityReserved {
+        data_storage_account: Pubkey,
+        added_capacity: usize,
+        total_capacity: usize
+    },
+    DataStorageAccountLocked {
+        data_storage_account: Pubkey,
+        lock_holder: Pubkey,
+        lock_expiry: i64
+    },
+    DataStorageAccountUnlocked {
+        data_storage_account: Pubkey
+    },
+    DataStorageAccountAuthorityTransferred {
+        old_data_storage_account: Pubkey,
+        new_data_storage_account: Pubkey,
+        old_authority_account: Pubkey,
+        new_authority_account: Pubkey
+    },
+    DataStorageAccountSealed {
+        old_data_storage_account: Pubkey,
+        data_storage_account: Pubkey,
+        authority_account: Pubkey
+    },
+    DataStorageAccountDuplicated {
+        source_data_storage_account: Pubkey,
+        data_storage_account: Pubkey,
+        account_label: [u8; 30]
+    },
+    DataStorageAccountPatched {
+        data_storage_account: Pubkey,
+        authority_account: Pubkey,
+        offset: usize,
+        len: usize
+    },
+    DataStorageAccountCpiAllowlistSet {
+        data_storage_account: Pubkey,
+        cpi_allowlist: [Pubkey; MAX_CPI_ALLOWLIST_LEN]
+    },
+    DataStorageAccountAuthorityRepaired {
+        data_storage_account: Pubkey,
+        authority_account: Pubkey
+    },
+    DataStorageAccountPatchLogCompacted {
+        data_storage_account: Pubkey,
+        compacted_patches: usize
+    },
+    DataStorageAccountDelegateSet {
+        data_storage_account: Pubkey,
+        delegate: Pubkey
+    },
+    DataStorageAccountSoftClosed {
+        data_storage_account: Pubkey,
+        authority_account: Pubkey,
+        closed_at: i64
+    },
+    DataStorageAccountRevived {
+        data_storage_account: Pubkey,
+        authority_account: Pubkey
+    },
+    DataStorageAccountLayoutMigrated {
+        data_storage_account: Pubkey,
+        header_version: u8
+    },
+    DataStorageAccountToppedUp {
+        data_storage_account: Pubkey,
+        funder_account: Pubkey,
+        lamports: u64
+    },
+    DataStorageAccountTouched {
+        data_storage_account: Pubkey,
+        last_updated: i64
+    },
+    DataStorageAccountRenamed {
+        old_data_storage_account: Pubkey,
+        data_storage_account: Pubkey,
+        account_label: [u8; 30]
+    },
+    DataStorageAccountChunksAllocated {
+        data_storage_account: Pubkey,
+        total_len: u32,
+        chunk_count: u16
+    },
+    DataStorageAccountChunkWritten {
+        data_storage_account: Pubkey,
+        chunk_index: u16,
+        new_revision: u32,
+        last_editor: Pubkey
+    },
+    DataStorageAccountImmutableWindowSet {
+        data_storage_account: Pubkey,
+        lock_until: i64
+    },
+    DataStorageConfigInitialized {
+        config_account: Pubkey,
+        admin: Pubkey,
+        max_data_len: u32,
+        creation_fee: u64,
+        treasury: Pubkey
+    },
+    DataStorageConfigUpdated {
+        config_account: Pubkey,
+        admin: Pubkey,
+        max_data_len: u32,
+        creation_fee: u64,
+        treasury: Pubkey
+    },
+    DataStorageAccountChunksClosed {
+        data_storage_account: Pubkey,
+        chunk_count: u16
+    }
+}
+
+/// Encodes an instruction's `u8` discriminator the way the program expects it, the 8-byte Anchor-compatible one when the
+/// 'anchor_discriminators' feature is enabled.
+/// NOTE: Panics on unknown discriminators.
+pub fn encode_instruction_discriminator(discriminator: u8) -> Vec<u8> {
+    if cfg!(feature = "anchor_discriminators") {
+        let (anchor_discriminator, _) = ANCHOR_INSTRUCTION_DISCRIMINATORS[discriminator as usize];
+
+        anchor_discriminator.to_vec()
+    } else {
+        vec![ discriminator ]
+    }
+}
+
+/// Splits an instruction's data into it's `u8` discriminator and payload, the inverse of `encode_instruction_discriminator`.
+/// Fails with `ProgramError::InvalidInstructionData` when the data is empty (or the Anchor-compatible discriminator is unknown).
+pub fn decode_instruction_discriminator(instruction_data: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
+    if cfg!(feature = "anchor_discriminators") {
+        let (
+            anchor_discriminator,
+            payload
+        ) = instruction_data
+            .split_at_checked(8)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        let (_, discriminator) = ANCHOR_INSTRUCTION_DISCRIMINATORS
+            .iter()
+            .find(|(known_discriminator, _)| known_discriminator == anchor_discriminator)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok((*discriminator, payload))
+    } else {
+        let (
+            discriminator,
+            payload
+        ) = instruction_data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok((*discriminator, payload))
+    }
+}
+
+/// Typed (off-chain & testing) view of the program's instructions, see '//? program's instructions' for each one's wire format.
+/// NOTE: `pack` & `unpack` use the wire format (the discriminator followed by the raw payload), the Borsh derive is a separate
+///       (length-prefixed) encoding for storing typed instructions. The program itself parses the raw bytes.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum DataStorageInstruction {
+    Create {
+        label: [u8; MAX_LABEL_LENGTH],
+        data: Vec<u8>
+    },
+    Edit {
+        data: Vec<u8>
+    },
+    Close,
+    Log,
+    Duplicate {
+        label: [u8; MAX_LABEL_LENGTH]
+    },
+    ReserveCapacity {
+        additional_capacity: u16
+    },
+    Append {
+        data: Vec<u8>
+    },
+    Lock {
+        lock_holder: Pubkey,
+        lock_expiry: i64
+    },
+    Unlock,
+    SelfTest,
+    TransferAuthority,
+    Seal,
+    CompareAndClose {
+        expected_data_hash: [u8; 32]
+    },
+    Truncate {
+        data_len: u16
+    },
+    Patch {
+        offset: u16,
+        data: Vec<u8>
+    },
+    SetCpiAllowlist {
+        allowlist: Vec<Pubkey>
+    },
+    RepairAuthority,
+    IsImmutable,
+    FactoryCreate {
+        label: [u8; MAX_LABEL_LENGTH],
+        max_size: u32,
+        data: Vec<u8>
+    },
+    VerifyData,
+    AppendPatchLog {
+        offset: u16,
+        data: Vec<u8>
+    },
+    CompactPatchLog,
+    SetDelegate {
+        delegate: Pubkey
+    },
+    Attest,
+    CloseExpired,
+    ReadData,
+    CreateVariableLabel {
+        label: Vec<u8>,
+        max_size: u32,
+        expires_at: i64,
+        data: Vec<u8>
+    },
+    SoftClose,
+    Revive {
+        max_size: u32,
+        expires_at: i64,
+        data: Vec<u8>
+    },
+    BatchCreate {
+        accounts: Vec<([u8; MAX_LABEL_LENGTH], Vec<u8>)>
+    },
+    MigrateLayout,
+    CreateCompressed {
+        label: [u8; MAX_LABEL_LENGTH],
+        max_size: u32,
+        expires_at: i64,
+        data: Vec<u8>
+    },
+    CompareAndSwap {
+        expected_data_hash: [u8; 32],
+        data: Vec<u8>
+    },
+    TopUp,
+    CreateKeyValue {
+        label: [u8; MAX_LABEL_LENGTH],
+        max_size: u32,
+        expires_at: i64,
+        data: Vec<u8>
+    },
+    SetEntry {
+        key: Vec<u8>,
+        value: Vec<u8>
+    },
+    GetEntry {
+        key: Vec<u8>
+    },
+    RemoveEntry {
+        key: Vec<u8>
+    },
+    Touch,
+    Rename {
+        label: [u8; MAX_LABEL_LENGTH]
+    },
+    AllocateChunks {
+        total_len: u32
+    },
+    WriteChunk {
+        chunk_index: u16,
+        offset: u16,
+        data: Vec<u8>
+    },
+    ReadChunk {
+        chunk_index: u16,
+        offset: u16
+    },
+    SetImmutableWindow {
+        lock_until: i64
+    },
+    InitConfig {
+        max_data_len: u32,
+        creation_fee: u64,
+        treasury: Pubkey
+    },
+    UpdateConfig {
+        admin: Pubkey,
+        max_data_len: u32,
+        creation_fee: u64,
+        treasury: Pubkey
+    },
+    CreateIdempotent {
+        label: [u8; MAX_LABEL_LENGTH],
+        max_size: u32,
+        expires_at: i64,
+        data: Vec<u8>
+    },
+    GetMetadata,
+    CreateWithOptions {
+        label: [u8; MAX_LABEL_LENGTH],
+        max_size: u32,
+        expires_at: i64,
+        data: Vec<u8>
+    },
+    FactoryCreateWithOptions {
+        label: [u8; MAX_LABEL_LENGTH],
+        max_size: u32,
+        expires_at: i64,
+        data: Vec<u8>
+    },
+    EditWithOptions {
+        display_label: Option<[u8; MAX_LABEL_LENGTH]>,
+        idempotency_key: Option<u64>,
+        expected_revision: Option<u32>,
+        compressed: bool,
+        data: Vec<u8>
+    },
+    CloseChunks
+}
+
+impl DataStorageInstruction {
+    /// The instruction's `u8` discriminator.
+    pub fn discriminator(&self) -> u8 {
+        match self {
+            Self::Create { .. } => CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Edit { .. } => EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Close => CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Log => LOG_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Duplicate { .. } => DUPLICATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::ReserveCapacity { .. } => RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR,
+            Self::Append { .. } => APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Lock { .. } => LOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Unlock => UNLOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::SelfTest => SELF_TEST_INSTRUCTION_DISCRIMINATOR,
+            Self::TransferAuthority => TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR,
+            Self::Seal => SEAL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::CompareAndClose { .. } => COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Truncate { .. } => TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Patch { .. } => PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::SetCpiAllowlist { .. } => SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR,
+            Self::RepairAuthority => REPAIR_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR,
+            Self::IsImmutable => IS_DATA_STORAGE_ACCOUNT_IMMUTABLE_INSTRUCTION_DISCRIMINATOR,
+            Self::FactoryCreate { .. } => FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::VerifyData => VERIFY_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR,
+            Self::AppendPatchLog { .. } => APPEND_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR,
+            Self::CompactPatchLog => COMPACT_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR,
+            Self::SetDelegate { .. } => SET_DATA_STORAGE_ACCOUNT_DELEGATE_INSTRUCTION_DISCRIMINATOR,
+            Self::Attest => ATTEST_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::CloseExpired => CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::ReadData => READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR,
+            Self::CreateVariableLabel { .. } => CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::SoftClose => SOFT_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Revive { .. } => REVIVE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::BatchCreate { .. } => BATCH_CREATE_DATA_STORAGE_ACCOUNTS_INSTRUCTION_DISCRIMINATOR,
+            Self::MigrateLayout => MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT_INSTRUCTION_DISCRIMINATOR,
+            Self::CreateCompressed { .. } => CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::CompareAndSwap { .. } => COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::TopUp => TOP_UP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::CreateKeyValue { .. } => CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::SetEntry { .. } => SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR,
+            Self::GetEntry { .. } => GET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR,
+            Self::RemoveEntry { .. } => REMOVE_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR,
+            Self::Touch => TOUCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::Rename { .. } => RENAME_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::AllocateChunks { .. } => ALLOCATE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR,
+            Self::WriteChunk { .. } => WRITE_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR,
+            Self::ReadChunk { .. } => READ_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR,
+            Self::SetImmutableWindow { .. } => SET_DATA_STORAGE_ACCOUNT_IMMUTABLE_WINDOW_INSTRUCTION_DISCRIMINATOR,
+            Self::InitConfig { .. } => INIT_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR,
+            Self::UpdateConfig { .. } => UPDATE_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR,
+            Self::CreateIdempotent { .. } => CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            Self::GetMetadata => GET_DATA_STORAGE_ACCOUNT_METADATA_INSTRUCTION_DISCRIMINATOR,
+            Self::CreateWithOptions { .. } => CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR,
+            Self::FactoryCreateWithOptions { .. } => FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR,
+            Self::EditWithOptions { .. } => EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR,
+            Self::CloseChunks => CLOSE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR
+        }
+    }
+
+    /// Encodes the instruction's data (see `encode_instruction_discriminator` for the discriminator).
+    pub fn pack(&self) -> Vec<u8> {
+        let mut instruction_data = encode_instruction_discriminator(self.discriminator());
+
+        match self {
+            Self::Create { label, data } => {
+                instruction_data.extend_from_slice(label);
+                instruction_data.extend_from_slice(data);
+            },
+            Self::FactoryCreate { label, max_size, data } => {
+                instruction_data.extend_from_slice(label);
+                instruction_data.extend_from_slice(&max_size.to_le_bytes());
+                instruction_data.extend_from_slice(data);
+            },
+            Self::CreateWithOptions { label, max_size, expires_at, data } |
+            Self::FactoryCreateWithOptions { label, max_size, expires_at, data } |
+            Self::CreateCompressed { label, max_size, expires_at, data } |
+            Self::CreateKeyValue { label, max_size, expires_at, data } |
+            Self::CreateIdempotent { label, max_size, expires_at, data } => {
+                instruction_data.extend_from_slice(label);
+                instruction_data.extend_from_slice(&max_size.to_le_bytes());
+                instruction_data.extend_from_slice(&expires_at.to_le_bytes());
+                instruction_data.extend_from_slice(data);
+            },
+            Self::EditWithOptions { display_label, idempotency_key, expected_revision, compressed, data } => {
+                let mut edit_flags = 0;
+                if display_label.is_some() {
+                    edit_flags |= EDIT_FLAG_DISPLAY_LABEL;
+                };
+                if idempotency_key.is_some() {
+                    edit_flags |= EDIT_FLAG_IDEMPOTENCY_KEY;
+                };
+                if expected_revision.is_some() {
+                    edit_flags |= EDIT_FLAG_EXPECTED_REVISION;
+                };
+                if *compressed {
+                    edit_flags |= EDIT_FLAG_COMPRESSED;
+                };
+
+                instruction_data.push(edit_flags);
+                if let Some(display_label) = display_label {
+                    instruction_data.extend_from_slice(display_label);
+                };
+                if let Some(idempotency_key) = idempotency_key {
+                    instruction_data.extend_from_slice(&idempotency_key.to_le_bytes());
+                };
+                if let Some(expected_revision) = expected_revision {
+                    instruction_data.extend_from_slice(&expected_revision.to_le_bytes());
+                };
+                instruction_data.extend_from_slice(data);
+            },
+            Self::Duplicate { label } |
+            Self::Rename { label } => instruction_data.extend_from_slice(label),
+            Self::ReserveCapacity { additional_capacity } => instruction_data.extend_from_slice(&additional_capacity.to_le_bytes()),
+            Self::Edit { data } |
+            Self::Append { data } => instruction_data.extend_from_slice(data),
+            Self::Lock { lock_holder, lock_expiry } => {
+                instruction_data.extend_from_slice(lock_holder.as_ref());
+                instruction_data.extend_from_slice(&lock_expiry.to_le_bytes());
+            },
+            Self::CompareAndClose { expected_data_hash } => instruction_data.extend_from_slice(expected_data_hash),
+            Self::Truncate { data_len } => instruction_data.extend_from_slice(&data_len.to_le_bytes()),
+            Self::Patch { offset, data } |
+            Self::AppendPatchLog { offset, data } => {
+                instruction_data.extend_from_slice(&offset.to_le_bytes());
+                instruction_data.extend_from_slice(data);
+            },
+            Self::SetCpiAllowlist { allowlist } => {
+                for allowed_program in allowlist {
+                    instruction_data.extend_from_slice(allowed_program.as_ref());
+                }
+            },
+            Self::SetDelegate { delegate } => instruction_data.extend_from_slice(delegate.as_ref()),
+            Self::CreateVariableLabel { label, max_size, expires_at, data } => {
+                instruction_data.push(label.len() as u8);
+                instruction_data.extend_from_slice(label);
+                instruction_data.extend_from_slice(&max_size.to_le_bytes());
+                instruction_data.extend_from_slice(&expires_at.to_le_bytes());
+                instruction_data.extend_from_slice(data);
+            },
+            Self::Revive { max_size, expires_at, data } => {
+                instruction_data.extend_from_slice(&max_size.to_le_bytes());
+                instruction_data.extend_from_slice(&expires_at.to_le_bytes());
+                instruction_data.extend_from_slice(data);
+            },
+            Self::BatchCreate { accounts } => {
+                instruction_data.push(accounts.len() as u8);
+                for (label, data) in accounts {
+                    instruction_data.extend_from_slice(label);
+                    instruction_data.extend_from_slice(&(data.len() as u16).to_le_bytes());
+                    instruction_data.extend_from_slice(data);
+                }
+            },
+            Self::CompareAndSwap { expected_data_hash, data } => {
+                instruction_data.extend_from_slice(expected_data_hash);
+                instruction_data.extend_from_slice(data);
+            },
+            Self::SetEntry { key, value } => {
+                instruction_data.push(key.len() as u8);
+                instruction_data.extend_from_slice(key);
+                instruction_data.extend_from_slice(value);
+            },
+            Self::GetEntry { key } |
+            Self::RemoveEntry { key } => instruction_data.extend_from_slice(key),
+            Self::AllocateChunks { total_len } => instruction_data.extend_from_slice(&total_len.to_le_bytes()),
+            Self::WriteChunk { chunk_index, offset, data } => {
+                instruction_data.extend_from_slice(&chunk_index.to_le_bytes());
+                instruction_data.extend_from_slice(&offset.to_le_bytes());
+                instruction_data.extend_from_slice(data);
+            },
+            Self::ReadChunk { chunk_index, offset } => {
+                instruction_data.extend_from_slice(&chunk_index.to_le_bytes());
+                instruction_data.extend_from_slice(&offset.to_le_bytes());
+            },
+            Self::SetImmutableWindow { lock_until } => instruction_data.extend_from_slice(&lock_until.to_le_bytes()),
+            Self::InitConfig { max_data_len, creation_fee, treasury } => {
+                instruction_data.extend_from_slice(&max_data_len.to_le_bytes());
+                instruction_data.extend_from_slice(&creation_fee.to_le_bytes());
+                instruction_data.extend_from_slice(treasury.as_ref());
+            },
+            Self::UpdateConfig { admin, max_data_len, creation_fee, treasury } => {
+                instruction_data.extend_from_slice(admin.as_ref());
+                instruction_data.extend_from_slice(&max_data_len.to_le_bytes());
+                instruction_data.extend_from_slice(&creation_fee.to_le_bytes());
+                instruction_data.extend_from_slice(treasury.as_ref());
+            },
+            Self::Close |
+            Self::Log |
+            Self::Unlock |
+            Self::SelfTest |
+            Self::TransferAuthority |
+            Self::Seal |
+            Self::RepairAuthority |
+            Self::IsImmutable |
+            Self::VerifyData |
+            Self::CompactPatchLog |
+            Self::Attest |
+            Self::CloseExpired |
+            Self::ReadData |
+            Self::SoftClose |
+            Self::MigrateLayout |
+            Self::TopUp |
+            Self::Touch |
+            Self::GetMetadata |
+            Self::CloseChunks => ()
+        };
+
+        instruction_data
+    }
+
+    /// Decodes an instruction's data, fails with `ProgramError::InvalidInstructionData` on an unknown discriminator or a
+    /// payload that is too short for it (or has unknown edit-flags).
+    /// NOTE: Only checks the payload's shape, the values (labels, lengths, ...) are validated by the program.
+    pub fn unpack(instruction_data: &[u8]) -> Result<Self, ProgramError> {
+        let (
+            discriminator,
+            payload
+        ) = decode_instruction_discriminator(instruction_data)?;
+
+        fn take<const N: usize>(payload: &[u8]) -> Result<([u8; N], &[u8]), ProgramError> {
+            let (
+                bytes,
+                payload
+            ) = payload
+                .split_at_checked(N)
+                .ok_or(ProgramError::InvalidInstructionData)?;
+
+            Ok((bytes.try_into().unwrap(), payload))
+        }
+
+        let instruction = match discriminator {
+            CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+                let (label, data) = take::<MAX_LABEL_LENGTH>(payload)?;
+
+                Self::Create { label, data: data.to_vec() }
+            },
+            FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+                let (label, payload) = take::<MAX_LABEL_LENGTH>(payload)?;
+                let (max_size, data) = take::<4>(payload)?;
+
+                Self::FactoryCreate { label, max_size: u32::from_le_bytes(max_size), data: data.to_vec() }
+            },
+            CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR |
+            FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR |
+            CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR |
+            CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR |
+            CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+                let (label, payload) = take::<MAX_LABEL_LENGTH>(payload)?;
+                let (max_size, payload) = take::<4>(payload)?;
+                let (expires_at, data) = take::<8>(payload)?;
+
+                let (max_size, expires_at, data) = (u32::from_le_bytes(max_size), i64::from_le_bytes(expires_at), data.to_vec());
+                match discriminator {
+                    CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR => Self::CreateWithOptions { label, max_size, expires_at, data },
+                    FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR => Self::FactoryCreateWithOptions { label, max_size, expires_at, data },
+                    CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::CreateCompressed { label, max_size, expires_at, data },
+                    CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::CreateIdempotent { label, max_size, expires_at, data },
+                    _ => Self::CreateKeyValue { label, max_size, expires_at, data }
+                }
+            },
+            EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Edit {
+                data: payload.to_vec()
+            },
+            EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR => {
+                let ([ edit_flags ], mut payload) = take::<1>(payload)?;
+                if edit_flags & !(EDIT_FLAG_DISPLAY_LABEL | EDIT_FLAG_IDEMPOTENCY_KEY | EDIT_FLAG_EXPECTED_REVISION | EDIT_FLAG_COMPRESSED) != 0 {
+                    return Err(
+                        ProgramError::InvalidInstructionData
+                    );
+                };
+
+                let mut display_label = None;
+                if edit_flags & EDIT_FLAG_DISPLAY_LABEL != 0 {
+                    let (label, rest) = take::<MAX_LABEL_LENGTH>(payload)?;
+                    (display_label, payload) = (Some(label), rest);
+                };
+                let mut idempotency_key = None;
+                if edit_flags & EDIT_FLAG_IDEMPOTENCY_KEY != 0 {
+                    let (key, rest) = take::<8>(payload)?;
+                    (idempotency_key, payload) = (Some(u64::from_le_bytes(key)), rest);
+                };
+                let mut expected_revision = None;
+                if edit_flags & EDIT_FLAG_EXPECTED_REVISION != 0 {
+                    let (revision, rest) = take::<4>(payload)?;
+                    (expected_revision, payload) = (Some(u32::from_le_bytes(revision)), rest);
+                };
+
+                Self::EditWithOptions {
+                    display_label,
+                    idempotency_key,
+                    expected_revision,
+                    compressed: edit_flags & EDIT_FLAG_COMPRESSED != 0,
+                    data: payload.to_vec()
+                }
+            },
+            CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Close,
+            LOG_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Log,
+            DUPLICATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Duplicate {
+                label: take::<MAX_LABEL_LENGTH>(payload)?.0
+            },
+            RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR => Self::ReserveCapacity {
+                additional_capacity: u16::from_le_bytes(take::<2>(payload)?.0)
+            },
+            APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Append {
+                data: payload.to_vec()
+            },
+            LOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+                let (lock_holder, payload) = take::<32>(payload)?;
+                let (lock_expiry, _) = take::<8>(payload)?;
+
+                Self::Lock {
+                    lock_holder: Pubkey::new_from_array(lock_holder),
+                    lock_expiry: i64::from_le_bytes(lock_expiry)
+                }
+            },
+            UNLOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Unlock,
+            SELF_TEST_INSTRUCTION_DISCRIMINATOR => Self::SelfTest,
+            TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR => Self::TransferAuthority,
+            SEAL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Seal,
+            COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::CompareAndClose {
+                expected_data_hash: take::<32>(payload)?.0
+            },
+            TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Truncate {
+                data_len: u16::from_le_bytes(take::<2>(payload)?.0)
+            },
+            PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR |
+            APPEND_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR => {
+                let (offset, data) = take::<2>(payload)?;
+
+                let (offset, data) = (u16::from_le_bytes(offset), data.to_vec());
+                if discriminator == PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR {
+                    Self::Patch { offset, data }
+                } else {
+                    Self::AppendPatchLog { offset, data }
+                }
+            },
+            SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR => {
+                if payload.len() % size_of::<Pubkey>() != 0 {
+                    return Err(
+                        ProgramError::InvalidInstructionData
+                    );
+                };
+
+                Self::SetCpiAllowlist {
+                    allowlist: payload
+                        .chunks_exact(size_of::<Pubkey>())
+                        .map(|allowed_program| Pubkey::new_from_array(allowed_program.try_into().unwrap()))
+                        .collect()
+                }
+            },
+            REPAIR_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR => Self::RepairAuthority,
+            IS_DATA_STORAGE_ACCOUNT_IMMUTABLE_INSTRUCTION_DISCRIMINATOR => Self::IsImmutable,
+            VERIFY_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR => Self::VerifyData,
+            COMPACT_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR => Self::CompactPatchLog,
+            SET_DATA_STORAGE_ACCOUNT_DELEGATE_INSTRUCTION_DISCRIMINATOR => Self::SetDelegate {
+                delegate: Pubkey::new_from_array(take::<32>(payload)?.0)
+            },
+            ATTEST_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Attest,
+            CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::CloseExpired,
+            READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR => Self::ReadData,
+            GET_DATA_STORAGE_ACCOUNT_METADATA_INSTRUCTION_DISCRIMINATOR => Self::GetMetadata,
+            CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+                let ([ label_len ], payload) = take::<1>(payload)?;
+                let (
+                    label,
+                    payload
+                ) = payload
+                    .split_at_checked(label_len as usize)
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+                let (max_size, payload) = take::<4>(payload)?;
+                let (expires_at, data) = take::<8>(payload)?;
+
+                Self::CreateVariableLabel {
+                    label: label.to_vec(),
+                    max_size: u32::from_le_bytes(max_size),
+                    expires_at: i64::from_le_bytes(expires_at),
+                    data: data.to_vec()
+                }
+            },
+            SOFT_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::SoftClose,
+            REVIVE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+                let (max_size, payload) = take::<4>(payload)?;
+                let (expires_at, data) = take::<8>(payload)?;
+
+                Self::Revive {
+                    max_size: u32::from_le_bytes(max_size),
+                    expires_at: i64::from_le_bytes(expires_at),
+                    data: data.to_vec()
+                }
+            },
+            BATCH_CREATE_DATA_STORAGE_ACCOUNTS_INSTRUCTION_DISCRIMINATOR => {
+                let ([ accounts_count ], mut payload) = take::<1>(payload)?;
+
+                let mut accounts = Vec::with_capacity(accounts_count as usize);
+                for _ in 0..accounts_count {
+                    let (label, rest) = take::<MAX_LABEL_LENGTH>(payload)?;
+                    let (data_len, rest) = take::<2>(rest)?;
+                    let (
+                        data,
+                        rest
+                    ) = rest
+                        .split_at_checked(u16::from_le_bytes(data_len) as usize)
+                        .ok_or(ProgramError::InvalidInstructionData)?;
+
+                    accounts.push((label, data.to_vec()));
+                    payload = rest;
+                }
+
+                Self::BatchCreate { accounts }
+            },
+            MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT_INSTRUCTION_DISCRIMINATOR => Self::MigrateLayout,
+            COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
+                let (expected_data_hash, data) = take::<32>(payload)?;
+
+                Self::CompareAndSwap {
+                    expected_data_hash,
+                    data: data.to_vec()
+                }
+            },
+            TOP_UP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::TopUp,
+            SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR => {
+                let ([ key_len ], payload) = take::<1>(payload)?;
+                let (
+                    key,
+                    value
+                ) = payload
+                    .split_at_checked(key_len as usize)
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+
+                Self::SetEntry {
+                    key: key.to_vec(),
+                    value: value.to_vec()
+                }
+            },
+            GET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR => Self::GetEntry {
+                key: payload.to_vec()
+            },
+            REMOVE_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR => Self::RemoveEntry {
+                key: payload.to_vec()
+            },
+            TOUCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Touch,
+            CLOSE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR => Self::CloseChunks,
+            RENAME_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => Self::Rename {
+                label: take::<MAX_LABEL_LENGTH>(payload)?.0
+            },
+            ALLOCATE_DATA_STORAGE_ACCOUNT_CHUNKS_INSTRUCTION_DISCRIMINATOR => Self::AllocateChunks {
+                total_len: u32::from_le_bytes(take::<4>(payload)?.0)
+            },
+            WRITE_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR => {
+                let (chunk_index, payload) = take::<2>(payload)?;
+                let (offset, data) = take::<2>(payload)?;
+
+                Self::WriteChunk {
+                    chunk_index: u16::from_le_bytes(chunk_index),
+                    offset: u16::from_le_bytes(offset),
+                    data: data.to_vec()
+                }
+            },
+            READ_DATA_STORAGE_ACCOUNT_CHUNK_INSTRUCTION_DISCRIMINATOR => {
+                let (chunk_index, payload) = take::<2>(payload)?;
+
+                Self::ReadChunk {
+                    chunk_index: u16::from_le_bytes(chunk_index),
+                    offset: u16::from_le_bytes(take::<2>(payload)?.0)
+                }
+            },
+            SET_DATA_STORAGE_ACCOUNT_IMMUTABLE_WINDOW_INSTRUCTION_DISCRIMINATOR => Self::SetImmutableWindow {
+                lock_until: i64::from_le_bytes(take::<8>(payload)?.0)
+            },
+            INIT_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR => {
+                let (max_data_len, payload) = take::<4>(payload)?;
+                let (creation_fee, payload) = take::<8>(payload)?;
+
+                Self::InitConfig {
+                    max_data_len: u32::from_le_bytes(max_data_len),
+                    creation_fee: u64::from_le_bytes(creation_fee),
+                    treasury: Pubkey::new_from_array(take::<32>(payload)?.0)
+                }
+            },
+            UPDATE_DATA_STORAGE_CONFIG_INSTRUCTION_DISCRIMINATOR => {
+                let (admin, payload) = take::<32>(payload)?;
+                let (max_data_len, payload) = take::<4>(payload)?;
+                let (creation_fee, payload) = take::<8>(payload)?;
+
+                Self::UpdateConfig {
+                    admin: Pubkey::new_from_array(admin),
+                    max_data_len: u32::from_le_bytes(max_data_len),
+                    creation_fee: u64::from_le_bytes(creation_fee),
+                    treasury: Pubkey::new_from_array(take::<32>(payload)?.0)
+                }
+            },
+            _ => return Err(
+                ProgramError::InvalidInstructionData
+            )
+        };
+
+        Ok(instruction)
+    }
+}
+
+/// Derives the data-storage account pda (and it's bump) of `authority` and `label`, the same way the create handler does.
+pub fn find_data_storage_pda(
+    authority: &Pubkey,
+    label: &[u8; MAX_LABEL_LENGTH],
+    program_id: &Pubkey
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority.as_ref(),
+            label
+        ],
+        program_id
+    )
+}
+
+/// Same as `find_data_storage_pda` with a known `bump` (e.g. the one stored in the account's header), skips the bump search.
+/// Fails with `ProgramError::InvalidSeeds` when the seeds land on the ed25519 curve.
+pub fn create_data_storage_pda(
+    authority: &Pubkey,
+    label: &[u8; MAX_LABEL_LENGTH],
+    bump: u8,
+    program_id: &Pubkey
+) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority.as_ref(),
+            label,
+            &[ bump ]
+        ],
+        program_id
+    ).map_err(|_| ProgramError::InvalidSeeds)
+}
+
+/// Derives the pda (and it's bump) of a variable-label account (see "CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT"),
+/// `label` is the unpadded label.
+/// NOTE: Panics when `label` is longer than `MAX_VARIABLE_LABEL_LENGTH`.
+pub fn find_variable_label_data_storage_pda(
+    authority: &Pubkey,
+    label: &[u8],
+    program_id: &Pubkey
+) -> (Pubkey, u8) {
+    assert!(label.len() <= MAX_VARIABLE_LABEL_LENGTH, "label is too long");
+
+    let label_seed = variable_label_seed(label);
+    let [
+        label_seed_0,
+        label_seed_1,
+        label_seed_2
+    ] = split_label_seed(&label_seed);
+
+    Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority.as_ref(),
+            label_seed_0,
+            label_seed_1,
+            label_seed_2
+        ],
+        program_id
+    )
+}
+
+/// Derives the pda (and it's bump) of a chunked data-storage account's `chunk_index`-th chunk (see '//? chunks').
+pub fn find_chunk_pda(
+    data_storage_account: &Pubkey,
+    chunk_index: u16,
+    program_id: &Pubkey
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CHUNK_SEED_PREFIX,
+            data_storage_account.as_ref(),
+            &chunk_index.to_le_bytes()
+        ],
+        program_id
+    )
+}
+
+/// Returns a data-storage account's data-field as a `&str`, for accounts that hold text.
+/// Fails with `DataStorageError::InvalidData` when the data-field is not valid utf-8.
+pub fn data_as_str(account_data: &[u8]) -> Result<&str, ProgramError> {
+    let data_len = u16::from_le_bytes(
+        account_data
+            .get(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
+            .ok_or(ProgramError::InvalidAccountData)?
+            .try_into()
+            .unwrap()
+    ) as usize;
+
+    let data = account_data
+        .get(DATA_OFFSET..DATA_OFFSET + data_len)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    core::str::from_utf8(data).map_err(|_| DataStorageError::InvalidData.into())
+}
+
+/// Returns whether a data-storage account has been edited (edit/append/truncate/patch) since it was created,
+/// unlike 'last-updated' this can't be confused with a genuine '0' timestamp.
+pub fn has_been_edited(account_data: &[u8]) -> Result<bool, ProgramError> {
+    account_data
+        .get(HAS_BEEN_EDITED_OFFSET)
+        .map(|has_been_edited| *has_been_edited != 0)
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+/// Typed (off-chain & CPI) view of a data-storage account, see '//? program's data account' for the byte layout.
+/// NOTE: The program itself reads & writes the raw bytes, deserializing the whole account would cost too many compute-units.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct DataStorageAccount {
+    pub owner: Pubkey,
+    pub label: [u8; MAX_LABEL_LENGTH],
+    pub last_updated: i64,
+    pub bump: u8,
+    pub is_initialized: bool,
+    pub data_len: u16,
+    pub max_size: u32,
+    pub lock_holder: Pubkey,
+    pub lock_expiry: i64,
+    pub display_label: [u8; MAX_LABEL_LENGTH],
+    pub idempotency_key: u64,
+    pub cpi_allowlist: [Pubkey; MAX_CPI_ALLOWLIST_LEN],
+    pub has_been_edited: bool,
+    pub created_at: i64,
+    pub revision: u32,
+    pub data_hash: [u8; 32],
+    pub delegate: Pubkey,
+    pub expires_at: i64,
+    pub last_editor: Pubkey,
+    pub layout_version: u8,
+    /// The (unpadded) variable label, empty for `LAYOUT_VERSION_FIXED_LABEL` accounts.
+    pub variable_label: Vec<u8>,
+    pub tombstone: bool,
+    pub closed_at: i64,
+    pub header_version: u8,
+    /// The data-field is stored compressed, the program never inflates it.
+    pub compressed: bool,
+    /// How the data-field is laid out, `ACCOUNT_KIND_BYTES`, `ACCOUNT_KIND_KEY_VALUE` (see `read_entries`) or
+    /// `ACCOUNT_KIND_CHUNKED` (the chunks' metadata, see `find_chunk_pda`).
+    pub kind: u8,
+    /// The data-field can't be written before this unix-timestamp, `0` when there is no immutable window.
+    pub lock_until: i64,
+    /// Always `DATA_STORAGE_ACCOUNT_DISCRIMINATOR`, `unpack` rejects any other account.
+    pub account_discriminator: u8,
+    pub data: Vec<u8>
+}
+
+impl DataStorageAccount {
+    /// Decodes a data-storage account's data, the reserved capacity beyond the data-field is dropped.
+    /// Fails with `ProgramError::InvalidAccountData` when the data is malformed and `ProgramError::UninitializedAccount`
+    /// when the account isn't initialized (tombstones excepted, see `tombstone`).
+    pub fn unpack(account_data: &[u8]) -> Result<Self, ProgramError> {
+        if account_data.len() < HEADER_LEN {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+
+        let (
+            header,
+            data
+        ) = account_data.split_at(HEADER_LEN);
+
+        let pubkey_at = |offset: usize| Pubkey::new_from_array(header[offset..offset + 32].try_into().unwrap());
+        let i64_at = |offset: usize| i64::from_le_bytes(header[offset..offset + 8].try_into().unwrap());
+
+        if header[ACCOUNT_DISCRIMINATOR_OFFSET] != DATA_STORAGE_ACCOUNT_DISCRIMINATOR {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+
+        // tombstones are uninitialized, but still unpack so their 'closed_at' can be read
+        if header[INIT_OFFSET] == false as u8 && header[TOMBSTONE_OFFSET] == false as u8 {
+            return Err(
+                ProgramError::UninitializedAccount
+            );
+        };
+
+        let data_len = u16::from_le_bytes(header[DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>()].try_into().unwrap());
+        let data = data
+            .get(..data_len as usize)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        let variable_label = header
+            .get(VARIABLE_LABEL_OFFSET..VARIABLE_LABEL_OFFSET + header[VARIABLE_LABEL_LEN_OFFSET] as usize)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        let mut cpi_allowlist = [Pubkey::default(); MAX_CPI_ALLOWLIST_LEN];
+        for (index, allowed_program) in cpi_allowlist.iter_mut().enumerate() {
+            *allowed_program = pubkey_at(CPI_ALLOWLIST_OFFSET + index * size_of::<Pubkey>());
+        };
+
+        Ok(
+            Self {
+                owner: pubkey_at(OWNER_OFFSET),
+                label: header[LABEL_OFFSET..LABEL_OFFSET + MAX_LABEL_LENGTH].try_into().unwrap(),
+                last_updated: i64_at(LAST_UPDATED_OFFSET),
+                bump: header[BUMP_OFFSET],
+                is_initialized: header[INIT_OFFSET] != 0,
+                data_len,
+                max_size: u32::from_le_bytes(header[MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + size_of::<u32>()].try_into().unwrap()),
+                lock_holder: pubkey_at(LOCK_HOLDER_OFFSET),
+                lock_expiry: i64_at(LOCK_EXPIRY_OFFSET),
+                display_label: header[DISPLAY_LABEL_OFFSET..DISPLAY_LABEL_OFFSET + MAX_LABEL_LENGTH].try_into().unwrap(),
+                idempotency_key: u64::from_le_bytes(header[IDEMPOTENCY_KEY_OFFSET..IDEMPOTENCY_KEY_OFFSET + size_of::<u64>()].try_into().unwrap()),
+                cpi_allowlist,
+                has_been_edited: header[HAS_BEEN_EDITED_OFFSET] != 0,
+                created_at: i64_at(CREATED_AT_OFFSET),
+                revision: u32::from_le_bytes(header[REVISION_OFFSET..REVISION_OFFSET + size_of::<u32>()].try_into().unwrap()),
+                data_hash: header[DATA_HASH_OFFSET..DATA_HASH_OFFSET + size_of::<Hash>()].try_into().unwrap(),
+                delegate: pubkey_at(DELEGATE_OFFSET),
+                expires_at: i64_at(EXPIRES_AT_OFFSET),
+                last_editor: pubkey_at(LAST_EDITOR_OFFSET),
+                layout_version: header[LAYOUT_VERSION_OFFSET],
+                variable_label: variable_label.to_vec(),
+                tombstone: header[TOMBSTONE_OFFSET] != 0,
+                closed_at: i64_at(CLOSED_AT_OFFSET),
+                header_version: header[HEADER_VERSION_OFFSET],
+                compressed: header[COMPRESSED_OFFSET] != 0,
+                kind: header[KIND_OFFSET],
+                lock_until: i64_at(LOCK_UNTIL_OFFSET),
+                account_discriminator: header[ACCOUNT_DISCRIMINATOR_OFFSET],
+                data: data.to_vec()
+            }
+        )
+    }
+}
+
+/// Zero-copy view of a data-storage account's data, for programs that read a few header fields (e.g. of an account passed
+/// to them) without unpacking the whole account. Every accessor checks it's bounds and fails with
+/// `ProgramError::InvalidAccountData` on a too short buffer, instead of panicking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataStorageAccountView<'a>(pub &'a [u8]);
+
+impl<'a> DataStorageAccountView<'a> {
+    fn bytes<const N: usize>(&self, offset: usize) -> Result<[u8; N], ProgramError> {
+        self.0
+            .get(offset..offset + N)
+            .map(|bytes| bytes.try_into().unwrap())
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    pub fn owner(&self) -> Result<Pubkey, ProgramError> {
+        self.bytes::<32>(OWNER_OFFSET).map(Pubkey::new_from_array)
+    }
+
+    /// The 30-byte (zero-padded) seed-label.
+    pub fn label(&self) -> Result<&'a [u8], ProgramError> {
+        self.0
+            .get(LABEL_OFFSET..LABEL_OFFSET + MAX_LABEL_LENGTH)
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    pub fn last_updated(&self) -> Result<i64, ProgramError> {
+        self.bytes::<8>(LAST_UPDATED_OFFSET).map(i64::from_le_bytes)
+    }
+
+    pub fn bump(&self) -> Result<u8, ProgramError> {
+        self.bytes::<1>(BUMP_OFFSET).map(|[ bump ]| bump)
+    }
+
+    pub fn is_initialized(&self) -> Result<bool, ProgramError> {
+        self.bytes::<1>(INIT_OFFSET).map(|[ is_initialized ]| is_initialized != 0)
+    }
+
+    pub fn data_len(&self) -> Result<u16, ProgramError> {
+        self.bytes::<2>(DATA_LEN_OFFSET).map(u16::from_le_bytes)
+    }
+
+    pub fn created_at(&self) -> Result<i64, ProgramError> {
+        self.bytes::<8>(CREATED_AT_OFFSET).map(i64::from_le_bytes)
+    }
+
+    pub fn revision(&self) -> Result<u32, ProgramError> {
+        self.bytes::<4>(REVISION_OFFSET).map(u32::from_le_bytes)
+    }
+
+    /// The data-field, the reserved capacity beyond it excluded.
+    pub fn data(&self) -> Result<&'a [u8], ProgramError> {
+        let data_len = self.data_len()? as usize;
+
+        self.0
+            .get(DATA_OFFSET..DATA_OFFSET + data_len)
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+}
+
+/// The header fields "GET_DATA_STORAGE_ACCOUNT_METADATA" returns (borsh-encoded), the data-field excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct DataStorageAccountMetadata {
+    pub owner: Pubkey,
+    pub label: [u8; MAX_LABEL_LENGTH],
+    pub last_updated: i64,
+    pub created_at: i64,
+    pub revision: u32,
+    pub data_len: u16
+}
+
+impl DataStorageAccountMetadata {
+    /// Reads the metadata from a data-storage account's header.
+    pub fn from_view(view: &DataStorageAccountView) -> Result<Self, ProgramError> {
+        Ok(
+            Self {
+                owner: view.owner()?,
+                label: view
+                    .label()?
+                    .try_into()
+                    .unwrap(),
+                last_updated: view.last_updated()?,
+                created_at: view.created_at()?,
+                revision: view.revision()?,
+                data_len: view.data_len()?
+            }
+        )
+    }
+
+    /// Decodes "GET_DATA_STORAGE_ACCOUNT_METADATA"'s return-data, fails with `ProgramError::InvalidAccountData` when it's malformed.
+    pub fn unpack(return_data: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(return_data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+/// Typed view of the config pda, see '//? config' for the byte layout (which is the struct's borsh encoding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct DataStorageConfig {
+    pub admin: Pubkey,
+    /// `0` means `MAX_DATA_LEN`.
+    pub max_data_len: u32,
+    /// Lamports the funding account pays the treasury for every account created with the config, `0` means none.
+    pub creation_fee: u64,
+    pub treasury: Pubkey,
+    pub bump: u8
+}
+
+impl DataStorageConfig {
+    /// Decodes the config pda's data, fails with `ProgramError::InvalidAccountData` when it isn't `CONFIG_LEN` bytes long.
+    pub fn unpack(config_data: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(config_data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Encodes the config the way the config pda stores it.
+    pub fn pack(&self) -> [u8; CONFIG_LEN] {
+        borsh::to_vec(self)
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+}
+
+/// Derives the (single) config pda and it's bump, see '//? config'.
+pub fn find_config_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ CONFIG_SEED_PREFIX ],
+        program_id
+    )
+}
+
+/// Decodes a key-value account's data-field into it's `(key, value)` entries, in order (see '//? key-value entries').
+/// Fails with `ProgramError::InvalidAccountData` when an entry is truncated, a key is empty or set twice.
+#[allow(clippy::type_complexity)]
+pub fn read_entries(data_field: &[u8]) -> Result<Vec<(&[u8], &[u8])>, ProgramError> {
+    let mut entries: Vec<(&[u8], &[u8])> = Vec::new();
+
+    let mut data_field = data_field;
+    while let Some((key_len, rest)) = data_field.split_first() {
+        let (
+            key,
+            rest
+        ) = rest
+            .split_at_checked(*key_len as usize)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let (
+            value_len,
+            rest
+        ) = rest
+            .split_first_chunk::<2>()
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let (
+            value,
+            rest
+        ) = rest
+            .split_at_checked(u16::from_le_bytes(*value_len) as usize)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        if key.is_empty() || entries.iter().any(|(other_key, _)| *other_key == key) {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+
+        entries.push((key, value));
+        data_field = rest;
+    };
+
+    Ok(entries)
+}
+
+/// Returns the current data-field of a data-storage account, the patch-log's pending patches applied (oldest first) over the base.
+/// Stale patches (queued on an older base, see '//? patch-log') are ignored.
+/// Fails with `ProgramError::InvalidAccountData` when either account's data is malformed.
+pub fn read_materialized_data(account_data: &[u8], patch_log_data: &[u8]) -> Result<Vec<u8>, ProgramError> {
+    let data_len = u16::from_le_bytes(
+        account_data
+            .get(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
+            .ok_or(ProgramError::InvalidAccountData)?
+            .try_into()
+            .unwrap()
+    ) as usize;
+
+    let mut data = account_data
+        .get(DATA_OFFSET..DATA_OFFSET + data_len)
+        .ok_or(ProgramError::InvalidAccountData)?
+        .to_vec();
+
+    let (
+        head,
+        count
+    ) = read_pending_patches(patch_log_data, account_data)?;
+
+    fold_patch_log_entries(&mut data, patch_log_data, head, count);
+
+    Ok(data)
+}
+
+/// Returns a funding-ledger's '(funder, lamports)' entries, oldest first.
+/// Fails with `ProgramError::InvalidAccountData` when the data isn't made of whole entries.
+pub fn read_funding_ledger(ledger_data: &[u8]) -> Result<Vec<(Pubkey, u64)>, ProgramError> {
+    if !ledger_data.len().is_multiple_of(FUNDING_LEDGER_ENTRY_LEN) {
+        return Err(
+            ProgramError::InvalidAccountData
+        );
+    };
+
+    Ok(
+        ledger_data
+            .chunks_exact(FUNDING_LEDGER_ENTRY_LEN)
+            .map(|entry| {
+                let (funder, lamports) = entry.split_at(size_of::<Pubkey>());
+
+                (
+                    Pubkey::new_from_array(funder.try_into().unwrap()),
+                    u64::from_le_bytes(lamports.try_into().unwrap())
+                )
+            })
+            .collect()
+    )
+}
+
+/// Size (in bytes) of a data-storage account with a `data_len` bytes long data-field (or capacity), header included.
+/// Every account size the program creates, reallocs to or checks is computed by it, so they can't drift apart.
+pub fn total_account_size(data_len: usize) -> usize {
+    HEADER_LEN + data_len
+}
+
+/// Rent-exempt lamports of a data-storage account with a `data_len` bytes long data-field (header included), what
+/// "CREATE_NEW_DATA_STORAGE_ACCOUNT" needs the funding account to pay for an account without reserved capacity.
+pub fn rent_for_data(data_len: usize, rent: &Rent) -> u64 {
+    rent.minimum_balance(total_account_size(data_len))
+}
+
+/// Rent-exempt lamports difference between an account of `old_account_size` and one of `new_account_size` bytes (header included),
+/// same math as `Rent::minimum_balance` but fails with `ProgramError::ArithmeticOverflow` instead of overflowing `u64`.
+pub fn rent_exempt_lamports_delta(
+    rent: &Rent,
+    old_account_size: usize,
+    new_account_size: usize
+) -> Result<u64, ProgramError> {
+    let minimum_balance = |account_size: usize| -> Result<u64, ProgramError> {
+        let lamports = ACCOUNT_STORAGE_OVERHEAD
+            .checked_add(account_size as u64)
+            .and_then(|bytes| bytes.checked_mul(rent.lamports_per_byte_year))
+            .ok_or(ProgramError::ArithmeticOverflow)? as f64 * rent.exemption_threshold;
+
+        // 'as u64' would silently saturate
+        if lamports >= u64::MAX as f64 {
+            return Err(
+                ProgramError::ArithmeticOverflow
+            );
+        };
+
+        Ok(lamports as u64)
+    };
+
+    Ok(
+        minimum_balance(new_account_size)?.abs_diff(minimum_balance(old_account_size)?)
+    )
+}
+
+/// Off-chain helpers for deriving data-storage account addresses and building instructions.
+#[cfg(feature = "client")]
+pub mod client {
+    use {
+        super::{
+            Pubkey,
+            MAX_LABEL_LENGTH,
+            FUNDING_LEDGER_SEED_PREFIX,
+            SYSTEM_PROGRAM_ID,
+            DataStorageError,
+            DataStorageInstruction,
+            find_data_storage_pda,
+            find_config_pda
+        },
+        solana_program::{
+            pubkey,
+            program_error::ProgramError,
+            instruction::{
+                AccountMeta,
+                Instruction
+            }
+        }
+    };
+
+    /// SPL Memo program (v2+) id.
+    pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+    /// Derives the data-storage account pda (and it's bump) for `authority` and `label`.
+    pub fn derive_dsa_address(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        label: &[u8; MAX_LABEL_LENGTH]
+    ) -> (Pubkey, u8) {
+        find_data_storage_pda(authority, label, program_id)
+    }
+
+    /// Derives the funding-ledger pda (and it's bump) of `data_storage_account`.
+    pub fn derive_funding_ledger_address(
+        program_id: &Pubkey,
+        data_storage_account: &Pubkey
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                FUNDING_LEDGER_SEED_PREFIX,
+                data_storage_account.as_ref()
+            ],
+            program_id
+        )
+    }
+
+    /// Derives the data-storage account pdas for all of `authority`'s `labels`, in order.
+    pub fn derive_addresses(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        labels: &[[u8; MAX_LABEL_LENGTH]]
+    ) -> Vec<(Pubkey, u8)> {
+        labels
+            .iter()
+            .map(|label| derive_dsa_address(program_id, authority, label))
+            .collect()
+    }
+
+    /// Builds a "CREATE_NEW_DATA_STORAGE_ACCOUNT" instruction ("CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS" when `max_size` or
+    /// `expires_at` is set), pass `SYSTEM_PROGRAM_ID` as `authority` for an immutable account and `0` as `expires_at` for an
+    /// account that never expires. Push the treasury account when the config has a creation-fee.
+    pub fn create_instruction(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        funding_account: &Pubkey,
+        label: &[u8; MAX_LABEL_LENGTH],
+        max_size: u32,
+        expires_at: i64,
+        data: &[u8]
+    ) -> Instruction {
+        let (dsa_addr, _) = derive_dsa_address(program_id, authority, label);
+        let (config_addr, _) = find_config_pda(program_id);
+
+        Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(*authority, authority != &SYSTEM_PROGRAM_ID),
+                AccountMeta::new(*funding_account, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(config_addr, false)
+            ],
+            data: if max_size == 0 && expires_at == 0 {
+                DataStorageInstruction::Create {
+                    label: *label,
+                    data: data.to_vec()
+                }
+            } else {
+                DataStorageInstruction::CreateWithOptions {
+                    label: *label,
+                    max_size,
+                    expires_at,
+                    data: data.to_vec()
+                }
+            }.pack()
+        }
+    }
+
+    /// Zero-pads `label` to the on-chain 30-byte label, fails with `InvalidLabel` when it's longer than that.
+    pub fn pad_label(label: &str) -> Result<[u8; MAX_LABEL_LENGTH], ProgramError> {
+        if label.len() > MAX_LABEL_LENGTH {
+            return Err(
+                DataStorageError::InvalidLabel.into()
+            );
+        };
+
+        let mut padded_label = [0; MAX_LABEL_LENGTH];
+        padded_label[..label.len()].copy_from_slice(label.as_bytes());
+
+        Ok(padded_label)
+    }
+
+    /// Builds a "CREATE_NEW_DATA_STORAGE_ACCOUNT" instruction for a `label`ed account without a max-size or an expiry,
+    /// `payer` funds the account's rent.
+    pub fn create_data_storage_account_ix(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        payer: &Pubkey,
+        label: &str,
+        data: &[u8]
+    ) -> Result<Instruction, ProgramError> {
+        Ok(
+            create_instruction(
+                program_id,
+                authority,
+                payer,
+                &pad_label(label)?,
+                0,
+                0,
+                data
+            )
+        )
+    }
+
+    /// Builds an "EDIT_DATA_STORAGE_ACCOUNT" instruction that replaces the `old_data_len` bytes long data-field
+    /// with `new_data`, `payer` funds the grow or receives the freed rent on a shrink.
+    pub fn edit_ix(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        payer: &Pubkey,
+        label: &str,
+        old_data_len: usize,
+        new_data: &[u8]
+    ) -> Result<Instruction, ProgramError> {
+        let (dsa_addr, _) = derive_dsa_address(program_id, authority, &pad_label(label)?);
+
+        let mut accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(*authority, true)
+        ];
+        if new_data.len() > old_data_len {
+            accounts.push(AccountMeta::new(*payer, true));
+            accounts.push(AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false));
+        } else if new_data.len() < old_data_len {
+            accounts.push(AccountMeta::new(*payer, false));
+        };
+
+        Ok(
+            Instruction {
+                program_id: *program_id,
+                accounts,
+                data: DataStorageInstruction::Edit {
+                    data: new_data.to_vec()
+                }.pack()
+            }
+        )
+    }
+
+    /// Builds a "CLOSE_DATA_STORAGE_ACCOUNT" instruction, the account's rent goes to `rent_receiver`.
+    pub fn close_ix(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        rent_receiver: &Pubkey,
+        label: &str
+    ) -> Result<Instruction, ProgramError> {
+        let (dsa_addr, _) = derive_dsa_address(program_id, authority, &pad_label(label)?);
+
+        Ok(
+            Instruction {
+                program_id: *program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(*authority, true),
+                    AccountMeta::new(*rent_receiver, false)
+                ],
+                data: DataStorageInstruction::Close.pack()
+            }
+        )
+    }
+
+    /// Same as `create_instruction`, preceded by an SPL Memo instruction (signed by the funding account) for off-chain reconciliation.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_with_memo(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        funding_account: &Pubkey,
+        label: &[u8; MAX_LABEL_LENGTH],
+        max_size: u32,
+        expires_at: i64,
+        data: &[u8],
+        memo: &str
+    ) -> Vec<Instruction> {
+        vec![
+            Instruction {
+                program_id: MEMO_PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new_readonly(*funding_account, true)
+                ],
+                data: memo.as_bytes().to_vec()
+            },
+            create_instruction(
+                program_id,
+                authority,
+                funding_account,
+                label,
+                max_size,
+                expires_at,
+                data
+            )
+        ]
+    }
+
+    /// Same as `derive_addresses` but spreads the derivations over rayon's thread-pool, for large lists.
+    #[cfg(feature = "parallel")]
+    pub fn derive_addresses_parallel(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        labels: &[[u8; MAX_LABEL_LENGTH]]
+    ) -> Vec<(Pubkey, u8)> {
+        use rayon::prelude::*;
+
+        labels
+            .par_iter()
+            .map(|label| derive_dsa_address(program_id, authority, label))
+            .collect()
+    }
+}
+
+/// Integration-test helpers for crates that build on top of the data-storage program.
+#[cfg(feature = "test-utils")]
+pub mod test_utils {
+    use {
+        super::{
+            Pubkey,
+            MAX_LABEL_LENGTH,
+            DATA_STORAGE_SEED_PREFIX,
+            CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+            SYSTEM_PROGRAM_ID,
+            encode_instruction_discriminator,
+            find_config_pda
+        },
+        solana_program_test::{
+            BanksClientError,
+            ProgramTestContext
+        },
+        solana_sdk::{
+            account::Account,
+            instruction::{
+                AccountMeta,
+                Instruction
+            },
+            signature::Signer,
+            transaction::Transaction
+        }
+    };
+
+    /// A previous deployment's program-id, listed in `ALLOWED_LEGACY_PROGRAM_IDS` so it's accounts can be tested against.
+    pub const LEGACY_PROGRAM_ID: Pubkey = Pubkey::new_from_array([ 9; 32 ]);
+
+    /// The data-storage account's state after each step of `scenario`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ScenarioStates {
+        pub data_storage_account: Pubkey,
+        pub created: Account,
+        pub grown: Account,
+        pub shrunk: Account,
+        pub closed: Option<Account>
+    }
+
+    /// Drives a data-storage account through it's whole lifecycle, with the context's payer as the authority:
+    /// creates it (`"Solana"`), edits it up (`"Solana Data Storage"`) and down (`"Sol"`), then closes it.
+    /// NOTE: The data-storage program must have been added to the `ProgramTest` under `program_id`.
+    pub async fn scenario(
+        ptc: &mut ProgramTestContext,
+        program_id: &Pubkey,
+        label: &[u8; MAX_LABEL_LENGTH]
+    ) -> Result<ScenarioStates, BanksClientError> {
+        let authority = ptc.payer.pubkey();
+        let (dsa_addr, _) = Pubkey::find_program_address(
+            &[
+                DATA_STORAGE_SEED_PREFIX,
+                authority.as_ref(),
+                label
+            ],
+            program_id
+        );
+
+        // create
+        let created = process_and_fetch(
+            ptc,
+            Instruction {
+                program_id: *program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority, true),
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(find_config_pda(program_id).0, false)
+                ],
+                data: [
+                    encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    label.as_slice(),
+                    b"Solana"
+                ].concat()
+            },
+            &dsa_addr
+        ).await?.ok_or(BanksClientError::ClientError("data-storage account hasn't been created"))?;
+
+        // edit up
+        let grown = process_and_fetch(
+            ptc,
+            Instruction {
+                program_id: *program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority, true),
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+                ],
+                data: [
+                    encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    b"Solana Data Storage".as_slice()
+                ].concat()
+            },
+            &dsa_addr
+        ).await?.ok_or(BanksClientError::ClientError("data-storage account is missing after growing it"))?;
+
+        // edit down
+        let shrunk = process_and_fetch(
+            ptc,
+            Instruction {
+                program_id: *program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority, true),
+                    AccountMeta::new(authority, false)
+                ],
+                data: [
+                    encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    b"Sol".as_slice()
+                ].concat()
+            },
+            &dsa_addr
+        ).await?.ok_or(BanksClientError::ClientError("data-storage account is missing after shrinking it"))?;
+
+        // close
+        let closed = process_and_fetch(
+            ptc,
+            Instruction {
+                program_id: *program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority, true),
+                    AccountMeta::new(authority, false)
+                ],
+                data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+            },
+            &dsa_addr
+        ).await?;
+
+        Ok(
+            ScenarioStates {
+                data_storage_account: dsa_addr,
+                created,
+                grown,
+                shrunk,
+                closed
+            }
+        )
+    }
+
+    async fn process_and_fetch(
+        ptc: &mut ProgramTestContext,
+        instruction: Instruction,
+        data_storage_account: &Pubkey
+    ) -> Result<Option<Account>, BanksClientError> {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await?;
+
+        ptc
+            .banks_client
+            .get_account(*data_storage_account)
+            .await
+    }
+}
+
+mod helper {
+    use super::{
+        AccountInfo,
+        next_account_info,
+        Pubkey,
+        invoke,
+        invoke_signed,
+        transfer_lamports,
+        allocate_memory,
+        assign_new_owner,
+        create_new_account,
+        ProgramError,
+        ProgramResult,
+        check_system_program_id,
+        DataStorageError,
+        MAX_PERMITTED_DATA_INCREASE,
+        SYSTEM_PROGRAM_ID,
+        sol_memcmp,
+        sol_memcpy,
+        sol_memset,
+        size_of,
+        Clock,
+        MAX_DATA_LEN,
+        MIN_DATA_LEN,
+        HEADER_LEN,
+        OWNER_OFFSET,
+        LABEL_OFFSET,
+        LAST_UPDATED_OFFSET,
+        BUMP_OFFSET,
+        INIT_OFFSET,
+        DATA_LEN_OFFSET,
+        MAX_SIZE_OFFSET,
+        LOCK_HOLDER_OFFSET,
+        LOCK_EXPIRY_OFFSET,
+        DISPLAY_LABEL_OFFSET,
+        IDEMPOTENCY_KEY_OFFSET,
+        CPI_ALLOWLIST_OFFSET,
+        HAS_BEEN_EDITED_OFFSET,
+        CREATED_AT_OFFSET,
+        REVISION_OFFSET,
+        DATA_HASH_OFFSET,
+        DELEGATE_OFFSET,
+        EXPIRES_AT_OFFSET,
+        LAST_EDITOR_OFFSET,
+        LAYOUT_VERSION_OFFSET,
+        VARIABLE_LABEL_LEN_OFFSET,
+        VARIABLE_LABEL_OFFSET,
+        TOMBSTONE_OFFSET,
+        CLOSED_AT_OFFSET,
+        HEADER_VERSION_OFFSET,
+        COMPRESSED_OFFSET,
+        KIND_OFFSET,
+        LOCK_UNTIL_OFFSET,
+        ACCOUNT_DISCRIMINATOR_OFFSET,
+        DATA_STORAGE_ACCOUNT_DISCRIMINATOR,
+        DATA_OFFSET,
+        DATA_STORAGE_SEED_PREFIX,
+        ALLOWED_LEGACY_PROGRAM_IDS,
+        FUNDING_LEDGER_SEED_PREFIX,
+        FUNDING_LEDGER_ENTRY_LEN,
+        PATCH_LOG_SEED_PREFIX,
+        PATCH_LOG_CAPACITY,
+        PATCH_LOG_ENTRY_LEN,
+        PATCH_LOG_BASE_OFFSET,
+        PATCH_LOG_BASE_LEN,
+        PATCH_LOG_LEN,
+        CHUNK_SEED_PREFIX,
+        CHUNK_LEN,
+        CHUNKS_METADATA_LEN,
+        CONFIG_SEED_PREFIX,
+        CONFIG_BUMP_OFFSET,
+        CONFIG_LEN,
+        DataStorageConfig,
+        find_config_pda,
+        BPF_LOADER_UPGRADEABLE_ID,
+        UpgradeableLoaderState,
+        MAX_CPI_ALLOWLIST_LEN,
+        MAX_LABEL_LENGTH,
+        MAX_VARIABLE_LABEL_LENGTH,
+        MAX_SEED_LEN,
+        LAYOUT_VERSION_FIXED_LABEL,
+        LAYOUT_VERSION_VARIABLE_LABEL,
+        HEADER_VERSION,
+        V1_HEADER_LEN,
+        ACCOUNT_KIND_BYTES,
+        ACCOUNT_KIND_KEY_VALUE,
+        ACCOUNT_KIND_CHUNKED,
+        check_instructions_sysvar_id,
+        get_instruction_relative,
+        rent_exempt_lamports_delta,
+        rent_for_data,
+        total_account_size,
+        read_entries,
+        Hash,
+        hashv
+    };
+    use solana_program::{
+        sysvar::{
+            Sysvar,
+            rent::Rent
+        },
+        system_instruction::MAX_PERMITTED_DATA_LENGTH
+    };
+
+    // Validates & creates a new data-storage account (at it's canonical pda) and initializes it's header & data-field, returns
+    // it's display-label. Shared by create & batch-create, the caller checks the signers & the system program account.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn create_and_initialize_dsa_account<'a>(
+        new_data_storage_pda_account_info: &AccountInfo<'a>,
+        authority_account_info: &AccountInfo<'a>,
+        funding_account_info: &AccountInfo<'a>,
+        account_label: &[u8],
+        variable_label: bool,
+        compressed: bool,
+        kind: u8,
+        account_max_size: u32,
+        account_expires_at: i64,
+        account_data: &[u8],
+        program_id: &Pubkey,
+        cpi_accounts_info: &[AccountInfo<'a>]
+    ) -> Result<[u8; MAX_LABEL_LENGTH], ProgramError> {
+        // validate label
+        if variable_label {
+            check_variable_account_label(account_label)?;
+        } else {
+            check_account_label(account_label)?;
+        };
+
+        if account_expires_at < 0 {
+            return Err(
+                DataStorageError::InvalidData.into()
+            );
+        };
+
+        // immutable accounts can never grow, so a max-size for them is meaningless
+        if authority_account_info.key == &SYSTEM_PROGRAM_ID && account_max_size != 0 {
+            return Err(
+                DataStorageError::InvalidData.into()
+            );
+        };
+
+        // the stored data-length is a 'u16', so longer data-fields would be silently truncated
+        check_data_length_fits_header(account_data.len())?;
+
+        check_data_length_above_minimum(account_data.len())?;
+
+        // validate data-field length against the account's quota
+        check_data_length_within_quota(
+            account_data.len(),
+            account_max_size
+        )?;
+
+        // get pda's bump and validate the pda's pubkey
+        let label_seed = if variable_label {
+            variable_label_seed(account_label)
+        } else {
+            account_label.to_vec()
+        };
+        let [
+            label_seed_0,
+            label_seed_1,
+            label_seed_2
+        ] = split_label_seed(&label_seed);
+        let (
+            dsa_address,
+            dsa_bump
+        ) = Pubkey::try_find_program_address(
+            &[
+                DATA_STORAGE_SEED_PREFIX,
+                authority_account_info.key.as_ref(),
+                label_seed_0,
+                label_seed_1,
+                label_seed_2
+            ],
+            program_id
+        ).ok_or(DataStorageError::FailedToFindProgramAddress)?;
+        if &dsa_address != new_data_storage_pda_account_info.key {
+            return Err(
+                ProgramError::InvalidSeeds
+            );
+        };
+
+        // a soft-closed account's tombstone still holds the pda
+        check_dsa_account_is_not_tombstone(
+            new_data_storage_pda_account_info,
+            program_id
+        )?;
+
+        check_dsa_label_is_unused(
+            new_data_storage_pda_account_info,
+            program_id
+        )?;
+
+        // create the account
+        let account_size = total_account_size(account_data.len());
+
+        let seeds: &[&[u8]] = &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_account_info.key.as_ref(),
+            label_seed_0,
+            label_seed_1,
+            label_seed_2,
+            &[ dsa_bump ]
+        ];
+
+        // make sure the stored bump re-creates the same address, edit & close rely on it
+        create_and_check_program_address(
+            seeds,
+            program_id,
+            new_data_storage_pda_account_info.key
+        )?;
+
+        create_pda_account(
+            new_data_storage_pda_account_info,
+            funding_account_info,
+            account_size,
+            program_id,
+            seeds,
+            cpi_accounts_info
+        )?;
+        verbose_log!("New data storage account created.");
+
+        // display-label, (variable) labels longer than 30 bytes are cut at the last char boundary that fits
+        let display_label_len = (0..=account_label.len().min(MAX_LABEL_LENGTH))
+            .rev()
+            .find(|end| core::str::from_utf8(&account_label[..*end]).is_ok())
+            .unwrap();
+        let mut display_label = [0; MAX_LABEL_LENGTH];
+        display_label[..display_label_len].copy_from_slice(&account_label[..display_label_len]);
+        let current_time = (Clock::get()?).unix_timestamp;
+
+        // initialize the account, every field is written through a single borrow of the account's data (scoped, so it's
+        // released before returning)
+        {
+            let mut dsa_data = new_data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            // 1. set account-authority
+            sol_memcpy(
+                dsa_data
+                    .get_mut(OWNER_OFFSET..OWNER_OFFSET + size_of::<Pubkey>())
+                    .unwrap(),
+                authority_account_info.key.as_ref(),
+                size_of::<Pubkey>()
+            );
+            // 2. set account-label, variable labels go to the variable label's slot (with the layout-version)
+            if variable_label {
+                *dsa_data
+                    .get_mut(LAYOUT_VERSION_OFFSET)
+                    .unwrap() = LAYOUT_VERSION_VARIABLE_LABEL;
+                *dsa_data
+                    .get_mut(VARIABLE_LABEL_LEN_OFFSET)
+                    .unwrap() = account_label.len() as u8;
+                sol_memcpy(
+                    dsa_data
+                        .get_mut(VARIABLE_LABEL_OFFSET..VARIABLE_LABEL_OFFSET + account_label.len())
+                        .unwrap(),
+                    account_label,
+                    account_label.len()
+                );
+            } else {
+                sol_memcpy(
+                    dsa_data
+                        .get_mut(LABEL_OFFSET..LABEL_OFFSET + MAX_LABEL_LENGTH)
+                        .unwrap(),
+                    account_label,
+                    size_of::<[u8; 30]>()
+                );
+            };
+            // 3. skip 'last-updated'
+            // 4. set account-bump
+            *dsa_data
+                .get_mut(BUMP_OFFSET)
+                .unwrap() = dsa_bump;
+            // 5. set is_initialized flag
+            *dsa_data
+                .get_mut(INIT_OFFSET)
+                .unwrap() = true as u8;
+            // 6. set account-data length and data
+            // 1. set length
+            sol_memcpy(
+                dsa_data
+                    .get_mut(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
+                    .unwrap(),
+                &(account_data.len() as u16).to_le_bytes(),
+                size_of::<u16>()
+            );
+            // 2. set max-size
+            sol_memcpy(
+                dsa_data
+                    .get_mut(MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + size_of::<u32>())
+                    .unwrap(),
+                &account_max_size.to_le_bytes(),
+                size_of::<u32>()
+            );
+            // 3. set display-label
+            sol_memcpy(
+                dsa_data
+                    .get_mut(DISPLAY_LABEL_OFFSET..DISPLAY_LABEL_OFFSET + MAX_LABEL_LENGTH)
+                    .unwrap(),
+                &display_label,
+                MAX_LABEL_LENGTH
+            );
+            // 4. set created-at
+            sol_memcpy(
+                dsa_data
+                    .get_mut(CREATED_AT_OFFSET..CREATED_AT_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &current_time.to_le_bytes(),
+                size_of::<i64>()
+            );
+            // 5. set expires-at
+            sol_memcpy(
+                dsa_data
+                    .get_mut(EXPIRES_AT_OFFSET..EXPIRES_AT_OFFSET + size_of::<i64>())
+                    .unwrap(),
+                &account_expires_at.to_le_bytes(),
+                size_of::<i64>()
+            );
+            // 6. set last-editor
+            sol_memcpy(
+                dsa_data
+                    .get_mut(LAST_EDITOR_OFFSET..LAST_EDITOR_OFFSET + size_of::<Pubkey>())
+                    .unwrap(),
+                authority_account_info.key.as_ref(),
+                size_of::<Pubkey>()
+            );
+            // 7. set header-version, compressed flag, account-kind & account-discriminator
+            *dsa_data
+                .get_mut(HEADER_VERSION_OFFSET)
+                .unwrap() = HEADER_VERSION;
+            *dsa_data
+                .get_mut(COMPRESSED_OFFSET)
+                .unwrap() = compressed as u8;
+            *dsa_data
+                .get_mut(KIND_OFFSET)
+                .unwrap() = kind;
+            *dsa_data
+                .get_mut(ACCOUNT_DISCRIMINATOR_OFFSET)
+                .unwrap() = DATA_STORAGE_ACCOUNT_DISCRIMINATOR;
+            // 8. set data
+            sol_memcpy(
+                dsa_data
+                    .get_mut(DATA_OFFSET..DATA_OFFSET + account_data.len())
+                    .unwrap(),
+                account_data,
+                account_data.len()
+            );
+            // 9. set data-hash
+            update_dsa_data_hash(&mut dsa_data)?;
+        };
+
+        Ok(display_label)
+    }
+
+    pub(super) fn create_pda_account<'a>(
+        new_pda_account_info: &AccountInfo<'a>,
+        fee_payer_account_info: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        seeds: &[&[u8]],
+        cpi_accounts_info: &[AccountInfo<'a>]
+    ) -> ProgramResult {
+        let rent = Rent::get()?.minimum_balance(space);
+        let new_pda_account_balance = new_pda_account_info.lamports();
+
+        // an empty pda is funded, allocated & assigned by a single cpi, so it can never end up half-created
+        if new_pda_account_balance == 0 {
+            return invoke_signed(
+                &create_new_account(
+                    fee_payer_account_info.key,
+                    new_pda_account_info.key,
+                    rent,
+                    space as u64,
+                    program_id
+                ),
+                cpi_accounts_info,
+                &[ seeds ]
+            );
+        };
+
+        // 'create_account' rejects pre-funded pdas (lamports sent to the address before it's creation), top them up instead
+        if new_pda_account_balance < rent {
+            let lamports_needed = rent
+                .checked_sub(new_pda_account_balance)
+                .unwrap();
+            
+            invoke(
+                &transfer_lamports(
+                    fee_payer_account_info.key,
+                    new_pda_account_info.key,
+                    lamports_needed
+                ),
+                cpi_accounts_info
+            )?;
+        };
+    
+        allocate_and_assign_pda_account(
+            new_pda_account_info,
+            space,
+            program_id,
+            seeds
+        )
+    }
+
+    // NOTE: The caller is responsible for funding the account (before or after) so it ends up rent-exempt.
+    pub(super) fn allocate_and_assign_pda_account(
+        new_pda_account_info: &AccountInfo,
+        space: usize,
+        program_id: &Pubkey,
+        seeds: &[&[u8]]
+    ) -> ProgramResult {
+        invoke_signed(
+            &allocate_memory(
+                new_pda_account_info.key,
+                space as u64
+            ),
+            std::slice::from_ref(new_pda_account_info),
+            &[ seeds ]
+        )?;
+    
+        invoke_signed(
+            &assign_new_owner(
+                new_pda_account_info.key,
+                program_id
+            ),
+            std::slice::from_ref(new_pda_account_info),
+            &[ seeds ]
+        )?;
+    
+        Ok(())
+    }
+    
+    // Moves a data-storage account (lamports, header & data-field) to the pda derived from 'new_authority' and the same label,
+    // since the authority is one of the pda's seeds. The old account gets closed.
+    pub(super) fn migrate_dsa_account(
+        data_storage_pda_account_info: &AccountInfo,
+        new_data_storage_pda_account_info: &AccountInfo,
+        new_authority: &Pubkey,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        // reject an already existing destination account
+        if new_data_storage_pda_account_info.owner != &SYSTEM_PROGRAM_ID || !new_data_storage_pda_account_info.data_is_empty() {
+            return Err(
+                ProgramError::AccountAlreadyInitialized
+            );
+        };
+
+        // get new pda's bump and validate the new pda's pubkey
+        let label_seed = read_label_seed(data_storage_pda_account_info)?;
+        let [
+            label_seed_0,
+            label_seed_1,
+            label_seed_2
+        ] = split_label_seed(&label_seed);
+        let (
+            dsa_address,
+            dsa_bump
+        ) = Pubkey::try_find_program_address(
+            &[
+                DATA_STORAGE_SEED_PREFIX,
+                new_authority.as_ref(),
+                label_seed_0,
+                label_seed_1,
+                label_seed_2
+            ],
+            program_id
+        ).ok_or(DataStorageError::FailedToFindProgramAddress)?;
+        if &dsa_address != new_data_storage_pda_account_info.key {
+            return Err(
+                ProgramError::InvalidSeeds
+            );
+        };
+
+        let seeds: &[&[u8]] = &[
+            DATA_STORAGE_SEED_PREFIX,
+            new_authority.as_ref(),
+            label_seed_0,
+            label_seed_1,
+            label_seed_2,
+            &[ dsa_bump ]
+        ];
+
+        // make sure the stored bump re-creates the same address, edit & close rely on it
+        create_and_check_program_address(
+            seeds,
+            program_id,
+            new_data_storage_pda_account_info.key
+        )?;
+
+        // create the new account, it's funded below with the old account's lamports
+        let account_size = data_storage_pda_account_info.data_len();
+
+        allocate_and_assign_pda_account(
+            new_data_storage_pda_account_info,
+            account_size,
+            program_id,
+            seeds
+        )?;
+
+        // move all lamports to the new pda, they already cover the (same sized) new account's rent
+        let dsa_lamport_balance = data_storage_pda_account_info.lamports();
+
+        **data_storage_pda_account_info.try_borrow_mut_lamports()? = 0;
+
+        **new_data_storage_pda_account_info.try_borrow_mut_lamports()? = new_data_storage_pda_account_info
+            .lamports()
+            .checked_add(dsa_lamport_balance)
+            .unwrap();
+
+        // move the account's header and data-field
+        let mut dsa_data = data_storage_pda_account_info
+            .data
+            .try_borrow_mut()
+            .unwrap();
+        let mut new_dsa_data = new_data_storage_pda_account_info
+            .data
+            .try_borrow_mut()
+            .unwrap();
+        sol_memcpy(
+            &mut new_dsa_data,
+            &dsa_data,
+            account_size
+        );
+        // 1. set new account-authority
+        sol_memcpy(
+            new_dsa_data
+                .get_mut(OWNER_OFFSET..OWNER_OFFSET + size_of::<Pubkey>())
+                .unwrap(),
+            new_authority.as_ref(),
+            size_of::<Pubkey>()
+        );
+        // 2. set new account-bump
+        *new_dsa_data
+            .get_mut(BUMP_OFFSET)
+            .unwrap() = dsa_bump;
+        // 3. clear any (expired) lock
+        sol_memset(
+            new_dsa_data
+                .get_mut(LOCK_HOLDER_OFFSET..DISPLAY_LABEL_OFFSET)
+                .unwrap(),
+            0,
+            size_of::<Pubkey>() + size_of::<i64>()
+        );
+        // 4. the old authority's delegate doesn't carry over
+        sol_memset(
+            new_dsa_data
+                .get_mut(DELEGATE_OFFSET..DELEGATE_OFFSET + size_of::<Pubkey>())
+                .unwrap(),
+            0,
+            size_of::<Pubkey>()
+        );
+
+        // uninitialize the old data-storage account
+        wipe_dsa_account_data(&mut dsa_data);
+
+        Ok(())
+    }
+
+    // Drains the account's rent-exempt minimum to the rent-receiver and the lamports above it (sent directly to the pda) to
+    // the excess-receiver, uninitializes it and wipes it's data. Fails with 'UnexpectedLamports' on excess without an
+    // excess-receiver.
+    pub(super) fn close_dsa_account(
+        data_storage_pda_account_info: &AccountInfo,
+        rent_receiver_account_info: &AccountInfo,
+        excess_receiver_account_info: Option<&AccountInfo>
+    ) -> ProgramResult {
+        // split off the lamports above the rent-exempt minimum
+        let excess_lamports = data_storage_pda_account_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(data_storage_pda_account_info.data_len()));
+        if excess_lamports > 0 {
+            let excess_receiver_account_info = excess_receiver_account_info.ok_or::<ProgramError>(
+                DataStorageError::UnexpectedLamports.into()
+            )?;
+
+            **data_storage_pda_account_info.try_borrow_mut_lamports()? = data_storage_pda_account_info
+                .lamports()
+                .checked_sub(excess_lamports)
+                .unwrap();
+
+            **excess_receiver_account_info.try_borrow_mut_lamports()? = excess_receiver_account_info
+                .lamports()
+                .checked_add(excess_lamports)
+                .unwrap();
+        };
+
+        // transfer dsa remaining lamports to the receiver-account
+        let dsa_lamport_balance = data_storage_pda_account_info.lamports();
+
+        **data_storage_pda_account_info.try_borrow_mut_lamports()? = 0;
+
+        **rent_receiver_account_info.try_borrow_mut_lamports()? = rent_receiver_account_info
+            .lamports()
+            .checked_add(dsa_lamport_balance)
+            .unwrap();
+
+        // uninitialize the data-storage account
+        wipe_dsa_account_data(
+            &mut data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap()
+        );
+
+        Ok(())
+    }
+
+    // Zeroes everything but the authority, so a closed account revived in the same transaction can't leak it's old data.
+    // NOTE: The authority is kept so the revived account is rejected as uninitialized, not as immutable.
+    pub(super) fn wipe_dsa_account_data(dsa_data: &mut [u8]) {
+        let wiped_data = dsa_data
+            .get_mut(size_of::<Pubkey>()..)
+            .unwrap();
+        let wiped_data_len = wiped_data.len();
+
+        sol_memset(
+            wiped_data,
+            0,
+            wiped_data_len
+        );
+    }
+
+    // Wipes the header but the authority, labels & bump (and layout), which the tombstone keeps so it can be revived at the
+    // same pda, and marks it as a tombstone closed at 'closed_at'. The data-field must already be dropped.
+    pub(super) fn tombstone_dsa_account_data(dsa_data: &mut [u8], closed_at: i64) {
+        // 1. 'last-updated', is-initialized, data-length, max-size & lock
+        sol_memset(
+            dsa_data
+                .get_mut(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                .unwrap(),
+            0,
+            size_of::<i64>()
+        );
+        sol_memset(
+            dsa_data
+                .get_mut(INIT_OFFSET..DISPLAY_LABEL_OFFSET)
+                .unwrap(),
+            0,
+            DISPLAY_LABEL_OFFSET - INIT_OFFSET
+        );
+        // 2. idempotency-key, cpi-allowlist, has-been-edited, created-at, revision, data-hash, delegate, expires-at & last-editor
+        sol_memset(
+            dsa_data
+                .get_mut(IDEMPOTENCY_KEY_OFFSET..LAYOUT_VERSION_OFFSET)
+                .unwrap(),
+            0,
+            LAYOUT_VERSION_OFFSET - IDEMPOTENCY_KEY_OFFSET
+        );
+        // 3. compressed flag, account-kind & lock-until, the data-field is dropped
+        *dsa_data
+            .get_mut(COMPRESSED_OFFSET)
+            .unwrap() = false as u8;
+        *dsa_data
+            .get_mut(KIND_OFFSET)
+            .unwrap() = ACCOUNT_KIND_BYTES;
+        sol_memset(
+            dsa_data
+                .get_mut(LOCK_UNTIL_OFFSET..LOCK_UNTIL_OFFSET + size_of::<i64>())
+                .unwrap(),
+            0,
+            size_of::<i64>()
+        );
+        // 4. set tombstone & closed-at
+        *dsa_data
+            .get_mut(TOMBSTONE_OFFSET)
+            .unwrap() = true as u8;
+        sol_memcpy(
+            dsa_data
+                .get_mut(CLOSED_AT_OFFSET..CLOSED_AT_OFFSET + size_of::<i64>())
+                .unwrap(),
+            &closed_at.to_le_bytes(),
+            size_of::<i64>()
+        );
+    }
+
+    // Same as 'next_account_info' but fails with a descriptive `error` (which account is missing) instead of 'NotEnoughAccountKeys',
+    // for instructions whose account count depends on their data.
+    pub(super) fn next_required_account_info<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+        accounts_info: &mut I,
+        error: DataStorageError
+    ) -> Result<I::Item, ProgramError> {
+        accounts_info
+            .next()
+            .ok_or(ProgramError::Custom(error as u32))
+    }
+
+    // Pda signers (signed for by their program with 'invoke_signed') are accepted the same way as keypair signers.
+    pub(super) fn check_account_is_signer(account_info: &AccountInfo) -> ProgramResult {
+        if !account_info.is_signer {
+            return Err(
+                ProgramError::MissingRequiredSignature
+            );
+        };
+    
+        Ok(())
+    }
+    
+    pub(super) fn check_system_program_account(expected_program_id: &Pubkey) -> ProgramResult {
+        if !check_system_program_id(expected_program_id) {
+            return Err(
+                ProgramError::IncorrectProgramId
+            );
+        };
+    
+        Ok(())
+    }
+    
+    // Every header read assumes a full header, reject shorter (e.g. freshly allocated) accounts before any of them.
+    pub(super) fn check_dsa_account_header_len(data_storage_account_info: &AccountInfo) -> ProgramResult {
+        if data_storage_account_info.data_len() < HEADER_LEN {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+
+        Ok(())
+    }
+
+    // NOTE: If a data-storage account's authority is SYSTEM_PROGRAM_ACCOUNT thix means that the dsa is an immutable-account and it's authority cannot be a signer BUT
+    //  to be developer friendly we add this check to make the code more beautiful !
+    pub(super) fn check_if_data_storage_account_is_immutable(data_storage_account_info: &AccountInfo) -> ProgramResult {
+        let cmp_result = sol_memcmp(
+            data_storage_account_info
+                .try_borrow_data()?
+                .get(OWNER_OFFSET..OWNER_OFFSET + size_of::<Pubkey>())
+                .ok_or(ProgramError::InvalidAccountData)?,
+            &SYSTEM_PROGRAM_ID.to_bytes(),
+            size_of::<Pubkey>()
+        );
+    
+        if cmp_result == 0 {
+            return Err(
+                DataStorageError::ImmutableDataStorage.into()
+            )
+        };
+    
+        Ok(())
+    }
+    
+    // NOTE: The authority is one of the PDA's seeds so a dsa can never be it's own authority, we add this check to
+    //  give a precise error when a client passes the dsa as the authority account by mistake.
+    pub(super) fn check_authority_is_not_data_storage_account(
+        data_storage_account_info: &AccountInfo,
+        authority_account_info: &AccountInfo
+    ) -> ProgramResult {
+        if authority_account_info.key == data_storage_account_info.key {
+            return Err(
+                DataStorageError::InvalidData.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    // Labels must be printable utf-8 (no control characters), zero-padded at the end only so every logical label has a single
+    // 30-byte form (and so a single pda), and, as a defensive measure against ambiguous seeds, can't be the seed-prefix itself.
+    pub(super) fn check_account_label(account_label: &[u8]) -> ProgramResult {
+        // strip the trailing zero-padding, any NUL left is an interior one
+        let logical_label = account_label
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(&[][..], |last| &account_label[..=last]);
+
+        if core::str::from_utf8(logical_label).map_or(true, |label| label.chars().any(char::is_control)) ||
+            logical_label == DATA_STORAGE_SEED_PREFIX {
+            return Err(
+                DataStorageError::InvalidLabel.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    // Variable labels are 1..=64 bytes long and unpadded (no trailing NUL), otherwise the same rules as 'check_account_label' apply.
+    pub(super) fn check_variable_account_label(account_label: &[u8]) -> ProgramResult {
+        if account_label.is_empty() || account_label.len() > MAX_VARIABLE_LABEL_LENGTH || account_label.last() == Some(&0) {
+            return Err(
+                DataStorageError::InvalidLabel.into()
+            );
+        };
+
+        check_account_label(account_label)
+    }
+
+    pub(super) fn check_dsa_account_owner(
+        data_storage_account_info: &AccountInfo,
+        expected_owner: &Pubkey
+    ) -> ProgramResult {
+        if data_storage_account_info.owner != expected_owner {
+            return Err(
+                ProgramError::InvalidAccountOwner
+            );
+        };
+    
+        Ok(())
+    }
+
+    // Read-only instructions also accept accounts of the program's previous deployments, see 'ALLOWED_LEGACY_PROGRAM_IDS'.
+    // NOTE: Such accounts' pdas are derived from their owner, not from the current program-id.
+    pub(super) fn check_dsa_account_owner_or_legacy(
+        data_storage_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        if ALLOWED_LEGACY_PROGRAM_IDS.contains(data_storage_account_info.owner) {
+            return Ok(());
+        };
+
+        check_dsa_account_owner(
+            data_storage_account_info,
+            program_id
+        )
+    }
+    
+    pub(super) fn create_and_check_program_address(
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+        expected_data_storage_pda_account_pubkey: &Pubkey
+    ) -> ProgramResult {
+        let dsa_pda_addr = Pubkey::create_program_address(
+            seeds,
+            program_id
+        ).map_err(|_| DataStorageError::FailedToFindProgramAddress)?;
+    
+        if &dsa_pda_addr != expected_data_storage_pda_account_pubkey {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+    
+        Ok(())
+    }
+    
+    pub(super) fn check_dsa_account_authority(
+        data_storage_account_info: &AccountInfo,
+        expected_authority_pubkey: &Pubkey
+    ) -> ProgramResult {
+        let cmp_result = sol_memcmp(
+            data_storage_account_info
+                .try_borrow_data()?
+                .get(OWNER_OFFSET..OWNER_OFFSET + size_of::<Pubkey>())
+                .ok_or(ProgramError::InvalidAccountData)?,
+            expected_authority_pubkey.as_ref(),
+            size_of::<Pubkey>()
+        );
+    
+        if cmp_result != 0 {
+            return Err(
+                ProgramError::IncorrectAuthority
+            );
+        };
+    
+        Ok(())
+    }
+    
+    // Same as 'check_dsa_account_authority', but the account's delegate (if any) is accepted too.
+    pub(super) fn check_dsa_account_authority_or_delegate(
+        data_storage_account_info: &AccountInfo,
+        expected_authority_pubkey: &Pubkey
+    ) -> ProgramResult {
+        // SYSTEM_PROGRAM means "no delegate", it can't sign anyway
+        if expected_authority_pubkey != &SYSTEM_PROGRAM_ID && read_delegate(data_storage_account_info)? == *expected_authority_pubkey {
+            return Ok(());
+        };
+
+        check_dsa_account_authority(
+            data_storage_account_info,
+            expected_authority_pubkey
+        )
+    }
+
+    // Labels are unique per-authority, so an initialized account at the pda means the label is already used. Checked before
+    // the create cpi, which would only fail with the system program's 'AccountAlreadyInUse'.
+    pub(super) fn check_dsa_label_is_unused(
+        new_data_storage_pda_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        if new_data_storage_pda_account_info.owner == program_id &&
+            new_data_storage_pda_account_info
+                .try_borrow_data()?
+                .get(INIT_OFFSET)
+                .is_some_and(|is_initialized_flag| *is_initialized_flag != false as u8) {
+            return Err(
+                DataStorageError::LabelAlreadyUsed.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    pub(super) fn check_dsa_account_is_not_tombstone(
+        data_storage_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        if data_storage_account_info.owner == program_id &&
+            data_storage_account_info.data_len() >= HEADER_LEN &&
+            read_tombstone(data_storage_account_info) {
+            return Err(
+                DataStorageError::AccountTombstoned.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    // An initialized data-storage account always carries the account-discriminator, any other (initialized) account of the
+    // program isn't one. Older headers don't have it yet, "MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT" only checks the flag.
+    pub(super) fn check_dsa_account_is_initialized(data_storage_account_info: &AccountInfo) -> ProgramResult {
+        check_dsa_account_is_initialized_flag(data_storage_account_info)?;
+
+        check_dsa_account_discriminator(data_storage_account_info)
+    }
+
+    pub(super) fn check_dsa_account_is_initialized_flag(data_storage_account_info: &AccountInfo) -> ProgramResult {
+        let dsa_data = data_storage_account_info.try_borrow_data()?;
+    
+        let is_initialized_flag = *dsa_data
+            .get(INIT_OFFSET)
+            .ok_or(ProgramError::InvalidAccountData)?;
+    
+        if is_initialized_flag == false as u8 {
+            return Err(
+                ProgramError::UninitializedAccount
+            );
+        };
+    
+        Ok(())
+    }
+
+    pub(super) fn check_dsa_account_discriminator(data_storage_account_info: &AccountInfo) -> ProgramResult {
+        let account_discriminator = *data_storage_account_info
+            .try_borrow_data()?
+            .get(ACCOUNT_DISCRIMINATOR_OFFSET)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        if account_discriminator != DATA_STORAGE_ACCOUNT_DISCRIMINATOR {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+
+        Ok(())
+    }
+    
+    // Runs every check a data-storage account and it's authority must pass before the account can be modified.
+    pub(super) fn check_dsa_account_and_authority(
+        data_storage_account_info: &AccountInfo,
+        authority_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        check_dsa_account_and_signer(
+            data_storage_account_info,
+            authority_account_info,
+            program_id,
+            check_dsa_account_authority
+        )
+    }
+
+    // 'check_authority' decides which signers are accepted in the authority's place.
+    fn check_dsa_account_and_signer(
+        data_storage_account_info: &AccountInfo,
+        authority_account_info: &AccountInfo,
+        program_id: &Pubkey,
+        check_authority: fn(&AccountInfo, &Pubkey) -> ProgramResult
+    ) -> ProgramResult {
+        // validate account's owner-program before reading any of it's (otherwise untrusted) data
+        check_dsa_account_owner(
+            data_storage_account_info,
+            program_id
+        )?;
+
+        check_authority_is_not_data_storage_account(
+            data_storage_account_info,
+            authority_account_info
+        )?;
+
+        check_dsa_account_header_len(data_storage_account_info)?;
+
+        check_if_data_storage_account_is_immutable(data_storage_account_info)?;
+
+        check_account_is_signer(authority_account_info)?;
+
+        // check that account is initialized
+        check_dsa_account_is_initialized(data_storage_account_info)?;
+
+        // validate account's authority
+        check_authority(
+            data_storage_account_info,
+            authority_account_info.key
+        )?;
+
+        check_dsa_account_address(
+            data_storage_account_info,
+            program_id
+        )
+    }
+
+    // Same checks as 'check_dsa_account_and_authority', but the delegate can sign in the authority's place, and while
+    // the account is locked the lock-holder takes the authority's (and delegate's) place.
+    pub(super) fn check_dsa_account_and_editor(
+        data_storage_account_info: &AccountInfo,
+        editor_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        // validate account's owner-program before reading it's lock
+        check_dsa_account_owner(
+            data_storage_account_info,
+            program_id
+        )?;
+
+        check_dsa_account_header_len(data_storage_account_info)?;
+
+        match read_active_lock_holder(data_storage_account_info)? {
+            Some(lock_holder) => {
+                check_authority_is_not_data_storage_account(
+                    data_storage_account_info,
+                    editor_account_info
+                )?;
+
+                check_if_data_storage_account_is_immutable(data_storage_account_info)?;
+
+                check_account_is_signer(editor_account_info)?;
+
+                check_dsa_account_is_initialized(data_storage_account_info)?;
+
+                if editor_account_info.key != &lock_holder {
+                    return Err(
+                        DataStorageError::AccountLocked.into()
+                    );
+                };
+
+                check_dsa_account_address(
+                    data_storage_account_info,
+                    program_id
+                )
+            },
+            None => check_dsa_account_and_signer(
+                data_storage_account_info,
+                editor_account_info,
+                program_id,
+                check_dsa_account_authority_or_delegate
+            )
+        }
+    }
+
+    // Lock/Unlock can be signed by the authority, or by the holder of the current (active) lock.
+    pub(super) fn check_dsa_account_and_lock_manager(
+        data_storage_account_info: &AccountInfo,
+        signer_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        check_dsa_account_owner(
+            data_storage_account_info,
+            program_id
+        )?;
+
+        if read_active_lock_holder(data_storage_account_info)?.as_ref() == Some(signer_account_info.key) {
+            check_dsa_account_and_editor(
+                data_storage_account_info,
+                signer_account_info,
+                program_id
+            )
+        } else {
+            check_dsa_account_and_authority(
+                data_storage_account_info,
+                signer_account_info,
+                program_id
+            )
+        }
+    }
+
+    pub(super) fn check_dsa_account_lock(
+        data_storage_account_info: &AccountInfo,
+        signer_account_info: &AccountInfo
+    ) -> ProgramResult {
+        match read_active_lock_holder(data_storage_account_info)? {
+            Some(lock_holder) if &lock_holder != signer_account_info.key => Err(
+                DataStorageError::AccountLocked.into()
+            ),
+            _ => Ok(())
+        }
+    }
+
+    // Unlike the lock, an immutable window binds the authority (and the delegate) too, see "SET_DATA_STORAGE_ACCOUNT_IMMUTABLE_WINDOW".
+    pub(super) fn check_dsa_account_is_not_time_locked(data_storage_account_info: &AccountInfo) -> ProgramResult {
+        // '0' (no window) doesn't need the clock
+        let lock_until = read_lock_until(data_storage_account_info);
+        if lock_until != 0 && (Clock::get()?).unix_timestamp < lock_until {
+            return Err(
+                DataStorageError::TimeLocked.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    // When the account has a cpi-allowlist, the (top-level) instruction must belong to one of the allowed programs,
+    // which means the data-storage program has been invoked through them via CPI.
+    pub(super) fn check_dsa_account_caller(
+        data_storage_account_info: &AccountInfo,
+        program_id: &Pubkey,
+        accounts_info: &[AccountInfo]
+    ) -> ProgramResult {
+        let cpi_allowlist = read_cpi_allowlist(data_storage_account_info);
+        if cpi_allowlist.iter().all(|allowed_program| allowed_program == &Pubkey::default()) {
+            return Ok(());
+        };
+
+        let instructions_sysvar_account_info = accounts_info
+            .iter()
+            .find(|account_info| check_instructions_sysvar_id(account_info.key))
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let caller_program_id = get_instruction_relative(
+            0,
+            instructions_sysvar_account_info
+        )?.program_id;
+
+        if &caller_program_id == program_id || caller_program_id == Pubkey::default() || !cpi_allowlist.contains(&caller_program_id) {
+            return Err(
+                DataStorageError::UnauthorizedCaller.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    // validate PDA, re-derived from the stored authority, label & bump
+    pub(super) fn check_dsa_account_address(
+        data_storage_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        let label_seed = read_label_seed(data_storage_account_info)?;
+        let [
+            label_seed_0,
+            label_seed_1,
+            label_seed_2
+        ] = split_label_seed(&label_seed);
+
+        let dsa_data = data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap();
+
+        let authority = dsa_data
+            .get(OWNER_OFFSET..OWNER_OFFSET + size_of::<Pubkey>())
+            .unwrap();
+        let bump = *dsa_data
+            .get(BUMP_OFFSET)
+            .unwrap();
+
+        create_and_check_program_address(
+            &[
+                DATA_STORAGE_SEED_PREFIX,
+                authority,
+                label_seed_0,
+                label_seed_1,
+                label_seed_2,
+                &[ bump ]
+            ],
+            program_id,
+            data_storage_account_info.key
+        )
+    }
+
+    // Returns the lock-holder while the lock hasn't expired yet.
+    pub(super) fn read_active_lock_holder(data_storage_account_info: &AccountInfo) -> Result<Option<Pubkey>, ProgramError> {
+        let dsa_data = data_storage_account_info.try_borrow_data()?;
+
+        let lock_holder = Pubkey::new_from_array(
+            dsa_data
+                .get(LOCK_HOLDER_OFFSET..LOCK_HOLDER_OFFSET + size_of::<Pubkey>())
+                .ok_or(ProgramError::InvalidAccountData)?
+                .try_into()
+                .unwrap()
+        );
+        let lock_expiry = i64::from_le_bytes(
+            dsa_data
+                .get(LOCK_EXPIRY_OFFSET..LOCK_EXPIRY_OFFSET + size_of::<i64>())
+                .ok_or(ProgramError::InvalidAccountData)?
+                .try_into()
+                .unwrap()
+        );
+
+        if lock_holder == Pubkey::default() || (Clock::get()?).unix_timestamp >= lock_expiry {
+            return Ok(None);
+        };
+
+        Ok(Some(lock_holder))
+    }
+
+    pub(super) fn read_authority(data_storage_account_info: &AccountInfo) -> Pubkey {
+        Pubkey::new_from_array(
+            data_storage_account_info
+                .data
+                .try_borrow()
+                .unwrap()
+                .get(OWNER_OFFSET..OWNER_OFFSET + size_of::<Pubkey>())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        )
+    }
+
+    pub(super) fn read_delegate(data_storage_account_info: &AccountInfo) -> Result<Pubkey, ProgramError> {
+        Ok(
+            Pubkey::new_from_array(
+                data_storage_account_info
+                    .try_borrow_data()?
+                    .get(DELEGATE_OFFSET..DELEGATE_OFFSET + size_of::<Pubkey>())
+                    .ok_or(ProgramError::InvalidAccountData)?
+                    .try_into()
+                    .unwrap()
+            )
+        )
+    }
+
+    pub(super) fn read_expires_at(data_storage_account_info: &AccountInfo) -> i64 {
+        i64::from_le_bytes(
+            data_storage_account_info
+                .data
+                .try_borrow()
+                .unwrap()
+                .get(EXPIRES_AT_OFFSET..EXPIRES_AT_OFFSET + size_of::<i64>())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        )
+    }
+
+    // The label part of the pda's seeds, the seed-label or (for variable-label accounts) the variable label's seed.
+    pub(super) fn read_label_seed(data_storage_account_info: &AccountInfo) -> Result<Vec<u8>, ProgramError> {
+        let dsa_data = data_storage_account_info.try_borrow_data()?;
+
+        match *dsa_data
+            .get(LAYOUT_VERSION_OFFSET)
+            .ok_or(ProgramError::InvalidAccountData)? {
+            LAYOUT_VERSION_FIXED_LABEL => Ok(
+                dsa_data
+                    .get(LABEL_OFFSET..LABEL_OFFSET + MAX_LABEL_LENGTH)
+                    .unwrap()
+                    .to_vec()
+            ),
+            LAYOUT_VERSION_VARIABLE_LABEL => {
+                let label_len = *dsa_data
+                    .get(VARIABLE_LABEL_LEN_OFFSET)
+                    .unwrap() as usize;
+                if label_len > MAX_VARIABLE_LABEL_LENGTH {
+                    return Err(
+                        ProgramError::InvalidAccountData
+                    );
+                };
+
+                Ok(
+                    variable_label_seed(
+                        dsa_data
+                            .get(VARIABLE_LABEL_OFFSET..VARIABLE_LABEL_OFFSET + label_len)
+                            .unwrap()
+                    )
+                )
+            },
+            _ => Err(
+                ProgramError::InvalidAccountData
+            )
+        }
+    }
+
+    // A variable label's seed is it's length followed by the (unpadded) label.
+    pub(super) fn variable_label_seed(label: &[u8]) -> Vec<u8> {
+        [
+            &[ label.len() as u8 ],
+            label
+        ].concat()
+    }
+
+    // Splits a label-seed in 'MAX_SEED_LEN'-byte seeds, the unused ones are empty and don't change the derived address.
+    pub(super) fn split_label_seed(label_seed: &[u8]) -> [&[u8]; 3] {
+        let (
+            label_seed_0,
+            label_seed
+        ) = label_seed.split_at(label_seed.len().min(MAX_SEED_LEN));
+        let (
+            label_seed_1,
+            label_seed_2
+        ) = label_seed.split_at(label_seed.len().min(MAX_SEED_LEN));
+
+        [ label_seed_0, label_seed_1, label_seed_2 ]
+    }
+
+    pub(super) fn read_idempotency_key(data_storage_account_info: &AccountInfo) -> u64 {
+        u64::from_le_bytes(
+            data_storage_account_info
+                .data
+                .try_borrow()
+                .unwrap()
+                .get(IDEMPOTENCY_KEY_OFFSET..IDEMPOTENCY_KEY_OFFSET + size_of::<u64>())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        )
+    }
+
+    pub(super) fn read_cpi_allowlist(data_storage_account_info: &AccountInfo) -> [Pubkey; MAX_CPI_ALLOWLIST_LEN] {
+        let dsa_data = data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap();
+
+        let mut cpi_allowlist = [Pubkey::default(); MAX_CPI_ALLOWLIST_LEN];
+        for (allowed_program, program_key) in cpi_allowlist
+            .iter_mut()
+            .zip(
+                dsa_data
+                    .get(CPI_ALLOWLIST_OFFSET..CPI_ALLOWLIST_OFFSET + MAX_CPI_ALLOWLIST_LEN * size_of::<Pubkey>())
+                    .unwrap()
+                    .chunks_exact(size_of::<Pubkey>())
+            )
+        {
+            *allowed_program = Pubkey::new_from_array(program_key.try_into().unwrap());
+        };
+
+        cpi_allowlist
+    }
+
+    pub(super) fn read_lock_until(data_storage_account_info: &AccountInfo) -> i64 {
+        i64::from_le_bytes(
+            data_storage_account_info
+                .data
+                .try_borrow()
+                .unwrap()
+                .get(LOCK_UNTIL_OFFSET..LOCK_UNTIL_OFFSET + size_of::<i64>())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        )
+    }
+
+    pub(super) fn read_tombstone(data_storage_account_info: &AccountInfo) -> bool {
+        *data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap()
+            .get(TOMBSTONE_OFFSET)
+            .unwrap() != 0
+    }
+
+    // Returns the header-version an account has been created (or migrated) with. Version '1' accounts have no header-version
+    // byte, but they're the only ones exactly as long as their header & data-field: the data-length is at the same offset in
+    // every version and every later header is longer (tombstones have a zero data-length). Later versions store it at
+    // 'HEADER_VERSION_OFFSET'.
+    pub(super) fn read_header_version(data_storage_account_info: &AccountInfo) -> Result<u8, ProgramError> {
+        let dsa_data = data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap();
 
-            sol_log("New data storage account has been initialized successfully. ✅");
-        },
+        let data_len = u16::from_le_bytes(
+            dsa_data
+                .get(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
+                .ok_or(ProgramError::InvalidAccountData)?
+                .try_into()
+                .unwrap()
+        ) as usize;
+        if dsa_data.len() == V1_HEADER_LEN + data_len {
+            return Ok(1);
+        };
 
-        EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
-            sol_log("⚙️ Instruction: EditDataStorageAccount");
+        match dsa_data.get(HEADER_VERSION_OFFSET) {
+            Some(header_version) if (2..=HEADER_VERSION).contains(header_version) => Ok(*header_version),
+            _ => Err(
+                DataStorageError::MalformedAccount.into()
+            )
+        }
+    }
 
-            let data_storage_pda_account_info = next_account_info(accounts_info)?;
-            let authority_account_info = next_account_info(accounts_info)?;
+    pub(super) fn read_has_been_edited(data_storage_account_info: &AccountInfo) -> bool {
+        *data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap()
+            .get(HAS_BEEN_EDITED_OFFSET)
+            .unwrap() != 0
+    }
 
-            check_if_data_storage_account_is_immutable(data_storage_pda_account_info)?;
+    pub(super) fn read_revision(data_storage_account_info: &AccountInfo) -> u32 {
+        u32::from_le_bytes(
+            data_storage_account_info
+                .data
+                .try_borrow()
+                .unwrap()
+                .get(REVISION_OFFSET..REVISION_OFFSET + size_of::<u32>())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        )
+    }
 
-            check_account_is_signer(authority_account_info)?;
+    // sha-256 of the data-field only, the reserved capacity beyond it isn't part of the hash
+    pub(super) fn hash_dsa_data(dsa_data: &[u8]) -> Result<[u8; 32], ProgramError> {
+        let data_len = u16::from_le_bytes(
+            dsa_data
+                .get(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
+                .ok_or(ProgramError::InvalidAccountData)?
+                .try_into()
+                .unwrap()
+        ) as usize;
 
-            // validate account's owner-program
-            check_dsa_account_owner(
-                data_storage_pda_account_info,
-                program_id
-            )?;
+        let data_field = dsa_data
+            .get(DATA_OFFSET..DATA_OFFSET + data_len)
+            .ok_or(DataStorageError::MalformedAccount)?;
 
-            // check that account is initialized
-            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+        Ok(
+            hashv(&[ data_field ]).to_bytes()
+        )
+    }
 
-            // validate account's authority
-            check_dsa_account_authority(
-                data_storage_pda_account_info,
-                authority_account_info.key.to_bytes()
-            )?;
+    pub(super) fn update_dsa_data_hash(dsa_data: &mut [u8]) -> ProgramResult {
+        let data_hash = hash_dsa_data(dsa_data)?;
 
-            // deserialize account data
-            let dsa_data = data_storage_pda_account_info
+        sol_memcpy(
+            dsa_data
+                .get_mut(DATA_HASH_OFFSET..DATA_HASH_OFFSET + size_of::<Hash>())
+                .unwrap(),
+            &data_hash,
+            size_of::<Hash>()
+        );
+
+        Ok(())
+    }
+
+    // Returns the new revision.
+    pub(super) fn increment_dsa_revision(dsa_data: &mut [u8]) -> Result<u32, ProgramError> {
+        let revision = dsa_data
+            .get_mut(REVISION_OFFSET..REVISION_OFFSET + size_of::<u32>())
+            .unwrap();
+
+        let new_revision = u32::from_le_bytes(
+            (*revision)
+                .try_into()
+                .unwrap()
+        )
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        sol_memcpy(
+            revision,
+            &new_revision.to_le_bytes(),
+            size_of::<u32>()
+        );
+
+        Ok(new_revision)
+    }
+
+    pub(super) fn set_dsa_last_editor(dsa_data: &mut [u8], last_editor: &Pubkey) {
+        sol_memcpy(
+            dsa_data
+                .get_mut(LAST_EDITOR_OFFSET..LAST_EDITOR_OFFSET + size_of::<Pubkey>())
+                .unwrap(),
+            last_editor.as_ref(),
+            size_of::<Pubkey>()
+        );
+    }
+
+    pub(super) fn read_display_label(data_storage_account_info: &AccountInfo) -> [u8; 30] {
+        data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap()
+            .get(DISPLAY_LABEL_OFFSET..DISPLAY_LABEL_OFFSET + MAX_LABEL_LENGTH)
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    pub(super) fn read_last_updated(data_storage_account_info: &AccountInfo) -> i64 {
+        i64::from_le_bytes(
+            data_storage_account_info
                 .data
-                .try_borrow_mut()
-                .unwrap();
+                .try_borrow()
+                .unwrap()
+                .get(LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + size_of::<i64>())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        )
+    }
 
-            let label = dsa_data
-                .get(32..62)
-                .unwrap();
-            let bump = *dsa_data
-                .get(70)
-                .unwrap();
-            // validate PDA
-            // Also we could validate authority_account & owner_program right here BUT to be developer friendly we seperated these checks!
-            let seeds: &[&[u8]] = &[
-                b"data_storage_account",
-                authority_account_info.key.as_ref(),
-                label,
-                &[ bump ]
-            ];
-            create_and_check_program_address(
-                seeds,
+    // NOTE: Rejects a (corrupted) stored length that claims more bytes than the account's data-field can hold.
+    pub(super) fn read_data_len(data_storage_account_info: &AccountInfo) -> Result<usize, ProgramError> {
+        let data_len = u16::from_le_bytes(
+            data_storage_account_info
+                .data
+                .try_borrow()
+                .unwrap()
+                .get(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        ) as usize;
+
+        if data_len > read_data_capacity(data_storage_account_info) {
+            return Err(
+                DataStorageError::MalformedAccount.into()
+            );
+        };
+
+        Ok(data_len)
+    }
+
+    pub(super) fn read_max_size(data_storage_account_info: &AccountInfo) -> u32 {
+        u32::from_le_bytes(
+            data_storage_account_info
+                .data
+                .try_borrow()
+                .unwrap()
+                .get(MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + size_of::<u32>())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        )
+    }
+
+    pub(super) fn read_data_capacity(data_storage_account_info: &AccountInfo) -> usize {
+        data_storage_account_info
+            .data_len()
+            .saturating_sub(HEADER_LEN)
+    }
+
+    // NOTE: A max-size of '0' means the account has no quota of it's own, so the global 'MAX_DATA_LEN' is used.
+    pub(super) fn check_data_length_fits_header(data_length: usize) -> ProgramResult {
+        if data_length > MAX_DATA_LEN {
+            return Err(
+                DataStorageError::DataTooLarge.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    // NOTE: 'MIN_DATA_LEN' defaults to '0', which makes the comparison always false until it's raised.
+    #[allow(clippy::absurd_extreme_comparisons)]
+    pub(super) fn check_data_length_above_minimum(data_length: usize) -> ProgramResult {
+        if data_length < MIN_DATA_LEN {
+            return Err(
+                DataStorageError::DataTooSmall.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    pub(super) fn check_data_length_within_quota(
+        data_length: usize,
+        max_size: u32
+    ) -> ProgramResult {
+        let quota = if max_size == 0 {
+            MAX_DATA_LEN
+        } else {
+            max_size as usize
+        };
+
+        if data_length > quota {
+            return Err(
+                DataStorageError::QuotaExceeded.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    pub(super) fn calculate_extra_rent_exempt_lamports(
+        old_account_size: usize,
+        new_account_size: usize
+    ) -> Result<u64, ProgramError> {
+        rent_exempt_lamports_delta(
+            &Rent::get()?,
+            old_account_size,
+            new_account_size
+        )
+    }
+    
+    // Grows the data-field's capacity to 'new_capacity', the funding account pays the extra rent-exempt lamports.
+    pub(super) fn grow_dsa_capacity<'a>(
+        data_storage_pda_account_info: &AccountInfo<'a>,
+        funding_account_info: &AccountInfo<'a>,
+        funding_ledger_account_info: Option<&AccountInfo<'a>>,
+        new_capacity: usize,
+        program_id: &Pubkey,
+        cpi_accounts_info: &[AccountInfo<'a>]
+    ) -> ProgramResult {
+        let old_capacity = read_data_capacity(data_storage_pda_account_info);
+
+        // 'realloc' would reject growing more than 10 KiB in one instruction with an opaque error
+        if new_capacity
+            .checked_sub(old_capacity)
+            .unwrap() > MAX_PERMITTED_DATA_INCREASE
+        {
+            return Err(
+                DataStorageError::ReallocTooLarge.into()
+            );
+        };
+
+        // calculate rent_exempt lmaports to transfer to the data-account for extra-bytes
+        let extra_rent_lamports = calculate_extra_rent_exempt_lamports(
+            total_account_size(old_capacity),
+            total_account_size(new_capacity)
+        )?;
+
+        // transfer lamports to the data-account
+        invoke(
+            &transfer_lamports(
+                funding_account_info.key,
+                data_storage_pda_account_info.key,
+                extra_rent_lamports
+            ),
+            cpi_accounts_info
+        )?;
+
+        // realloc extra bytes
+        calculate_new_dsa_size_and_realloc(
+            new_capacity,
+            old_capacity,
+            data_storage_pda_account_info,
+            true
+        )?;
+
+        if let Some(funding_ledger_account_info) = funding_ledger_account_info {
+            append_funding_ledger_entry(
+                funding_ledger_account_info,
+                data_storage_pda_account_info,
+                funding_account_info,
+                extra_rent_lamports,
                 program_id,
-                data_storage_pda_account_info.key
+                cpi_accounts_info
             )?;
+        };
 
-            drop(dsa_data);
+        Ok(())
+    }
 
-            // update data-storage account
-            // update 'last-updated' field
-            let current_time = (Clock::get()?).unix_timestamp;
-            sol_memcpy(
-                data_storage_pda_account_info
-                    .data
-                    .try_borrow_mut()
-                    .unwrap()
-                    .get_mut(62..70)
-                    .unwrap(),
-                &current_time.to_le_bytes(),
-                size_of::<i64>()
+    // Grows the data-storage account (only when the funding accounts are passed, otherwise the reserved capacity is used) and
+    // writes the new data-length & data-field. The account's size is verified right after the realloc, so a wrong size can
+    // never end up with a data-length stored for it.
+    pub(super) fn grow_and_write<'a>(
+        data_storage_pda_account_info: &AccountInfo<'a>,
+        funding_accounts_info: Option<(&AccountInfo<'a>, Option<&AccountInfo<'a>>)>,
+        old_data_length: usize,
+        new_data: &[u8],
+        program_id: &Pubkey,
+        cpi_accounts_info: &[AccountInfo<'a>]
+    ) -> ProgramResult {
+        let new_data_length = new_data.len();
+        if new_data_length < old_data_length {
+            return Err(
+                ProgramError::InvalidArgument
             );
+        };
 
-            let old_data_length = u16::from_le_bytes(
-                data_storage_pda_account_info
-                    .data
-                    .try_borrow_mut()
-                    .unwrap()
-                    .get(72..74)
-                    .unwrap()
-                    .try_into()
-                    .unwrap()
-            ) as usize;
-
-            let new_data_length = ix_data.len();
+        if let Some((funding_account_info, funding_ledger_account_info)) = funding_accounts_info {
+            grow_dsa_capacity(
+                data_storage_pda_account_info,
+                funding_account_info,
+                funding_ledger_account_info,
+                new_data_length,
+                program_id,
+                cpi_accounts_info
+            )?;
 
-            if new_data_length == old_data_length {
-                // write new data
-                sol_memcpy(
-                    data_storage_pda_account_info
-                        .data
-                        .try_borrow_mut()
-                        .unwrap()
-                        .get_mut(74..)
-                        .unwrap(),
-                    ix_data,
-                    old_data_length
-                );
-            } else if new_data_length < old_data_length {
-                // write new data-length
-                sol_memcpy(
-                    data_storage_pda_account_info
-                        .data
-                        .try_borrow_mut()
-                        .unwrap()
-                        .get_mut(72..74)
-                        .unwrap(),
-                    &u16::to_le_bytes(new_data_length as u16),
-                    size_of::<u16>()
+            if data_storage_pda_account_info.data_len() != total_account_size(new_data_length) {
+                return Err(
+                    ProgramError::InvalidAccountData
                 );
+            };
+        };
 
-                // write new data
-                sol_memcpy(
-                    data_storage_pda_account_info
-                        .data
-                        .try_borrow_mut()
-                        .unwrap()
-                        .get_mut(74..)
-                        .unwrap(),
-                    ix_data,
-                    new_data_length
-                );
+        if read_data_capacity(data_storage_pda_account_info) < new_data_length {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
 
-                // realloc account data
-                calculate_new_dsa_size_and_realloc(
-                    new_data_length,
-                    old_data_length,
-                    data_storage_pda_account_info,
-                    new_data_length > old_data_length
-                )?;
+        let mut dsa_data = data_storage_pda_account_info
+            .data
+            .try_borrow_mut()
+            .unwrap();
 
-                // calculate rent_exempt lamports to refund
-                let extra_rent_lamports = calculate_extra_rent_exempt_lamports(
-                    old_data_length,
-                    new_data_length,
-                    new_data_length > old_data_length
-                )?;
+        // write new data-length
+        sol_memcpy(
+            dsa_data
+                .get_mut(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
+                .unwrap(),
+            &u16::to_le_bytes(new_data_length as u16),
+            size_of::<u16>()
+        );
 
-                // refund the extra rent_exempt
-                let rent_receiver_account_info = next_account_info(accounts_info)?;
+        // write new data
+        sol_memcpy(
+            dsa_data
+                .get_mut(DATA_OFFSET..)
+                .unwrap(),
+            new_data,
+            new_data_length
+        );
 
-                **data_storage_pda_account_info.try_borrow_mut_lamports()? = data_storage_pda_account_info
-                    .lamports()
-                    .checked_sub(extra_rent_lamports)
-                    .unwrap();
+        Ok(())
+    }
 
-                **rent_receiver_account_info.try_borrow_mut_lamports()? = rent_receiver_account_info
-                    .lamports()
-                    .checked_add(extra_rent_lamports)
-                    .unwrap();
-            } else if new_data_length > old_data_length {
-                // calculate rent_exempt lmaports to transfer to the data-account for extra-bytes
-                let extra_rent_lamports = calculate_extra_rent_exempt_lamports(
-                    old_data_length,
-                    new_data_length,
-                    new_data_length > old_data_length
-                )?;
+    // Writes the new data-field (and it's length) the way an edit does: in place when the length is unchanged, refunding the
+    // freed rent to the next account on a shrink, or growing into the reserved capacity (then the funding accounts) on a grow.
+    // NOTE: Fails with 'InvalidAccountData' before writing anything if the account can't hold 'old_data_length' bytes.
+    pub(super) fn write_dsa_data_field<'a, 'b, I: Iterator<Item = &'b AccountInfo<'a>>>(
+        data_storage_pda_account_info: &AccountInfo<'a>,
+        accounts_info: &mut I,
+        old_data_length: usize,
+        new_data: &[u8],
+        program_id: &Pubkey,
+        cpi_accounts_info: &[AccountInfo<'a>]
+    ) -> ProgramResult
+    where
+        'a: 'b
+    {
+        let new_data_length = new_data.len();
 
-                // transfer lamports to the data-account
-                let funding_account_info = next_account_info(accounts_info)?;
-                let system_program_account_info = next_account_info(accounts_info)?;
+        if old_data_length > read_data_capacity(data_storage_pda_account_info) {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
 
-                check_system_program_account(system_program_account_info.key)?;
+        if new_data_length == old_data_length {
+            // write new data
+            sol_memcpy(
+                data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap()
+                    .get_mut(DATA_OFFSET..)
+                    .unwrap(),
+                new_data,
+                old_data_length
+            );
+        } else if new_data_length < old_data_length {
+            let rent_receiver_account_info = next_required_account_info(accounts_info, DataStorageError::MissingRentReceiver)?;
 
-                invoke(
-                    &transfer_lamports(
-                        funding_account_info.key,
-                        data_storage_pda_account_info.key,
-                        extra_rent_lamports
-                    ),
-                    &[
-                        funding_account_info.clone(),
-                        data_storage_pda_account_info.clone()
-                    ]
-                )?;
+            // realloc first, so the new data-length & data-field are written into the already shrunk account (never sized
+            // off the old one), the freed rent is refunded last
+            shrink_dsa_capacity(
+                data_storage_pda_account_info,
+                new_data_length,
+                old_data_length
+            )?;
 
-                // realloc extra bytes
-                calculate_new_dsa_size_and_realloc(
-                    new_data_length,
-                    old_data_length,
-                    data_storage_pda_account_info,
-                    new_data_length > old_data_length
-                )?;
+            {
+                let mut dsa_data = data_storage_pda_account_info
+                    .data
+                    .try_borrow_mut()
+                    .unwrap();
 
                 // write new data-length
                 sol_memcpy(
-                    data_storage_pda_account_info
-                        .data
-                        .try_borrow_mut()
-                        .unwrap()
-                        .get_mut(72..74)
+                    dsa_data
+                        .get_mut(DATA_LEN_OFFSET..DATA_LEN_OFFSET + size_of::<u16>())
                         .unwrap(),
                     &u16::to_le_bytes(new_data_length as u16),
                     size_of::<u16>()
@@ -506,397 +8454,821 @@ pub fn process_instruction(
 
                 // write new data
                 sol_memcpy(
-                    data_storage_pda_account_info
-                        .data
-                        .try_borrow_mut()
-                        .unwrap()
-                        .get_mut(74..)
+                    dsa_data
+                        .get_mut(DATA_OFFSET..DATA_OFFSET + new_data_length)
                         .unwrap(),
-                    ix_data,
+                    new_data,
                     new_data_length
                 );
-            };
 
-            let event = Events::DataStorageAccountEdited {
-                data_storage_account: *data_storage_pda_account_info.key,
-                authority_account: *authority_account_info.key,
-                old_data_len: old_data_length,
-                new_data_len: new_data_length
+                // clear the old data-field's bytes left in the reserved capacity behind the new one
+                let stale_len = dsa_data.len() - DATA_OFFSET - new_data_length;
+                sol_memset(
+                    dsa_data
+                        .get_mut(DATA_OFFSET + new_data_length..)
+                        .unwrap(),
+                    0,
+                    stale_len
+                );
             };
-            emit!(event);
 
-            sol_log("Data storage account has been updated successfully. ✅");
-        },
-        
-        CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR => {
-            sol_log("⚙️ Instruction: CloseDataStorageAccount");
+            refund_freed_rent_exempt_lamports(
+                data_storage_pda_account_info,
+                rent_receiver_account_info,
+                new_data_length,
+                old_data_length
+            )?;
+        } else if new_data_length > old_data_length {
+            // validate new data-length against the account's quota
+            check_data_length_within_quota(
+                new_data_length,
+                read_max_size(data_storage_pda_account_info)
+            )?;
 
-            let data_storage_pda_account_info = next_account_info(accounts_info)?;
-            let authority_account_info = next_account_info(accounts_info)?;
-            let rent_receiver_account_info = next_account_info(accounts_info)?;
+            // use the reserved capacity first, only grow the account when the new data doesn't fit in it
+            let funding_accounts_info = if new_data_length > read_data_capacity(data_storage_pda_account_info) {
+                let funding_account_info = next_required_account_info(accounts_info, DataStorageError::MissingFundingAccount)?;
+                let system_program_account_info = next_required_account_info(accounts_info, DataStorageError::MissingSystemProgram)?;
 
-            check_if_data_storage_account_is_immutable(data_storage_pda_account_info)?;
+                check_system_program_account(system_program_account_info.key)?;
+                check_account_is_signer(funding_account_info)?;
 
-            check_account_is_signer(authority_account_info)?;
+                let funding_ledger_account_info = next_account_info(accounts_info)
+                    .ok()
+                    .filter(|account_info| !check_instructions_sysvar_id(account_info.key));
 
-            // validate account's owner-program
-            check_dsa_account_owner(
+                Some((funding_account_info, funding_ledger_account_info))
+            } else {
+                None
+            };
+
+            grow_and_write(
                 data_storage_pda_account_info,
-                program_id
+                funding_accounts_info,
+                old_data_length,
+                new_data,
+                program_id,
+                cpi_accounts_info
             )?;
+        };
 
-            // check that account is initialized
-            check_dsa_account_is_initialized(data_storage_pda_account_info)?;
+        Ok(())
+    }
 
-            // validate account's authority
-            check_dsa_account_authority(
-                data_storage_pda_account_info,
-                authority_account_info.key.to_bytes()
-            )?;
+    // Only 'ACCOUNT_KIND_BYTES' accounts are written to as raw bytes, the others' data-fields are laid out by their own
+    // instructions (see '//? key-value entries' & '//? chunks').
+    pub(super) fn check_dsa_account_is_bytes(data_storage_account_info: &AccountInfo) -> ProgramResult {
+        if *data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap()
+            .get(KIND_OFFSET)
+            .unwrap() != ACCOUNT_KIND_BYTES
+        {
+            return Err(
+                DataStorageError::NotBytesAccount.into()
+            );
+        };
 
-            // deserialize account data
-            let dsa_data = data_storage_pda_account_info
-                .data
-                .try_borrow_mut()
-                .unwrap();
+        Ok(())
+    }
 
-            let label = dsa_data
-                .get(32..62)
-                .unwrap();
-            let bump = *dsa_data
-                .get(70)
-                .unwrap();
+    // Chunk pdas are derived from the data-storage account's Pubkey, the paths that move or close it would strand them
+    // (see '//? chunks').
+    pub(super) fn check_dsa_account_is_not_chunked(data_storage_account_info: &AccountInfo) -> ProgramResult {
+        if *data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap()
+            .get(KIND_OFFSET)
+            .unwrap() == ACCOUNT_KIND_CHUNKED
+        {
+            return Err(
+                DataStorageError::ChunkedAccount.into()
+            );
+        };
 
-            // validate PDA
-            // Also we could validate authority_account & owner_program right here BUT to be developer friendly we seperated these checks!
-            create_and_check_program_address(
+        Ok(())
+    }
+
+    // Only 'ACCOUNT_KIND_KEY_VALUE' accounts hold entries (see '//? key-value entries').
+    pub(super) fn check_dsa_account_is_key_value(data_storage_account_info: &AccountInfo) -> ProgramResult {
+        if *data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap()
+            .get(KIND_OFFSET)
+            .unwrap() != ACCOUNT_KIND_KEY_VALUE
+        {
+            return Err(
+                DataStorageError::NotKeyValueAccount.into()
+            );
+        };
+
+        Ok(())
+    }
+
+    // Rebuilds a key-value data-field with the key's entry set to 'value' (in place, or appended when the key isn't set yet)
+    // or removed ('None'), fails with 'MalformedAccount' on a malformed data-field and 'EntryNotFound' when removing a missing key.
+    pub(super) fn splice_entry(data_field: &[u8], key: &[u8], value: Option<&[u8]>) -> Result<Vec<u8>, ProgramError> {
+        let entries = read_entries(data_field)
+            .map_err(|_| DataStorageError::MalformedAccount)?;
+
+        let push_entry = |new_data_field: &mut Vec<u8>, key: &[u8], value: &[u8]| {
+            new_data_field.push(key.len() as u8);
+            new_data_field.extend_from_slice(key);
+            new_data_field.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            new_data_field.extend_from_slice(value);
+        };
+
+        let mut new_data_field = Vec::with_capacity(data_field.len() + value.map_or(0, |value| 3 + key.len() + value.len()));
+        let mut found = false;
+        for (entry_key, entry_value) in entries {
+            if entry_key == key {
+                found = true;
+
+                if let Some(value) = value {
+                    push_entry(&mut new_data_field, key, value);
+                };
+            } else {
+                push_entry(&mut new_data_field, entry_key, entry_value);
+            };
+        };
+
+        match (found, value) {
+            (false, Some(value)) => push_entry(&mut new_data_field, key, value),
+            (false, None) => return Err(
+                DataStorageError::EntryNotFound.into()
+            ),
+            _ => ()
+        };
+
+        Ok(new_data_field)
+    }
+
+    // Appends a '(funder, lamports)' entry to the data-storage account's funding-ledger, the first entry creates the ledger pda.
+    pub(super) fn append_funding_ledger_entry<'a>(
+        funding_ledger_account_info: &AccountInfo<'a>,
+        data_storage_pda_account_info: &AccountInfo<'a>,
+        funding_account_info: &AccountInfo<'a>,
+        lamports: u64,
+        program_id: &Pubkey,
+        cpi_accounts_info: &[AccountInfo<'a>]
+    ) -> ProgramResult {
+        let (
+            funding_ledger_pda_addr,
+            funding_ledger_bump
+        ) = Pubkey::find_program_address(
+            &[
+                FUNDING_LEDGER_SEED_PREFIX,
+                data_storage_pda_account_info.key.as_ref()
+            ],
+            program_id
+        );
+        if funding_ledger_account_info.key != &funding_ledger_pda_addr {
+            return Err(
+                ProgramError::InvalidSeeds
+            );
+        };
+
+        let old_ledger_len = funding_ledger_account_info.data_len();
+        let new_ledger_len = old_ledger_len
+            .checked_add(FUNDING_LEDGER_ENTRY_LEN)
+            .unwrap();
+
+        if funding_ledger_account_info.owner == &SYSTEM_PROGRAM_ID {
+            create_pda_account(
+                funding_ledger_account_info,
+                funding_account_info,
+                new_ledger_len,
+                program_id,
                 &[
-                    b"data_storage_account",
-                    authority_account_info.key.as_ref(),
-                    label,
-                    &[ bump ]
+                    FUNDING_LEDGER_SEED_PREFIX,
+                    data_storage_pda_account_info.key.as_ref(),
+                    &[ funding_ledger_bump ]
                 ],
-                program_id,
-                data_storage_pda_account_info.key
+                cpi_accounts_info
             )?;
+        } else {
+            if funding_ledger_account_info.owner != program_id {
+                return Err(
+                    ProgramError::IllegalOwner
+                );
+            };
 
-            drop(dsa_data);
-
-            // transfer dsa all lamports to the receiver-account
-            let dsa_lamport_balance = data_storage_pda_account_info.lamports();
+            invoke(
+                &transfer_lamports(
+                    funding_account_info.key,
+                    funding_ledger_account_info.key,
+                    calculate_extra_rent_exempt_lamports(
+                        old_ledger_len,
+                        new_ledger_len
+                    )?
+                ),
+                cpi_accounts_info
+            )?;
 
-            **data_storage_pda_account_info.try_borrow_mut_lamports()? = 0;
+            funding_ledger_account_info.realloc(
+                new_ledger_len,
+                false
+            )?;
+        };
 
-            **rent_receiver_account_info.try_borrow_mut_lamports()? = rent_receiver_account_info
-                .lamports()
-                .checked_add(dsa_lamport_balance)
-                .unwrap();
+        let mut funding_ledger_data = funding_ledger_account_info
+            .data
+            .try_borrow_mut()
+            .unwrap();
 
-            // uninitialize the data-storage account
-            let mut dsa_data = data_storage_pda_account_info
-                .data
-                .try_borrow_mut()
-                .unwrap();
-            let is_initialized_flag = dsa_data
-                .get_mut(71)
-                .unwrap();
-            *is_initialized_flag = false as u8;
+        sol_memcpy(
+            funding_ledger_data
+                .get_mut(old_ledger_len..old_ledger_len + 32)
+                .unwrap(),
+            funding_account_info.key.as_ref(),
+            size_of::<Pubkey>()
+        );
+        sol_memcpy(
+            funding_ledger_data
+                .get_mut(old_ledger_len + 32..new_ledger_len)
+                .unwrap(),
+            &lamports.to_le_bytes(),
+            size_of::<u64>()
+        );
 
-            let event = Events::DataStorageAccountClosed {
-                data_storage_account: *data_storage_pda_account_info.key,
-                authority_account: *authority_account_info.key
-            };
-            emit!(event);
+        Ok(())
+    }
 
-            sol_log("Data storage account has been closed successfully. ✅");
-        },
-        _ => return Err(
-            ProgramError::InvalidInstructionData
-        )
-    };
+    // Returns the chunked account's '(total length, chunk count)', see '//? chunks'.
+    pub(super) fn read_chunks_metadata(data_storage_account_info: &AccountInfo) -> Result<(usize, usize), ProgramError> {
+        let dsa_data = data_storage_account_info
+            .data
+            .try_borrow()
+            .unwrap();
+        if *dsa_data
+            .get(KIND_OFFSET)
+            .unwrap() != ACCOUNT_KIND_CHUNKED
+        {
+            return Err(
+                DataStorageError::NotChunkedAccount.into()
+            );
+        };
 
-    Ok(())
-}
+        let metadata = dsa_data
+            .get(DATA_OFFSET..DATA_OFFSET + CHUNKS_METADATA_LEN)
+            .ok_or(DataStorageError::MalformedAccount)?;
+        let total_len = u32::from_le_bytes(metadata[..4].try_into().unwrap()) as usize;
+        let chunk_count = u16::from_le_bytes(metadata[4..].try_into().unwrap()) as usize;
 
-#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
-pub enum DataStorageError {
-    #[error("immutable data storage account.")]
-    ImmutableDataStorage = 70,
-    #[error("find_program_address failed!")]
-    FailedToFindProgramAddress,
-    #[error("invalid account-label (invalid utf-8)")]
-    InvalidLabel,
-    #[error("invalid data")]
-    InvalidData
-}
+        Ok((total_len, chunk_count))
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Events {
-    NewDataStorageAccountCreated {
-        data_storage_account: Pubkey,
-        authority_account: Pubkey,
-        account_label: [u8; 30]
-    },
-    DataStorageAccountEdited {
-        data_storage_account: Pubkey,
-        authority_account: Pubkey,
-        old_data_len: usize,
-        new_data_len: usize
-    },
-    DataStorageAccountClosed {
-        data_storage_account: Pubkey,
-        authority_account: Pubkey
+    // Only the last chunk can be shorter than 'CHUNK_LEN'.
+    pub(super) fn chunk_len(total_len: usize, chunk_index: usize) -> usize {
+        total_len
+            .saturating_sub(chunk_index * CHUNK_LEN)
+            .min(CHUNK_LEN)
     }
-}
 
-mod helper {
-    use super::{
-        AccountInfo,
-        Pubkey,
-        invoke,
-        invoke_signed,
-        transfer_lamports,
-        allocate_memory,
-        assign_new_owner,
-        ProgramError,
-        ProgramResult,
-        check_system_program_id,
-        DataStorageError,
-        SYSTEM_PROGRAM_ID,
-        sol_memcmp,
-        size_of
-    };
-    use solana_program::sysvar::{
-        Sysvar,
-        rent::Rent
-    };
+    // The config pda is the program's own, at the address derived from it's stored bump.
+    pub(super) fn check_config_account(
+        config_pda_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        if config_pda_account_info.owner != program_id {
+            return Err(
+                ProgramError::InvalidAccountOwner
+            );
+        };
 
-    pub(super) fn create_pda_account<'a, 'b>(
-        new_pda_account_info: &AccountInfo<'a>,
-        fee_payer_account_info: &AccountInfo<'b>,
-        space: usize,
+        if config_pda_account_info.data_len() != CONFIG_LEN {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+
+        let config_bump = *config_pda_account_info
+            .data
+            .try_borrow()
+            .unwrap()
+            .get(CONFIG_BUMP_OFFSET)
+            .unwrap();
+        let config_pda_addr = Pubkey::create_program_address(
+            &[
+                CONFIG_SEED_PREFIX,
+                &[ config_bump ]
+            ],
+            program_id
+        ).map_err(|_| ProgramError::InvalidSeeds)?;
+        if config_pda_account_info.key != &config_pda_addr {
+            return Err(
+                ProgramError::InvalidSeeds
+            );
+        };
+
+        Ok(())
+    }
+
+    // Applies the config to a create of 'accounts_count' accounts (see '//? config'): takes the config pda (and the treasury,
+    // when there's a creation-fee) from 'accounts_info', charges the creation-fees and returns the max data-length. The config
+    // pda is required even before it's initialized, otherwise clients could skip it by not passing it.
+    pub(super) fn apply_dsa_config<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+        accounts_info: &mut I,
+        funding_account_info: &AccountInfo,
+        accounts_count: u64,
         program_id: &Pubkey,
-        seeds: &[&[u8]]
-    ) -> ProgramResult where 'b:'a, 'a:'b {
-        let rent = Rent::get()?.minimum_balance(space);
-        let new_pda_account_balance = new_pda_account_info.lamports();
-        if new_pda_account_balance < rent {
-            let lamports_needed = rent
-                .checked_sub(new_pda_account_balance)
-                .unwrap();
-            
+        cpi_accounts_info: &[AccountInfo]
+    ) -> Result<usize, ProgramError> {
+        let config_pda_account_info = next_required_account_info(accounts_info, DataStorageError::MissingConfig)?;
+
+        // not initialized yet, nothing to enforce
+        if config_pda_account_info.owner == &SYSTEM_PROGRAM_ID && config_pda_account_info.data_is_empty() {
+            let (
+                config_pda_addr,
+                _
+            ) = find_config_pda(program_id);
+            if config_pda_account_info.key != &config_pda_addr {
+                return Err(
+                    ProgramError::InvalidSeeds
+                );
+            };
+
+            return Ok(MAX_DATA_LEN);
+        };
+
+        check_config_account(
+            config_pda_account_info,
+            program_id
+        )?;
+
+        let config = DataStorageConfig::unpack(
+            &config_pda_account_info
+                .data
+                .try_borrow()
+                .unwrap()
+        )?;
+
+        if config.creation_fee != 0 {
+            let treasury_account_info = next_required_account_info(accounts_info, DataStorageError::MissingTreasury)?;
+            if treasury_account_info.key != &config.treasury {
+                return Err(
+                    ProgramError::InvalidArgument
+                );
+            };
+
             invoke(
                 &transfer_lamports(
-                    fee_payer_account_info.key,
-                    new_pda_account_info.key,
-                    lamports_needed
+                    funding_account_info.key,
+                    treasury_account_info.key,
+                    config.creation_fee
+                        .checked_mul(accounts_count)
+                        .ok_or(ProgramError::ArithmeticOverflow)?
                 ),
-                &[
-                    fee_payer_account_info.clone(),
-                    new_pda_account_info.clone()
-                ]
+                cpi_accounts_info
             )?;
         };
-    
-        invoke_signed(
-            &allocate_memory(
-                new_pda_account_info.key,
-                space as u64
-            ),
-            &[ new_pda_account_info.clone() ],
-            &[ seeds ]
-        )?;
-    
-        invoke_signed(
-            &assign_new_owner(
-                new_pda_account_info.key,
-                program_id
-            ),
-            &[ new_pda_account_info.clone() ],
-            &[ seeds ]
-        )?;
-    
-        Ok(())
+
+        Ok(
+            match config.max_data_len {
+                0 => MAX_DATA_LEN,
+                max_data_len => max_data_len as usize
+            }
+        )
     }
-    
-    pub(super) fn check_account_is_signer(account_info: &AccountInfo) -> ProgramResult {
-        if account_info.is_signer == false {
+
+    // Only the program's upgrade-authority can initialize the config, so it can't be front-run right after the deploy.
+    pub(super) fn check_program_upgrade_authority(
+        program_data_account_info: &AccountInfo,
+        upgrade_authority_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        let (
+            program_data_addr,
+            _
+        ) = Pubkey::find_program_address(
+            &[ program_id.as_ref() ],
+            &BPF_LOADER_UPGRADEABLE_ID
+        );
+        if program_data_account_info.key != &program_data_addr || program_data_account_info.owner != &BPF_LOADER_UPGRADEABLE_ID {
             return Err(
-                ProgramError::MissingRequiredSignature
+                ProgramError::InvalidAccountData
             );
         };
-    
+
+        // 'UpgradeableLoaderState::ProgramData' : 'u32' variant (3), 'u64' slot, 'Option<Pubkey>' upgrade-authority
+        let program_data = program_data_account_info
+            .data
+            .try_borrow()
+            .unwrap();
+        let metadata = program_data
+            .get(..UpgradeableLoaderState::size_of_programdata_metadata())
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if metadata[..size_of::<u32>()] != 3_u32.to_le_bytes() {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+
+        let upgrade_authority_offset = size_of::<u32>() + size_of::<u64>();
+        if metadata[upgrade_authority_offset] != 1 || metadata[upgrade_authority_offset + 1..] != upgrade_authority_account_info.key.to_bytes() {
+            return Err(
+                ProgramError::IncorrectAuthority
+            );
+        };
+
         Ok(())
     }
-    
-    pub(super) fn check_system_program_account(expected_program_id: &Pubkey) -> ProgramResult {
-        if check_system_program_id(expected_program_id) == false {
+
+    // Returns the chunk pda's bump, after checking it's the data-storage account's 'chunk_index'-th chunk.
+    pub(super) fn check_chunk_address(
+        chunk_account_info: &AccountInfo,
+        data_storage_pda_account_info: &AccountInfo,
+        chunk_index: u16,
+        program_id: &Pubkey
+    ) -> Result<u8, ProgramError> {
+        let (
+            chunk_pda_addr,
+            chunk_bump
+        ) = Pubkey::find_program_address(
+            &[
+                CHUNK_SEED_PREFIX,
+                data_storage_pda_account_info.key.as_ref(),
+                &chunk_index.to_le_bytes()
+            ],
+            program_id
+        );
+        if chunk_account_info.key != &chunk_pda_addr {
             return Err(
-                ProgramError::IncorrectProgramId
+                ProgramError::InvalidSeeds
             );
         };
-    
-        Ok(())
+
+        Ok(chunk_bump)
     }
-    
-    // NOTE: If a data-storage account's authority is SYSTEM_PROGRAM_ACCOUNT thix means that the dsa is an immutable-account and it's authority cannot be a signer BUT
-    //  to be developer friendly we add this check to make the code more beautiful !
-    pub(super) fn check_if_data_storage_account_is_immutable(data_storage_account_info: &AccountInfo) -> ProgramResult {
-        let cmp_result = sol_memcmp(
-            data_storage_account_info
-                .data
-                .try_borrow()
-                .unwrap()
-                .get(..32)
-                .unwrap(),
-            &SYSTEM_PROGRAM_ID.to_bytes(),
-            size_of::<Pubkey>()
+
+    // Returns the patch-log pda's bump, after checking it's derived from the data-storage account.
+    pub(super) fn check_patch_log_address(
+        patch_log_account_info: &AccountInfo,
+        data_storage_pda_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> Result<u8, ProgramError> {
+        let (
+            patch_log_pda_addr,
+            patch_log_bump
+        ) = Pubkey::find_program_address(
+            &[
+                PATCH_LOG_SEED_PREFIX,
+                data_storage_pda_account_info.key.as_ref()
+            ],
+            program_id
         );
-    
-        if cmp_result == 0 {
+        if patch_log_account_info.key != &patch_log_pda_addr {
             return Err(
-                ProgramError::Custom(
-                    DataStorageError::ImmutableDataStorage as u32
-                )
-            )
+                ProgramError::InvalidSeeds
+            );
         };
-    
-        Ok(())
+
+        Ok(patch_log_bump)
     }
-    
-    pub(super) fn check_dsa_account_owner(
-        data_storage_account_info: &AccountInfo,
-        expected_owner: &Pubkey
-    ) -> ProgramResult {
-        if data_storage_account_info.owner != expected_owner {
+
+    pub(super) fn read_patch_log_head_and_count(patch_log_data: &[u8]) -> Result<(usize, usize), ProgramError> {
+        if patch_log_data.len() != PATCH_LOG_LEN {
             return Err(
-                ProgramError::InvalidAccountOwner
+                ProgramError::InvalidAccountData
             );
         };
-    
-        Ok(())
+
+        let head = *patch_log_data.first().unwrap() as usize;
+        let count = *patch_log_data.get(1).unwrap() as usize;
+        if head >= PATCH_LOG_CAPACITY || count > PATCH_LOG_CAPACITY {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+
+        Ok((head, count))
     }
-    
-    pub(super) fn create_and_check_program_address(
-        seeds: &[&[u8]],
-        program_id: &Pubkey,
-        expected_data_storage_pda_account_pubkey: &Pubkey
+
+    // The data-storage account's '(created-at, revision, data-hash)', as stored in the patch-log.
+    pub(super) fn patch_log_base(dsa_data: &[u8]) -> [u8; PATCH_LOG_BASE_LEN] {
+        let mut patch_log_base = [0; PATCH_LOG_BASE_LEN];
+        patch_log_base[..size_of::<i64>()].copy_from_slice(&dsa_data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + size_of::<i64>()]);
+        patch_log_base[size_of::<i64>()..size_of::<i64>() + size_of::<u32>()]
+            .copy_from_slice(&dsa_data[REVISION_OFFSET..REVISION_OFFSET + size_of::<u32>()]);
+        patch_log_base[size_of::<i64>() + size_of::<u32>()..]
+            .copy_from_slice(&dsa_data[DATA_HASH_OFFSET..DATA_HASH_OFFSET + size_of::<[u8; 32]>()]);
+
+        patch_log_base
+    }
+
+    // Returns the pending patches' '(head, count)', no patches when they've been queued on an older base (they're stale).
+    pub(super) fn read_pending_patches(patch_log_data: &[u8], dsa_data: &[u8]) -> Result<(usize, usize), ProgramError> {
+        let (
+            head,
+            count
+        ) = read_patch_log_head_and_count(patch_log_data)?;
+
+        if dsa_data.len() < HEADER_LEN {
+            return Err(
+                ProgramError::InvalidAccountData
+            );
+        };
+
+        if patch_log_data[PATCH_LOG_BASE_OFFSET..] != patch_log_base(dsa_data) {
+            return Ok((0, 0));
+        };
+
+        Ok((head, count))
+    }
+
+    // Closes the data-storage account's patch-log (if it exists), it's lamports go to the receiver.
+    pub(super) fn close_patch_log_account(
+        patch_log_account_info: &AccountInfo,
+        data_storage_pda_account_info: &AccountInfo,
+        rent_receiver_account_info: &AccountInfo,
+        program_id: &Pubkey
     ) -> ProgramResult {
-        let dsa_pda_addr = Pubkey::create_program_address(
-            seeds,
+        check_patch_log_address(
+            patch_log_account_info,
+            data_storage_pda_account_info,
             program_id
-        ).map_err::<ProgramError, _>(|_| ProgramError::Custom(DataStorageError::FailedToFindProgramAddress as u32))?;
-    
-        if &dsa_pda_addr != expected_data_storage_pda_account_pubkey {
+        )?;
+        if patch_log_account_info.owner == &SYSTEM_PROGRAM_ID {
+            return Ok(());
+        } else if patch_log_account_info.owner != program_id {
             return Err(
-                ProgramError::InvalidAccountData
+                ProgramError::IllegalOwner
             );
         };
-    
+
+        let patch_log_lamports = patch_log_account_info.lamports();
+
+        **patch_log_account_info.try_borrow_mut_lamports()? = 0;
+
+        **rent_receiver_account_info.try_borrow_mut_lamports()? = rent_receiver_account_info
+            .lamports()
+            .checked_add(patch_log_lamports)
+            .unwrap();
+
+        let mut patch_log_data = patch_log_account_info
+            .data
+            .try_borrow_mut()
+            .unwrap();
+        let patch_log_len = patch_log_data.len();
+        sol_memset(
+            &mut patch_log_data,
+            0,
+            patch_log_len
+        );
+
         Ok(())
     }
-    
-    pub(super) fn check_dsa_account_authority(
-        data_storage_account_info: &AccountInfo,
-        expected_authority_pubkey: [u8; 32]
+
+    // Folds the pending patches into the data-storage account's base before it moves to another pda (the patch-log is derived
+    // from the old one), then closes the patch-log (if it exists), it's lamports go to the receiver.
+    pub(super) fn compact_and_close_patch_log_account(
+        patch_log_account_info: &AccountInfo,
+        data_storage_pda_account_info: &AccountInfo,
+        rent_receiver_account_info: &AccountInfo,
+        program_id: &Pubkey
     ) -> ProgramResult {
-        let cmp_result = sol_memcmp(
-            data_storage_account_info
+        check_patch_log_address(
+            patch_log_account_info,
+            data_storage_pda_account_info,
+            program_id
+        )?;
+        if patch_log_account_info.owner == program_id {
+            let data_length = read_data_len(data_storage_pda_account_info)?;
+
+            let mut dsa_data = data_storage_pda_account_info
+                .data
+                .try_borrow_mut()
+                .unwrap();
+            let patch_log_data = patch_log_account_info
                 .data
                 .try_borrow()
-                .unwrap()
-                .get(..32)
-                .unwrap(),
-            expected_authority_pubkey.as_slice(),
-            size_of::<Pubkey>()
-        );
-    
-        if cmp_result != 0 {
+                .unwrap();
+
+            let (
+                head,
+                count
+            ) = read_pending_patches(&patch_log_data, &dsa_data)?;
+
+            if count > 0 {
+                fold_patch_log_entries(
+                    dsa_data
+                        .get_mut(DATA_OFFSET..DATA_OFFSET + data_length)
+                        .unwrap(),
+                    &patch_log_data,
+                    head,
+                    count
+                );
+
+                update_dsa_data_hash(&mut dsa_data)?;
+            };
+        };
+
+        close_patch_log_account(
+            patch_log_account_info,
+            data_storage_pda_account_info,
+            rent_receiver_account_info,
+            program_id
+        )
+    }
+
+    // Closes the data-storage account's 'chunk_index'-th chunk, it's lamports go to the rent-receiver.
+    pub(super) fn close_chunk_account(
+        chunk_account_info: &AccountInfo,
+        data_storage_pda_account_info: &AccountInfo,
+        chunk_index: u16,
+        rent_receiver_account_info: &AccountInfo,
+        program_id: &Pubkey
+    ) -> ProgramResult {
+        check_chunk_address(
+            chunk_account_info,
+            data_storage_pda_account_info,
+            chunk_index,
+            program_id
+        )?;
+        if chunk_account_info.owner != program_id {
             return Err(
-                ProgramError::IncorrectAuthority
+                ProgramError::IllegalOwner
             );
         };
-    
-        Ok(())
-    }
-    
-    pub(super) fn check_dsa_account_is_initialized(data_storage_account_info: &AccountInfo) -> ProgramResult {
-        let dsa_data = data_storage_account_info
-            .data
-            .try_borrow()
+
+        let chunk_lamports = chunk_account_info.lamports();
+
+        **chunk_account_info.try_borrow_mut_lamports()? = 0;
+
+        **rent_receiver_account_info.try_borrow_mut_lamports()? = rent_receiver_account_info
+            .lamports()
+            .checked_add(chunk_lamports)
             .unwrap();
-    
-        let is_initialized_flag = *dsa_data
-            .get(71)
+
+        let mut chunk_data = chunk_account_info
+            .data
+            .try_borrow_mut()
             .unwrap();
-    
-        if is_initialized_flag == false as u8 {
+        let chunk_data_len = chunk_data.len();
+        sol_memset(
+            &mut chunk_data,
+            0,
+            chunk_data_len
+        );
+
+        Ok(())
+    }
+
+    pub(super) fn patch_log_entry(patch_log_data: &[u8], slot: usize) -> &[u8] {
+        patch_log_data
+            .get(2 + slot * PATCH_LOG_ENTRY_LEN..2 + (slot + 1) * PATCH_LOG_ENTRY_LEN)
+            .unwrap()
+    }
+
+    pub(super) fn patch_log_entry_mut(patch_log_data: &mut [u8], slot: usize) -> &mut [u8] {
+        patch_log_data
+            .get_mut(2 + slot * PATCH_LOG_ENTRY_LEN..2 + (slot + 1) * PATCH_LOG_ENTRY_LEN)
+            .unwrap()
+    }
+
+    // Applies the 'count' pending patches from 'head' over the data-field, oldest first.
+    pub(super) fn fold_patch_log_entries(data_field: &mut [u8], patch_log_data: &[u8], head: usize, count: usize) {
+        for index in 0..count {
+            apply_patch_log_entry(
+                data_field,
+                patch_log_entry(patch_log_data, (head + index) % PATCH_LOG_CAPACITY)
+            );
+        };
+    }
+
+    // NOTE: A patch that no longer fits (the data-field has been shrunk directly since it was queued) is skipped.
+    pub(super) fn apply_patch_log_entry(data_field: &mut [u8], patch_log_entry: &[u8]) {
+        let offset = u16::from_le_bytes(
+            patch_log_entry
+                .get(..2)
+                .unwrap()
+                .try_into()
+                .unwrap()
+        ) as usize;
+        let len = *patch_log_entry
+            .get(2)
+            .unwrap() as usize;
+
+        if let Some(patched_data) = data_field.get_mut(offset..offset + len) {
+            sol_memcpy(
+                patched_data,
+                patch_log_entry
+                    .get(3..3 + len)
+                    .unwrap(),
+                len
+            );
+        };
+    }
+
+    // Shrinks the account by the freed '(old_data_len - new_data_len)' bytes, see 'refund_freed_rent_exempt_lamports' for
+    // their rent. The account's size is verified right after the realloc, any reserved capacity beyond the old data-field is kept.
+    pub(super) fn shrink_dsa_capacity(
+        data_storage_pda_account_info: &AccountInfo,
+        new_data_len: usize,
+        old_data_len: usize
+    ) -> ProgramResult {
+        let reserved_capacity = read_data_capacity(data_storage_pda_account_info)
+            .checked_sub(old_data_len)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        // realloc account data
+        calculate_new_dsa_size_and_realloc(
+            new_data_len,
+            old_data_len,
+            data_storage_pda_account_info,
+            false
+        )?;
+
+        if data_storage_pda_account_info.data_len() != total_account_size(new_data_len + reserved_capacity) {
             return Err(
-                ProgramError::UninitializedAccount
+                ProgramError::InvalidAccountData
             );
         };
-    
+
         Ok(())
     }
-    
-    pub(super) fn calculate_extra_rent_exempt_lamports(
-        old_data_length: usize,
-        new_data_length: usize,
-        new_is_bigger: bool
-    ) -> Result<u64, ProgramError> {
-        let extra_bytes_len: usize;
-        if new_is_bigger == false {
-            extra_bytes_len = old_data_length
-            .checked_sub(new_data_length)
+
+    // The rent-receiver gets the rent-exempt lamports of the '(old_data_len - new_data_len)' bytes a shrink freed.
+    pub(super) fn refund_freed_rent_exempt_lamports(
+        data_storage_pda_account_info: &AccountInfo,
+        rent_receiver_account_info: &AccountInfo,
+        new_data_len: usize,
+        old_data_len: usize
+    ) -> ProgramResult {
+        // calculate rent_exempt lamports to refund, never more than what's above the new size's rent-exempt minimum
+        // (accounts funded under other rent parameters could otherwise drop below it)
+        let extra_rent_lamports = calculate_extra_rent_exempt_lamports(
+            total_account_size(old_data_len),
+            total_account_size(new_data_len)
+        )?.min(
+            data_storage_pda_account_info
+                .lamports()
+                .saturating_sub(rent_for_data(new_data_len, &Rent::get()?))
+        );
+
+        // refund the extra rent_exempt
+        **data_storage_pda_account_info.try_borrow_mut_lamports()? = data_storage_pda_account_info
+            .lamports()
+            .checked_sub(extra_rent_lamports)
             .unwrap();
-        } else {
-            extra_bytes_len = new_data_length
-            .checked_sub(old_data_length)
+
+        **rent_receiver_account_info.try_borrow_mut_lamports()? = rent_receiver_account_info
+            .lamports()
+            .checked_add(extra_rent_lamports)
             .unwrap();
-        };
-    
-        let rent_sysvar = Rent::get()?;
-    
-        let extra_rent_lamports = (
-            rent_sysvar
-                .lamports_per_byte_year
-                .checked_mul(extra_bytes_len as u64)
-                .unwrap()
-        ).checked_mul(rent_sysvar.exemption_threshold as u64).unwrap();
-    
-        Ok(extra_rent_lamports)
+
+        Ok(())
     }
-    
+
     pub(super) fn calculate_new_dsa_size_and_realloc(
         new_data_len: usize,
         old_data_len: usize,
         data_storage_pda_account_info: &AccountInfo,
         new_is_bigger: bool
     ) -> ProgramResult {
-        let new_dsa_size: usize;
-        if new_is_bigger == false {
+        let new_dsa_size = if !new_is_bigger {
             let extra_bytes = old_data_len
                 .checked_sub(new_data_len)
                 .unwrap();
     
-            new_dsa_size = data_storage_pda_account_info
+            // NOTE: The data-field can shrink down to zero-length (data_len() == HEADER_LEN) but never into the header
+            let new_dsa_size = data_storage_pda_account_info
                 .data_len()
                 .checked_sub(extra_bytes)
-                .unwrap();
+                .ok_or(ProgramError::InvalidAccountData)?;
+            if new_dsa_size < HEADER_LEN {
+                return Err(
+                    ProgramError::InvalidAccountData
+                );
+            };
+
+            new_dsa_size
         } else {
             let extra_bytes = new_data_len
                 .checked_sub(old_data_len)
                 .unwrap();
         
-            new_dsa_size = data_storage_pda_account_info
+            let new_dsa_size = data_storage_pda_account_info
                 .data_len()
                 .checked_add(extra_bytes)
                 .unwrap();
+            // 'realloc' would reject accounts bigger than 10 MiB with an opaque error
+            if new_dsa_size > MAX_PERMITTED_DATA_LENGTH as usize {
+                return Err(
+                    DataStorageError::DataTooLarge.into()
+                );
+            };
+
+            new_dsa_size
         };
     
+        // zero the bytes a grow exposes, they may hold stale bytes of a bigger allocation made earlier in the instruction
         data_storage_pda_account_info.realloc(
             new_dsa_size,
-            false
+            new_is_bigger
         )?;
     
         Ok(())
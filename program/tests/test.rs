@@ -19,6 +19,7 @@ use {
         native_token::sol_to_lamports, 
         pubkey::Pubkey, 
         rent::{
+            Rent,
             DEFAULT_EXEMPTION_THRESHOLD, 
             DEFAULT_LAMPORTS_PER_BYTE_YEAR
         }, 
@@ -33,7 +34,34 @@ use {
             Transaction,
             TransactionError
         },
-        system_instruction::SystemError
+        system_instruction::{
+            SystemError,
+            MAX_PERMITTED_DATA_LENGTH,
+            transfer as transfer_lamports
+        },
+        program_error::ProgramError,
+        decode_error::DecodeError,
+        keccak::{
+            hash as keccak_hash,
+            hashv as keccak_hashv
+        },
+        hash::hashv,
+        account_info::AccountInfo,
+        entrypoint::{
+            ProgramResult,
+            MAX_PERMITTED_DATA_INCREASE
+        },
+        program::{
+            invoke,
+            invoke_signed,
+            get_return_data,
+            set_return_data
+        },
+        sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID,
+        bpf_loader_upgradeable::{
+            ID as BPF_LOADER_UPGRADEABLE_ID,
+            UpgradeableLoaderState
+        }
     },
 
     data_storage::{
@@ -42,15 +70,181 @@ use {
         CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
         EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
         CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
-        Events
+        LOG_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        DUPLICATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR,
+        APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        LOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        UNLOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        SELF_TEST_INSTRUCTION_DISCRIMINATOR,
+        TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR,
+        SEAL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR,
+        REPAIR_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR,
+        IS_DATA_STORAGE_ACCOUNT_IMMUTABLE_INSTRUCTION_DISCRIMINATOR,
+        FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        VERIFY_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR,
+        APPEND_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR,
+        COMPACT_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR,
+        SET_DATA_STORAGE_ACCOUNT_DELEGATE_INSTRUCTION_DISCRIMINATOR,
+        ATTEST_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR,
+        GET_DATA_STORAGE_ACCOUNT_METADATA_INSTRUCTION_DISCRIMINATOR,
+        CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR,
+        EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR,
+        FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR,
+        CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        SOFT_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        REVIVE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        BATCH_CREATE_DATA_STORAGE_ACCOUNTS_INSTRUCTION_DISCRIMINATOR,
+        MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT_INSTRUCTION_DISCRIMINATOR,
+        CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        TOP_UP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR,
+        GET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR,
+        REMOVE_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR,
+        TOUCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        RENAME_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR,
+        EDIT_FLAG_DISPLAY_LABEL,
+        EDIT_FLAG_IDEMPOTENCY_KEY,
+        EDIT_FLAG_EXPECTED_REVISION,
+        EDIT_FLAG_COMPRESSED,
+        HEADER_LEN,
+        OWNER_OFFSET,
+        LABEL_OFFSET,
+        LAST_UPDATED_OFFSET,
+        BUMP_OFFSET,
+        INIT_OFFSET,
+        DATA_LEN_OFFSET,
+        MAX_SIZE_OFFSET,
+        LOCK_HOLDER_OFFSET,
+        DISPLAY_LABEL_OFFSET,
+        IDEMPOTENCY_KEY_OFFSET,
+        CPI_ALLOWLIST_OFFSET,
+        CREATED_AT_OFFSET,
+        REVISION_OFFSET,
+        DATA_HASH_OFFSET,
+        DELEGATE_OFFSET,
+        EXPIRES_AT_OFFSET,
+        LAST_EDITOR_OFFSET,
+        CLOSED_AT_OFFSET,
+        HEADER_VERSION_OFFSET,
+        LOCK_UNTIL_OFFSET,
+        ACCOUNT_DISCRIMINATOR_OFFSET,
+        DATA_OFFSET,
+        KIND_OFFSET,
+        MIN_DATA_LEN,
+        MAX_DATA_LEN,
+        MAX_VARIABLE_LABEL_LENGTH,
+        MAX_BATCH_CREATE_LEN,
+        LAYOUT_VERSION_FIXED_LABEL,
+        LAYOUT_VERSION_VARIABLE_LABEL,
+        HEADER_VERSION,
+        ACCOUNT_KIND_BYTES,
+        ACCOUNT_KIND_KEY_VALUE,
+        ACCOUNT_KIND_CHUNKED,
+        DATA_STORAGE_ACCOUNT_DISCRIMINATOR,
+        CHUNK_LEN,
+        V1_HEADER_LEN,
+        DATA_STORAGE_SEED_PREFIX,
+        FUNDING_LEDGER_SEED_PREFIX,
+        FUNDING_LEDGER_ENTRY_LEN,
+        PATCH_LOG_SEED_PREFIX,
+        PATCH_LOG_CAPACITY,
+        PATCH_LOG_LEN,
+        MAX_PATCH_LOG_ENTRY_DATA_LEN,
+        CONFIG_LEN,
+        Events,
+        data_as_str,
+        has_been_edited,
+        read_funding_ledger,
+        rent_exempt_lamports_delta,
+        rent_for_data,
+        total_account_size,
+        read_materialized_data,
+        read_entries,
+        find_data_storage_pda,
+        find_chunk_pda,
+        find_config_pda,
+        find_variable_label_data_storage_pda,
+        create_data_storage_pda,
+        encode_instruction_discriminator,
+        DataStorageAccount,
+        DataStorageAccountView,
+        DataStorageAccountMetadata,
+        DataStorageConfig,
+        DataStorageInstruction,
+        ANCHOR_INSTRUCTION_DISCRIMINATORS
     },
 
     arrayref::{
         array_ref,
         array_refs
-    }
+    },
+
+    base64::{
+        Engine,
+        prelude::BASE64_STANDARD
+    },
+
+    borsh::BorshDeserialize
 };
 
+fn data_storage_account_data(
+    authority: &Pubkey,
+    label: &[u8; 30],
+    bump: u8,
+    data: &[u8]
+) -> Vec<u8> {
+    vec![
+        authority
+            .to_bytes()
+            .to_vec(),
+        label.to_vec(),
+        i64::to_le_bytes(0).to_vec(),
+        vec![ bump ],
+        vec![ true as u8 ],
+        u16::to_le_bytes(data.len() as u16).to_vec(),
+        u32::to_le_bytes(0).to_vec(),
+        vec![ 0; 40 ],
+        label.to_vec(),
+        u64::to_le_bytes(0).to_vec(),
+        vec![ 0; 96 ],
+        vec![ false as u8 ],
+        i64::to_le_bytes(0).to_vec(),
+        u32::to_le_bytes(0).to_vec(),
+        hashv(&[ data ]).to_bytes().to_vec(),
+        vec![ 0; 32 ],
+        i64::to_le_bytes(0).to_vec(),
+        vec![ 0; 32 ],
+        vec![ LAYOUT_VERSION_FIXED_LABEL, 0 ],
+        vec![ 0; MAX_VARIABLE_LABEL_LENGTH ],
+        vec![ false as u8 ],
+        i64::to_le_bytes(0).to_vec(),
+        vec![ HEADER_VERSION, false as u8, ACCOUNT_KIND_BYTES ],
+        i64::to_le_bytes(0).to_vec(),
+        vec![ DATA_STORAGE_ACCOUNT_DISCRIMINATOR ],
+        data.to_vec()
+    ].into_iter().flatten().collect::<Vec<_>>()
+}
+
+// Decodes the borsh-serialized events out of the transaction's "Program data: " log lines.
+fn emitted_events(logs: &[String]) -> Vec<Events> {
+    logs
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| BASE64_STANDARD.decode(data).ok())
+        .filter_map(|data| Events::try_from_slice(&data).ok())
+        .collect()
+}
+
 fn setup(program_id: &Pubkey) -> ProgramTest {
     ProgramTest::new(
         "data_storage",
@@ -99,17 +293,18 @@ async fn test_create_and_initialize_new_data_storage_account() {
             &data_storage_program_id
         );
 
-        let instruction_accounts: [AccountMeta; 4] = [
+        let instruction_accounts: [AccountMeta; 5] = [
             AccountMeta::new(data_storage_pda.0, false),
             AccountMeta::new_readonly(authority_keypair.pubkey(), true),
             AccountMeta::new(ptc.payer.pubkey(), true),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
         ];
 
         let data_storage_account_data = String::from("PooriaGG..");
 
         let instruction_data: &[u8] = &[
-            &[ CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
             data_storage_account_label.as_slice(),
             data_storage_account_data.as_bytes()
         ].concat();
@@ -142,15 +337,13 @@ async fn test_create_and_initialize_new_data_storage_account() {
             authority_account: authority_keypair.pubkey(),
             account_label: data_storage_account_label
         };
-        let log_event = format!("Program log: {:?}", event);
-
-        assert_eq!(
-            simulation_result
-                .simulation_details
-                .unwrap()
-                .logs
-                .contains(&log_event),
-            true,
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
             "Invalid emitted event!"
         );
 
@@ -175,11 +368,11 @@ async fn test_create_and_initialize_new_data_storage_account() {
 
         assert_eq!(
             data.len(),
-            84,
+            466,
             "Invalid data length."
         );
         
-        let dsa_data = array_ref![ data, 0, 84 ];
+        let dsa_data = array_ref![ data, 0, 466 ];
         let (
             expected_authority,
             expected_label,
@@ -187,8 +380,10 @@ async fn test_create_and_initialize_new_data_storage_account() {
             _,
             expected_is_initialize,
             expected_data_length,
+            expected_max_size,
+            _,
             expected_data
-        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 10 ];
+        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 4, 378, 10 ];
 
         assert_eq!(
             *expected_authority,
@@ -215,6 +410,21 @@ async fn test_create_and_initialize_new_data_storage_account() {
             10u16,
             "Invalid expected data_length."
         );
+        assert_eq!(
+            u32::from_le_bytes(*expected_max_size),
+            0u32,
+            "Invalid expected max_size."
+        );
+        assert_ne!(
+            i64::from_le_bytes(*array_ref![ data, CREATED_AT_OFFSET, 8 ]),
+            0i64,
+            "Invalid expected created_at."
+        );
+        assert_eq!(
+            *array_ref![ data, DATA_HASH_OFFSET, 32 ],
+            hashv(&[ data_storage_account_data.as_bytes() ]).to_bytes(),
+            "Invalid expected data hash."
+        );
         assert_eq!(
             String::from_utf8(expected_data.to_vec()).unwrap(),
             data_storage_account_data,
@@ -223,6 +433,85 @@ async fn test_create_and_initialize_new_data_storage_account() {
     };
     // success - create a mutable data storage account
 
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - create a zero-length-data account, every header byte must be exactly what the program writes
+    {
+        let data_storage_account_label: [u8; 30] = [ 66; 30 ];
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = find_data_storage_pda(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            &data_storage_program_id
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new(dsa_addr, false),
+                        AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                        AccountMeta::new(ptc.payer.pubkey(), true),
+                        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                        AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+                    ],
+                    data: [
+                        encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                        data_storage_account_label.as_slice(),
+                    ].concat()
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // created-at comes from the clock & the last-editor is the authority, the rest is the fixture's
+        let mut expected_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            &[]
+        );
+        expected_data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + 8].copy_from_slice(&data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + 8]);
+        expected_data[LAST_EDITOR_OFFSET..LAST_EDITOR_OFFSET + 32].copy_from_slice(authority_keypair.pubkey().as_ref());
+
+        assert_ne!(
+            i64::from_le_bytes(*array_ref![ data, CREATED_AT_OFFSET, 8 ]),
+            0i64,
+            "Invalid expected created_at."
+        );
+        assert_eq!(
+            data,
+            expected_data,
+            "Invalid account bytes."
+        );
+    };
+    // success - create a zero-length-data account, every header byte must be exactly what the program writes
+
     ptc
         .get_new_latest_blockhash()
         .await
@@ -242,17 +531,18 @@ async fn test_create_and_initialize_new_data_storage_account() {
             &data_storage_program_id
         );
 
-        let instruction_accounts: [AccountMeta; 4] = [
+        let instruction_accounts: [AccountMeta; 5] = [
             AccountMeta::new(data_storage_pda.0, false),
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
             AccountMeta::new(ptc.payer.pubkey(), true),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
         ];
 
         let data_storage_account_data = String::from("PooriaGG..");
 
         let instruction_data: &[u8] = &[
-            &[ CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
             data_storage_account_label.as_slice(),
             data_storage_account_data.as_bytes()
         ].concat();
@@ -291,11 +581,11 @@ async fn test_create_and_initialize_new_data_storage_account() {
 
         assert_eq!(
             data.len(),
-            84,
+            466,
             "Invalid data length."
         );
         
-        let dsa_data = array_ref![ data, 0, 84 ];
+        let dsa_data = array_ref![ data, 0, 466 ];
         let (
             expected_authority,
             expected_label,
@@ -303,8 +593,10 @@ async fn test_create_and_initialize_new_data_storage_account() {
             _,
             expected_is_initialize,
             expected_data_length,
+            expected_max_size,
+            _,
             expected_data
-        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 10 ];
+        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 4, 378, 10 ];
 
         assert_eq!(
             *expected_authority,
@@ -331,6 +623,11 @@ async fn test_create_and_initialize_new_data_storage_account() {
             10u16,
             "Invalid expected data_length."
         );
+        assert_eq!(
+            u32::from_le_bytes(*expected_max_size),
+            0u32,
+            "Invalid expected max_size."
+        );
         assert_eq!(
             String::from_utf8(expected_data.to_vec()).unwrap(),
             data_storage_account_data,
@@ -344,7 +641,7 @@ async fn test_create_and_initialize_new_data_storage_account() {
         .await
         .unwrap();
 
-    // faliure - account already in use
+    // faliure - label already used
     {
         let mut data_storage_account_label: [u8; 30] = [0; 30];
         data_storage_account_label.fill(65);
@@ -358,17 +655,18 @@ async fn test_create_and_initialize_new_data_storage_account() {
             &data_storage_program_id
         );
 
-        let instruction_accounts: [AccountMeta; 4] = [
+        let instruction_accounts: [AccountMeta; 5] = [
             AccountMeta::new(data_storage_pda.0, false),
             AccountMeta::new_readonly(authority_keypair.pubkey(), true),
             AccountMeta::new(ptc.payer.pubkey(), true),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
         ];
 
         let data_storage_account_data = String::from("PooriaGG..");
 
         let instruction_data: &[u8] = &[
-            &[ CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
             data_storage_account_label.as_slice(),
             data_storage_account_data.as_bytes()
         ].concat();
@@ -401,12 +699,12 @@ async fn test_create_and_initialize_new_data_storage_account() {
             TransactionError::InstructionError(
                 0,
                 InstructionError::Custom(
-                    SystemError::AccountAlreadyInUse as u32
+                    DataStorageError::LabelAlreadyUsed as u32
                 )
             )
         );
     }
-    // faliure - account already in use
+    // faliure - label already used
 
     ptc
         .get_new_latest_blockhash()
@@ -427,17 +725,18 @@ async fn test_create_and_initialize_new_data_storage_account() {
             &data_storage_program_id
         );
 
-        let instruction_accounts: [AccountMeta; 4] = [
+        let instruction_accounts: [AccountMeta; 5] = [
             AccountMeta::new(data_storage_pda.0, false),
             AccountMeta::new_readonly(authority_keypair.pubkey(), true),
             AccountMeta::new(ptc.payer.pubkey(), true),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
         ];
 
         let data_storage_account_data = String::from("SOL");
 
         let instruction_data: &[u8] = &[
-            &[ CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
             data_storage_account_label.as_slice(),
             data_storage_account_data.as_bytes()
         ].concat();
@@ -496,17 +795,18 @@ async fn test_create_and_initialize_new_data_storage_account() {
             &data_storage_program_id
         );
 
-        let instruction_accounts: [AccountMeta; 4] = [
+        let instruction_accounts: [AccountMeta; 5] = [
             AccountMeta::new(data_storage_pda.0, false),
             AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
             AccountMeta::new(ptc.payer.pubkey(), true),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
         ];
 
         let data_storage_account_data = String::from("SOL");
 
         let instruction_data: &[u8] = &[
-            &[ CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
             data_storage_account_label.as_slice(),
             data_storage_account_data.as_bytes()
         ].concat();
@@ -539,37 +839,18 @@ async fn test_create_and_initialize_new_data_storage_account() {
         };
     }
     // faliure - invalid seeds OR failed to find program address
-}
-
-#[tokio::test]
-async fn test_edit_data_storage_account() {
-    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
-    let mut pt = setup(&data_storage_program_id);
-
-    //? add authority account
-    let authority_keypair = Keypair::new();
-    pt.add_account(
-        authority_keypair.pubkey(),
-        SolanaAccount::new(
-            sol_to_lamports(1.0),
-            0,
-            &SYSTEM_PROGRAM_ID
-        )
-    );
-    //? add authority account
 
-    let mut ptc = pt.start_with_context().await;
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
 
-    // success - new len == old len
+    // failure - data exceeds max-size
     {
-        //? add data storage account
         let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(100);
+        data_storage_account_label.fill(101);
 
-        let (
-            dsa_addr,
-            dsa_bump
-        ) = Pubkey::find_program_address(
+        let data_storage_pda = Pubkey::find_program_address(
             &[
                 b"data_storage_account",
                 authority_keypair.pubkey().to_bytes().as_slice(),
@@ -578,65 +859,33 @@ async fn test_edit_data_storage_account() {
             &data_storage_program_id
         );
 
-        let account_data = vec![
-            authority_keypair
-                .pubkey()
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ true as u8 ],
-            u16::to_le_bytes(6).to_vec(),
-            (b"Solana").to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
-        let account_data_len = account_data.len();
-
-        let account_lamport_balance = sol_to_lamports(0.01);
-
-        ptc.set_account(
-            &dsa_addr,
-            &AccountSharedData::from(
-                SolanaAccount {
-                    data: account_data,
-                    owner: data_storage_program_id,
-                    lamports: account_lamport_balance,
-                    rent_epoch: Epoch::default(),
-                    executable: false
-                }
-            )
-        );
-        //? add data storage account
+        let instruction_accounts: [AccountMeta; 5] = [
+            AccountMeta::new(data_storage_pda.0, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ];
 
-        let current_time = 300_i64;
-        ptc
-            .set_sysvar::<Clock>(
-                &Clock {
-                    unix_timestamp: current_time,
-                    ..Clock::default()
-                }
-            );
+        let data_storage_account_data = String::from("PooriaGG..");
 
-        let new_data = "Pooria";
-        let instruction_data = &[
-            &[ EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
-            new_data.as_bytes()
+        let instruction_data: &[u8] = &[
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            u32::to_le_bytes(9).as_slice(),
+            i64::to_le_bytes(0).as_slice(),
+            data_storage_account_data.as_bytes()
         ].concat();
 
-        let instruction_accounts = vec![
-            AccountMeta::new(dsa_addr, false),
-            AccountMeta::new_readonly(authority_keypair.pubkey(), true)
-        ];
-
         let instruction = Instruction {
             program_id: data_storage_program_id,
-            accounts: instruction_accounts,
+            accounts: instruction_accounts.to_vec(),
             data: instruction_data.to_vec()
         };
 
         let transaction = Transaction::new_signed_with_payer(
             &[ instruction ],
-            Some( &ptc.payer.pubkey() ),
+            Some(&ptc.payer.pubkey()),
             &[
                 &ptc.payer,
                 &authority_keypair
@@ -644,102 +893,36 @@ async fn test_edit_data_storage_account() {
             ptc.last_blockhash
         );
 
-        // validate emitted event
-        let simulation_result = ptc
-            .banks_client
-            .simulate_transaction(transaction.clone())
-            .await
-            .unwrap();
-
-        let event = Events::DataStorageAccountEdited {
-            data_storage_account: dsa_addr,
-            authority_account: authority_keypair.pubkey(),
-            old_data_len: 6,
-            new_data_len: new_data.len()
-        };
-        let log_event = format!("Program log: {:?}", event);
-
-        assert_eq!(
-            simulation_result
-                .simulation_details
-                .unwrap()
-                .logs
-                .contains(&log_event),
-            true,
-            "Invalid emitted event!"
-        );
-
-        ptc
+        let error = ptc
             .banks_client
             .process_transaction(transaction)
             .await
+            .unwrap_err()
             .unwrap();
 
-        let SolanaAccount { data, lamports, .. } = ptc
-            .banks_client
-            .get_account(dsa_addr)
-            .await
-            .unwrap()
-            .unwrap();
-
-        assert_eq!(
-            account_lamport_balance,
-            lamports,
-            "Invalid PDA lamport balance."
-        );
-
-        assert_eq!(
-            data.len(),
-            account_data_len,
-            "Invalid data len."
-        );
-
-        let dsa_data = array_ref![ data, 0, 80 ];
-        let (
-            _,
-            _,
-            expected_last_updated,
-            _,
-            _,
-            expected_data_len,
-            expected_data
-        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 6 ];
-
-        assert_eq!(
-            expected_data_len,
-            &u16::to_le_bytes(new_data.len() as u16),
-            "Ivnalid data len."
-        );
-
-        assert_eq!(
-            expected_data.as_slice(),
-            new_data.as_bytes(),
-            "Invalid new data."
-        );
-
         assert_eq!(
-            expected_last_updated,
-            &current_time.to_le_bytes(),
-            "Invalid time."
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::QuotaExceeded as u32
+                )
+            )
         );
     }
-    // success - new len == old len
+    // failure - data exceeds max-size
 
     ptc
         .get_new_latest_blockhash()
         .await
         .unwrap();
 
-    // success - new len < old len
+    // failure - label is the seed-prefix
     {
-        //? add data storage account
         let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(90);
+        data_storage_account_label[..DATA_STORAGE_SEED_PREFIX.len()].copy_from_slice(DATA_STORAGE_SEED_PREFIX);
 
-        let (
-            dsa_addr,
-            dsa_bump
-        ) = Pubkey::find_program_address(
+        let data_storage_pda = Pubkey::find_program_address(
             &[
                 b"data_storage_account",
                 authority_keypair.pubkey().to_bytes().as_slice(),
@@ -748,69 +931,31 @@ async fn test_edit_data_storage_account() {
             &data_storage_program_id
         );
 
-        let old_data = "Solana";
-        let account_data = vec![
-            authority_keypair
-                .pubkey()
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ true as u8 ],
-            u16::to_le_bytes(old_data.len() as u16).to_vec(),
-            old_data
-                .as_bytes()
-                .to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
-        let account_data_len = account_data.len();
+        let instruction_accounts: [AccountMeta; 5] = [
+            AccountMeta::new(data_storage_pda.0, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ];
 
-        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+        let data_storage_account_data = String::from("PooriaGG..");
 
-        ptc.set_account(
-            &dsa_addr,
-            &AccountSharedData::from(
-                SolanaAccount {
-                    data: account_data,
-                    owner: data_storage_program_id,
-                    lamports: dsa_account_lamport_balance,
-                    rent_epoch: Epoch::default(),
-                    executable: false
-                }
-            )
-        );
-        //? add data storage account
-
-        let current_time = 450_i64;
-        ptc
-            .set_sysvar::<Clock>(
-                &Clock {
-                    unix_timestamp: current_time,
-                    ..Clock::default()
-                }
-            );
-
-        let new_data = "SOL";
-        let instruction_data = &[
-            &[ EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
-            new_data.as_bytes()
+        let instruction_data: &[u8] = &[
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            data_storage_account_data.as_bytes()
         ].concat();
 
-        let instruction_accounts = vec![
-            AccountMeta::new(dsa_addr, false),
-            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
-            AccountMeta::new(authority_keypair.pubkey(), false)
-        ];
-
         let instruction = Instruction {
             program_id: data_storage_program_id,
-            accounts: instruction_accounts,
+            accounts: instruction_accounts.to_vec(),
             data: instruction_data.to_vec()
         };
 
         let transaction = Transaction::new_signed_with_payer(
             &[ instruction ],
-            Some( &ptc.payer.pubkey() ),
+            Some(&ptc.payer.pubkey()),
             &[
                 &ptc.payer,
                 &authority_keypair
@@ -818,125 +963,101 @@ async fn test_edit_data_storage_account() {
             ptc.last_blockhash
         );
 
-        let rent_receiver_before_tx_lamport_balance = ptc
-            .banks_client
-            .get_balance(authority_keypair.pubkey())
-            .await
-            .unwrap();
-
-        // validate emitted event
-        let simulation_result = ptc
-            .banks_client
-            .simulate_transaction(transaction.clone())
-            .await
-            .unwrap();
-
-        let event = Events::DataStorageAccountEdited {
-            data_storage_account: dsa_addr,
-            authority_account: authority_keypair.pubkey(),
-            old_data_len: old_data.len(),
-            new_data_len: new_data.len()
-        };
-        let log_event = format!("Program log: {:?}", event);
-
-        assert_eq!(
-            simulation_result
-                .simulation_details
-                .unwrap()
-                .logs
-                .contains(&log_event),
-            true,
-            "Invalid emitted event!"
-        );
-
-        ptc
+        let error = ptc
             .banks_client
             .process_transaction(transaction)
             .await
+            .unwrap_err()
             .unwrap();
 
-        let SolanaAccount { data, lamports: dsa_after_tx_lamport_balance, .. } = ptc
-            .banks_client
-            .get_account(dsa_addr)
-            .await
-            .unwrap()
-            .unwrap();
-
-        let rent_receiver_after_tx_lamport_balance = ptc
-            .banks_client
-            .get_balance(authority_keypair.pubkey())
-            .await
-            .unwrap();
-
-        let extra_rent_exempt_lamports = (
-            (old_data.len() - new_data.len()) as u64 * DEFAULT_LAMPORTS_PER_BYTE_YEAR
-        ) * DEFAULT_EXEMPTION_THRESHOLD as u64;
-
         assert_eq!(
-            dsa_account_lamport_balance - extra_rent_exempt_lamports,
-            dsa_after_tx_lamport_balance,
-            "Invalid data-storage-account's lamport balance."
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::InvalidLabel as u32
+                )
+            )
         );
+    }
+    // failure - label is the seed-prefix
 
-        assert_eq!(
-            rent_receiver_before_tx_lamport_balance + extra_rent_exempt_lamports,
-            rent_receiver_after_tx_lamport_balance,
-            "Invalid rent_receiver_account's lamport balance."
-        );
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
 
-        let new_account_data_len = account_data_len - ( old_data.len() - new_data.len() ) ;
-        assert_eq!(
-            new_account_data_len,
-            data.len(),
-            "Invalid data-storage-account's data len."
+    // failure - label with an interior NUL / a control character
+    for logical_label in [ b"Pooria\0GG".as_slice(), b"Pooria\tGG".as_slice() ] {
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label[..logical_label.len()].copy_from_slice(logical_label);
+
+        let data_storage_pda = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
         );
 
-        let dsa_data = array_ref![ data, 0, 77 ];
-        let (
-            _,
-            _,
-            expected_last_updated,
-            _,
-            _,
-            expected_data_len,
-            expected_data
-        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 3 ];
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(data_storage_pda.0, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                b"PooriaGG..".as_slice()
+            ].concat()
+        };
 
-        assert_eq!(
-            expected_data_len,
-            &u16::to_le_bytes(new_data.len() as u16),
-            "Invalid data len."
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
         );
 
-        assert_eq!(
-            expected_data.as_slice(),
-            new_data.as_bytes(),
-            "Invalid new data."
-        );
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
 
         assert_eq!(
-            expected_last_updated,
-            &i64::to_le_bytes(current_time),
-            "Invalid last-updated-time."
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::InvalidLabel as u32
+                )
+            ),
+            "Labels with non-printable characters must be rejected."
         );
     }
-    // success - new len < old len
+    // failure - label with an interior NUL / a control character
 
     ptc
         .get_new_latest_blockhash()
         .await
         .unwrap();
 
-    // success - new len > old len
+    // failure - funding account is not signer
     {
-        //? add data storage account
         let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(68);
+        data_storage_account_label.fill(70);
 
-        let (
-            dsa_addr,
-            dsa_bump
-        ) = Pubkey::find_program_address(
+        let data_storage_pda = Pubkey::find_program_address(
             &[
                 b"data_storage_account",
                 authority_keypair.pubkey().to_bytes().as_slice(),
@@ -945,70 +1066,25 @@ async fn test_edit_data_storage_account() {
             &data_storage_program_id
         );
 
-        let old_data = "Solana";
-        let account_data = vec![
-            authority_keypair
-                .pubkey()
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ true as u8 ],
-            u16::to_le_bytes(old_data.len() as u16).to_vec(),
-            old_data
-                .as_bytes()
-                .to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
-        let account_data_len = account_data.len();
-
-        let dsa_account_lamport_balance = sol_to_lamports(0.01);
-
-        ptc.set_account(
-            &dsa_addr,
-            &AccountSharedData::from(
-                SolanaAccount {
-                    data: account_data,
-                    owner: data_storage_program_id,
-                    lamports: dsa_account_lamport_balance,
-                    rent_epoch: Epoch::default(),
-                    executable: false
-                }
-            )
-        );
-        //? add data storage account
-
-        let current_time = 550_i64;
-        ptc
-            .set_sysvar::<Clock>(
-                &Clock {
-                    unix_timestamp: current_time,
-                    ..Clock::default()
-                }
-            );
-
-        let new_data = "PooriaGG!";
-        let instruction_data = &[
-            &[ EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
-            new_data.as_bytes()
-        ].concat();
-
-        let instruction_accounts = vec![
-            AccountMeta::new(dsa_addr, false),
-            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
-            AccountMeta::new(authority_keypair.pubkey(), false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
-        ];
-
         let instruction = Instruction {
             program_id: data_storage_program_id,
-            accounts: instruction_accounts,
-            data: instruction_data.to_vec()
+            accounts: vec![
+                AccountMeta::new(data_storage_pda.0, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                b"PooriaGG..".as_slice()
+            ].concat()
         };
 
         let transaction = Transaction::new_signed_with_payer(
             &[ instruction ],
-            Some( &ptc.payer.pubkey() ),
+            Some(&ptc.payer.pubkey()),
             &[
                 &ptc.payer,
                 &authority_keypair
@@ -1016,184 +1092,69 @@ async fn test_edit_data_storage_account() {
             ptc.last_blockhash
         );
 
-        let funding_account_before_tx_lamport_balance = ptc
-            .banks_client
-            .get_balance(authority_keypair.pubkey())
-            .await
-            .unwrap();
-
-        // validate emitted event
-        let simulation_result = ptc
+        let error = ptc
             .banks_client
-            .simulate_transaction(transaction.clone())
+            .process_transaction(transaction)
             .await
+            .unwrap_err()
             .unwrap();
 
-        let event = Events::DataStorageAccountEdited {
-            data_storage_account: dsa_addr,
-            authority_account: authority_keypair.pubkey(),
-            old_data_len: old_data.len(),
-            new_data_len: new_data.len()
-        };
-        let log_event = format!("Program log: {:?}", event);
-
         assert_eq!(
-            simulation_result
-                .simulation_details
-                .unwrap()
-                .logs
-                .contains(&log_event),
-            true,
-            "Invalid emitted event!"
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::MissingRequiredSignature
+            )
         );
+    }
+    // failure - funding account is not signer
 
-        ptc
-            .banks_client
-            .process_transaction(transaction)
-            .await
-            .unwrap();
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
 
-        let SolanaAccount { data, lamports: dsa_after_tx_lamport_balance, .. } = ptc
-            .banks_client
-            .get_account(dsa_addr)
-            .await
-            .unwrap()
-            .unwrap();
-
-        let funding_account_after_tx_lamport_balance = ptc
-            .banks_client
-            .get_balance(authority_keypair.pubkey())
-            .await
-            .unwrap();
-
-        let extra_rent_exempt_lamports = (
-            (new_data.len() - old_data.len()) as u64 * DEFAULT_LAMPORTS_PER_BYTE_YEAR
-        ) * DEFAULT_EXEMPTION_THRESHOLD as u64;
-
-        assert_eq!(
-            dsa_account_lamport_balance + extra_rent_exempt_lamports,
-            dsa_after_tx_lamport_balance,
-            "Invalid data-storage-account's lamport balance."
-        );
-
-        assert_eq!(
-            funding_account_before_tx_lamport_balance - extra_rent_exempt_lamports,
-            funding_account_after_tx_lamport_balance,
-            "Invalid funding_account's lamport balance."
-        );
-
-        let new_account_data_len = account_data_len + ( new_data.len() - old_data.len() );
-        assert_eq!(
-            new_account_data_len,
-            data.len(),
-            "Invalid data-storage-account's data len."
-        );
-
-        let dsa_data = array_ref![ data, 0, 83 ];
-        let (
-            _,
-            _,
-            expected_last_updated,
-            _,
-            _,
-            expected_data_len,
-            expected_data
-        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 9 ];
-
-        assert_eq!(
-            expected_data_len,
-            &u16::to_le_bytes(new_data.len() as u16),
-            "Invalid data len."
-        );
-
-        assert_eq!(
-            expected_data.as_slice(),
-            new_data.as_bytes(),
-            "Invalid new data."
-        );
-
-        assert_eq!(
-            expected_last_updated,
-            &i64::to_le_bytes(current_time),
-            "Invalid last-updated-time."
-        );
-    }  
-    // success - new len > old len
-
-    ptc
-        .get_new_latest_blockhash()
-        .await
-        .unwrap();
-
-    // failure - immutable data storage account
+    // failure - immutable account with a max-size
     {
-        //? add data storage account
         let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(82);
+        data_storage_account_label.fill(102);
 
-        let (
-            dsa_addr,
-            dsa_bump
-        ) = Pubkey::find_program_address(
+        let data_storage_pda = Pubkey::find_program_address(
             &[
                 b"data_storage_account",
-                authority_keypair.pubkey().to_bytes().as_slice(),
+                SYSTEM_PROGRAM_ID.to_bytes().as_slice(),
                 &data_storage_account_label
             ],
             &data_storage_program_id
         );
 
-        let old_data = "Solana";
-        let account_data = vec![
-            SYSTEM_PROGRAM_ID
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ true as u8 ],
-            u16::to_le_bytes(old_data.len() as u16).to_vec(),
-            old_data
-                .as_bytes()
-                .to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
-
-        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+        let instruction_accounts: [AccountMeta; 5] = [
+            AccountMeta::new(data_storage_pda.0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ];
 
-        ptc.set_account(
-            &dsa_addr,
-            &AccountSharedData::from(
-                SolanaAccount {
-                    data: account_data,
-                    owner: data_storage_program_id,
-                    lamports: dsa_account_lamport_balance,
-                    rent_epoch: Epoch::default(),
-                    executable: false
-                }
-            )
-        );
-        //? add data storage account
+        let data_storage_account_data = String::from("PooriaGG..");
 
-        let new_data = "Pooria";
-        let instruction_data = &[
-            &[ EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
-            new_data.as_bytes()
+        let instruction_data: &[u8] = &[
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            u32::to_le_bytes(64).as_slice(),
+            i64::to_le_bytes(0).as_slice(),
+            data_storage_account_data.as_bytes()
         ].concat();
 
-        let instruction_accounts = vec![
-            AccountMeta::new(dsa_addr, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-        ];
-
         let instruction = Instruction {
             program_id: data_storage_program_id,
-            accounts: instruction_accounts,
+            accounts: instruction_accounts.to_vec(),
             data: instruction_data.to_vec()
         };
 
         let transaction = Transaction::new_signed_with_payer(
             &[ instruction ],
-            Some( &ptc.payer.pubkey() ),
+            Some(&ptc.payer.pubkey()),
             &[ &ptc.payer ],
             ptc.last_blockhash
         );
@@ -1210,28 +1171,26 @@ async fn test_edit_data_storage_account() {
             TransactionError::InstructionError(
                 0,
                 InstructionError::Custom(
-                    DataStorageError::ImmutableDataStorage as u32
+                    DataStorageError::InvalidData as u32
                 )
             )
         );
     }
-    // failure - immutable data storage account
+    // failure - immutable account with a max-size
 
     ptc
         .get_new_latest_blockhash()
         .await
         .unwrap();
 
-    // failure - authority account is not signer
-    {
-        //? add data storage account
-        let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(82);
+    // failure - undersized instruction-data, a truncated label (29 bytes) & an options payload without max-size/expires-at (30 bytes)
+    for (discriminator, instruction_data_len) in [
+        (CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR, 29),
+        (CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR, 30)
+    ] {
+        let data_storage_account_label: [u8; 30] = [ 103; 30 ];
 
-        let (
-            dsa_addr,
-            dsa_bump
-        ) = Pubkey::find_program_address(
+        let data_storage_pda = Pubkey::find_program_address(
             &[
                 b"data_storage_account",
                 authority_keypair.pubkey().to_bytes().as_slice(),
@@ -1240,59 +1199,28 @@ async fn test_edit_data_storage_account() {
             &data_storage_program_id
         );
 
-        let old_data = "Solana";
-        let account_data = vec![
-            authority_keypair
-                .pubkey()
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ true as u8 ],
-            u16::to_le_bytes(old_data.len() as u16).to_vec(),
-            old_data
-                .as_bytes()
-                .to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
-
-        let dsa_account_lamport_balance = sol_to_lamports(0.01);
-
-        ptc.set_account(
-            &dsa_addr,
-            &AccountSharedData::from(
-                SolanaAccount {
-                    data: account_data,
-                    owner: data_storage_program_id,
-                    lamports: dsa_account_lamport_balance,
-                    rent_epoch: Epoch::default(),
-                    executable: false
-                }
-            )
-        );
-        //? add data storage account
-
-        let new_data = "Pooria";
-        let instruction_data = &[
-            &[ EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
-            new_data.as_bytes()
-        ].concat();
-
-        let instruction_accounts = vec![
-            AccountMeta::new(dsa_addr, false),
-            AccountMeta::new_readonly(authority_keypair.pubkey(), false),
-        ];
-
         let instruction = Instruction {
             program_id: data_storage_program_id,
-            accounts: instruction_accounts,
-            data: instruction_data.to_vec()
+            accounts: vec![
+                AccountMeta::new(data_storage_pda.0, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(discriminator).as_slice(),
+                &data_storage_account_label[..instruction_data_len]
+            ].concat()
         };
 
         let transaction = Transaction::new_signed_with_payer(
             &[ instruction ],
-            Some( &ptc.payer.pubkey() ),
-            &[ &ptc.payer ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
             ptc.last_blockhash
         );
 
@@ -1307,27 +1235,19 @@ async fn test_edit_data_storage_account() {
             error,
             TransactionError::InstructionError(
                 0,
-                InstructionError::MissingRequiredSignature
+                InstructionError::Custom(
+                    DataStorageError::InvalidData as u32
+                )
             )
         );
-    }
-    // failure - authority account is not signer
-
-    ptc
-        .get_new_latest_blockhash()
-        .await
-        .unwrap();
+    };
+    // failure - undersized instruction-data, a truncated label (29 bytes) & an options payload without max-size/expires-at (30 bytes)
 
-    // failure - invalid data-storage-account's program owner
+    // success - label only (empty data-field)
     {
-        //? add data storage account
-        let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(82);
+        let data_storage_account_label: [u8; 30] = [ 103; 30 ];
 
-        let (
-            dsa_addr,
-            dsa_bump
-        ) = Pubkey::find_program_address(
+        let data_storage_pda = Pubkey::find_program_address(
             &[
                 b"data_storage_account",
                 authority_keypair.pubkey().to_bytes().as_slice(),
@@ -1336,58 +1256,24 @@ async fn test_edit_data_storage_account() {
             &data_storage_program_id
         );
 
-        let old_data = "Solana";
-        let account_data = vec![
-            authority_keypair
-                .pubkey()
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ true as u8 ],
-            u16::to_le_bytes(old_data.len() as u16).to_vec(),
-            old_data
-                .as_bytes()
-                .to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
-
-        let dsa_account_lamport_balance = sol_to_lamports(0.01);
-
-        ptc.set_account(
-            &dsa_addr,
-            &AccountSharedData::from(
-                SolanaAccount {
-                    data: account_data,
-                    owner: SYSTEM_PROGRAM_ID,
-                    lamports: dsa_account_lamport_balance,
-                    rent_epoch: Epoch::default(),
-                    executable: false
-                }
-            )
-        );
-        //? add data storage account
-
-        let new_data = "Pooria";
-        let instruction_data = &[
-            &[ EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
-            new_data.as_bytes()
-        ].concat();
-
-        let instruction_accounts = vec![
-            AccountMeta::new(dsa_addr, false),
-            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
-        ];
-
         let instruction = Instruction {
             program_id: data_storage_program_id,
-            accounts: instruction_accounts,
-            data: instruction_data.to_vec()
+            accounts: vec![
+                AccountMeta::new(data_storage_pda.0, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+            ].concat()
         };
 
         let transaction = Transaction::new_signed_with_payer(
             &[ instruction ],
-            Some( &ptc.payer.pubkey() ),
+            Some(&ptc.payer.pubkey()),
             &[
                 &ptc.payer,
                 &authority_keypair
@@ -1395,38 +1281,38 @@ async fn test_edit_data_storage_account() {
             ptc.last_blockhash
         );
 
-        let error = ptc
+        ptc
             .banks_client
             .process_transaction(transaction)
             .await
-            .unwrap_err()
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(data_storage_pda.0)
+            .await
+            .unwrap()
             .unwrap();
 
         assert_eq!(
-            error,
-            TransactionError::InstructionError(
-                0,
-                InstructionError::InvalidAccountOwner
-            )
+            data.len(),
+            HEADER_LEN,
+            "Invalid data length."
         );
     }
-    // failure - invalid data-storage-account's program owner
+    // success - label only (empty data-field)
 
     ptc
         .get_new_latest_blockhash()
         .await
         .unwrap();
 
-    // failure - account is not initialized
+    // success - created account can be edited (stored bump round-trip)
     {
-        //? add data storage account
         let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(82);
+        data_storage_account_label.fill(102);
 
-        let (
-            dsa_addr,
-            dsa_bump
-        ) = Pubkey::find_program_address(
+        let data_storage_pda = Pubkey::find_program_address(
             &[
                 b"data_storage_account",
                 authority_keypair.pubkey().to_bytes().as_slice(),
@@ -1435,58 +1321,47 @@ async fn test_edit_data_storage_account() {
             &data_storage_program_id
         );
 
-        let old_data = "Solana";
-        let account_data = vec![
-            authority_keypair
-                .pubkey()
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ false as u8 ],
-            u16::to_le_bytes(old_data.len() as u16).to_vec(),
-            old_data
-                .as_bytes()
-                .to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
+        let data_storage_account_data = String::from("PooriaGG..");
 
-        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+        let create_instruction_data: &[u8] = &[
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            data_storage_account_data.as_bytes()
+        ].concat();
 
-        ptc.set_account(
-            &dsa_addr,
-            &AccountSharedData::from(
-                SolanaAccount {
-                    data: account_data,
-                    owner: data_storage_program_id,
-                    lamports: dsa_account_lamport_balance,
-                    rent_epoch: Epoch::default(),
-                    executable: false
-                }
-            )
-        );
-        //? add data storage account
+        let create_instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(data_storage_pda.0, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: create_instruction_data.to_vec()
+        };
 
-        let new_data = "Pooria";
-        let instruction_data = &[
-            &[ EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
+        let new_data = "Solana!!!!";
+        let edit_instruction_data: &[u8] = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
             new_data.as_bytes()
         ].concat();
 
-        let instruction_accounts = vec![
-            AccountMeta::new(dsa_addr, false),
-            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
-        ];
-
-        let instruction = Instruction {
+        let edit_instruction = Instruction {
             program_id: data_storage_program_id,
-            accounts: instruction_accounts,
-            data: instruction_data.to_vec()
+            accounts: vec![
+                AccountMeta::new(data_storage_pda.0, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: edit_instruction_data.to_vec()
         };
 
         let transaction = Transaction::new_signed_with_payer(
-            &[ instruction ],
-            Some( &ptc.payer.pubkey() ),
+            &[
+                create_instruction,
+                edit_instruction
+            ],
+            Some(&ptc.payer.pubkey()),
             &[
                 &ptc.payer,
                 &authority_keypair
@@ -1494,35 +1369,57 @@ async fn test_edit_data_storage_account() {
             ptc.last_blockhash
         );
 
-        let error = ptc
+        ptc
             .banks_client
             .process_transaction(transaction)
             .await
-            .unwrap_err()
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(data_storage_pda.0)
+            .await
+            .unwrap()
             .unwrap();
 
         assert_eq!(
-            error,
-            TransactionError::InstructionError(
-                0,
-                InstructionError::UninitializedAccount
-            )
+            data[BUMP_OFFSET],
+            data_storage_pda.1,
+            "Invalid expected bump."
+        );
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            new_data.as_bytes(),
+            "Invalid expected data."
         );
     }
-    // failure - account is not initialized
+    // success - created account can be edited (stored bump round-trip)
+}
 
-    ptc
-        .get_new_latest_blockhash()
-        .await
-        .unwrap();
+#[tokio::test]
+async fn test_edit_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
 
-    // failure - invalid authority
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    // success - new len == old len
     {
         //? add data storage account
         let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(82);
-
-        let unknown_wallet = Keypair::new();
+        data_storage_account_label.fill(100);
 
         let (
             dsa_addr,
@@ -1536,23 +1433,15 @@ async fn test_edit_data_storage_account() {
             &data_storage_program_id
         );
 
-        let old_data = "Solana";
-        let account_data = vec![
-            authority_keypair
-                .pubkey()
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ true as u8 ],
-            u16::to_le_bytes(old_data.len() as u16).to_vec(),
-            old_data
-                .as_bytes()
-                .to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+        let account_data_len = account_data.len();
 
-        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+        let account_lamport_balance = sol_to_lamports(0.01);
 
         ptc.set_account(
             &dsa_addr,
@@ -1560,7 +1449,7 @@ async fn test_edit_data_storage_account() {
                 SolanaAccount {
                     data: account_data,
                     owner: data_storage_program_id,
-                    lamports: dsa_account_lamport_balance,
+                    lamports: account_lamport_balance,
                     rent_epoch: Epoch::default(),
                     executable: false
                 }
@@ -1568,15 +1457,24 @@ async fn test_edit_data_storage_account() {
         );
         //? add data storage account
 
+        let current_time = 300_i64;
+        ptc
+            .set_sysvar::<Clock>(
+                &Clock {
+                    unix_timestamp: current_time,
+                    ..Clock::default()
+                }
+            );
+
         let new_data = "Pooria";
         let instruction_data = &[
-            &[ EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
             new_data.as_bytes()
         ].concat();
 
         let instruction_accounts = vec![
             AccountMeta::new(dsa_addr, false),
-            AccountMeta::new_readonly(unknown_wallet.pubkey(), true),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true)
         ];
 
         let instruction = Instruction {
@@ -1590,59 +1488,104 @@ async fn test_edit_data_storage_account() {
             Some( &ptc.payer.pubkey() ),
             &[
                 &ptc.payer,
-                &unknown_wallet
+                &authority_keypair
             ],
             ptc.last_blockhash
         );
 
-        let error = ptc
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountEdited {
+            data_storage_account: dsa_addr,
+            authority_account: authority_keypair.pubkey(),
+            old_data_len: 6,
+            new_data_len: new_data.len(),
+            new_revision: 1,
+            last_editor: authority_keypair.pubkey()
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
             .banks_client
             .process_transaction(transaction)
             .await
-            .unwrap_err()
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
             .unwrap();
 
         assert_eq!(
-            error,
-            TransactionError::InstructionError(
-                0,
-                InstructionError::IncorrectAuthority
-            )
+            account_lamport_balance,
+            lamports,
+            "Invalid PDA lamport balance."
         );
-    }
-    // failure - invalid authority
 
-    // faliure - invalid seeds OR failed to find program address
-    {
-        //? Impossible to get this error
-    }
-    // faliure - invalid seeds OR failed to find program address
-}
+        assert_eq!(
+            data.len(),
+            account_data_len,
+            "Invalid data len."
+        );
 
-#[tokio::test]
-async fn test_close_data_storage_account() {
-    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
-    let mut pt = setup(&data_storage_program_id);
-    
-    //? add authority account
-    let authority_keypair = Keypair::new();
-    pt.add_account(
-        authority_keypair.pubkey(),
-        SolanaAccount::new(
-            sol_to_lamports(1.0),
-            0,
-            &SYSTEM_PROGRAM_ID
-        )
-    );
-    //? add authority account
+        let dsa_data = array_ref![ data, 0, 462 ];
+        let (
+            _,
+            _,
+            expected_last_updated,
+            _,
+            _,
+            expected_data_len,
+            _,
+            _,
+            expected_data
+        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 4, 378, 6 ];
 
-    let mut ptc = pt.start_with_context().await;
+        assert_eq!(
+            expected_data_len,
+            &u16::to_le_bytes(new_data.len() as u16),
+            "Ivnalid data len."
+        );
 
-    // success
+        assert_eq!(
+            expected_data.as_slice(),
+            new_data.as_bytes(),
+            "Invalid new data."
+        );
+
+        assert_eq!(
+            expected_last_updated,
+            &current_time.to_le_bytes(),
+            "Invalid time."
+        );
+    }
+    // success - new len == old len
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - new len < old len
     {
         //? add data storage account
         let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(100);
+        data_storage_account_label.fill(90);
 
         let (
             dsa_addr,
@@ -1656,20 +1599,16 @@ async fn test_close_data_storage_account() {
             &data_storage_program_id
         );
 
-        let account_data = vec![
-            authority_keypair
-                .pubkey()
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ true as u8 ],
-            u16::to_le_bytes(6).to_vec(),
-            (b"Solana").to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
+        let old_data = "Solana";
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+        let account_data_len = account_data.len();
 
-        let account_lamport_balance = sol_to_lamports(0.01);
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
 
         ptc.set_account(
             &dsa_addr,
@@ -1677,7 +1616,7 @@ async fn test_close_data_storage_account() {
                 SolanaAccount {
                     data: account_data,
                     owner: data_storage_program_id,
-                    lamports: account_lamport_balance,
+                    lamports: dsa_account_lamport_balance,
                     rent_epoch: Epoch::default(),
                     executable: false
                 }
@@ -1685,29 +1624,36 @@ async fn test_close_data_storage_account() {
         );
         //? add data storage account
 
-        let rent_exempt_receiver_before_tx_balance = ptc
-            .banks_client
-            .get_balance(authority_keypair.pubkey())
-            .await
-            .unwrap();
+        let current_time = 450_i64;
+        ptc
+            .set_sysvar::<Clock>(
+                &Clock {
+                    unix_timestamp: current_time,
+                    ..Clock::default()
+                }
+            );
 
-        let instruction_accounts: Vec<AccountMeta> = vec![
+        let new_data = "SOL";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
             AccountMeta::new(dsa_addr, false),
             AccountMeta::new_readonly(authority_keypair.pubkey(), true),
             AccountMeta::new(authority_keypair.pubkey(), false)
         ];
 
-        let instruction_data: &[u8] = &[ CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ];
-
-        let instuction: Instruction = Instruction {
+        let instruction = Instruction {
             program_id: data_storage_program_id,
             accounts: instruction_accounts,
             data: instruction_data.to_vec()
         };
 
         let transaction = Transaction::new_signed_with_payer(
-            &[ instuction ],
-            Some(&ptc.payer.pubkey()),
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
             &[
                 &ptc.payer,
                 &authority_keypair
@@ -1715,64 +1661,122 @@ async fn test_close_data_storage_account() {
             ptc.last_blockhash
         );
 
+        let rent_receiver_before_tx_lamport_balance = ptc
+            .banks_client
+            .get_balance(authority_keypair.pubkey())
+            .await
+            .unwrap();
+
         // validate emitted event
         let simulation_result = ptc
             .banks_client
             .simulate_transaction(transaction.clone())
             .await
             .unwrap();
-    
-        let event = Events::DataStorageAccountClosed {
+
+        let event = Events::DataStorageAccountEdited {
             data_storage_account: dsa_addr,
-            authority_account: authority_keypair.pubkey()
+            authority_account: authority_keypair.pubkey(),
+            old_data_len: old_data.len(),
+            new_data_len: new_data.len(),
+            new_revision: 1,
+            last_editor: authority_keypair.pubkey()
         };
-        let log_event = format!("Program log: {:?}", event);
-    
-        assert_eq!(
-            simulation_result
-                .simulation_details
-                .unwrap()
-                .logs
-                .contains(&log_event),
-            true,
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
             "Invalid emitted event!"
         );
-    
+
         ptc
             .banks_client
             .process_transaction(transaction)
             .await
             .unwrap();
 
-        let rent_exempt_receiver_after_tx_balance = ptc
+        let SolanaAccount { data, lamports: dsa_after_tx_lamport_balance, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let rent_receiver_after_tx_lamport_balance = ptc
             .banks_client
             .get_balance(authority_keypair.pubkey())
             .await
             .unwrap();
 
+        let extra_rent_exempt_lamports = (
+            (old_data.len() - new_data.len()) as u64 * DEFAULT_LAMPORTS_PER_BYTE_YEAR
+        ) * DEFAULT_EXEMPTION_THRESHOLD as u64;
+
         assert_eq!(
-            rent_exempt_receiver_before_tx_balance + sol_to_lamports(0.01),
-            rent_exempt_receiver_after_tx_balance,
-            "Invalid rent_exempt_receiver lamport balance."
+            dsa_account_lamport_balance - extra_rent_exempt_lamports,
+            dsa_after_tx_lamport_balance,
+            "Invalid data-storage-account's lamport balance."
         );
 
-        let dsa_account_info = ptc
-            .banks_client
-            .get_account(dsa_addr)
-            .await
-            .unwrap();
+        assert_eq!(
+            rent_receiver_before_tx_lamport_balance + extra_rent_exempt_lamports,
+            rent_receiver_after_tx_lamport_balance,
+            "Invalid rent_receiver_account's lamport balance."
+        );
 
-        if let Some(_) = dsa_account_info {
-            panic!("Account must be closed so far !");
-        };
+        let new_account_data_len = account_data_len - ( old_data.len() - new_data.len() ) ;
+        assert_eq!(
+            new_account_data_len,
+            data.len(),
+            "Invalid data-storage-account's data len."
+        );
+
+        let dsa_data = array_ref![ data, 0, 459 ];
+        let (
+            _,
+            _,
+            expected_last_updated,
+            _,
+            _,
+            expected_data_len,
+            _,
+            _,
+            expected_data
+        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 4, 378, 3 ];
+
+        assert_eq!(
+            expected_data_len,
+            &u16::to_le_bytes(new_data.len() as u16),
+            "Invalid data len."
+        );
+
+        assert_eq!(
+            expected_data.as_slice(),
+            new_data.as_bytes(),
+            "Invalid new data."
+        );
+
+        assert_eq!(
+            expected_last_updated,
+            &i64::to_le_bytes(current_time),
+            "Invalid last-updated-time."
+        );
     }
-    // success
+    // success - new len < old len
 
-    // failure - Revival Attack
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - new len > old len
     {
         //? add data storage account
         let mut data_storage_account_label: [u8; 30] = [0; 30];
-        data_storage_account_label.fill(65);
+        data_storage_account_label.fill(68);
 
         let (
             dsa_addr,
@@ -1786,20 +1790,16 @@ async fn test_close_data_storage_account() {
             &data_storage_program_id
         );
 
-        let account_data = vec![
-            authority_keypair
-                .pubkey()
-                .to_bytes()
-                .to_vec(),
-            data_storage_account_label.to_vec(),
-            i64::to_le_bytes(0).to_vec(),
-            vec![ dsa_bump ],
-            vec![ true as u8 ],
-            u16::to_le_bytes(6).to_vec(),
-            (b"Solana").to_vec()
-        ].into_iter().flatten().collect::<Vec<_>>();
+        let old_data = "Solana";
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+        let account_data_len = account_data.len();
 
-        let account_lamport_balance = sol_to_lamports(0.01);
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
 
         ptc.set_account(
             &dsa_addr,
@@ -1807,7 +1807,7 @@ async fn test_close_data_storage_account() {
                 SolanaAccount {
                     data: account_data,
                     owner: data_storage_program_id,
-                    lamports: account_lamport_balance,
+                    lamports: dsa_account_lamport_balance,
                     rent_epoch: Epoch::default(),
                     executable: false
                 }
@@ -1815,123 +1815,16166 @@ async fn test_close_data_storage_account() {
         );
         //? add data storage account
 
-        // instruction close account
-        let instruction_accounts_1: Vec<AccountMeta> = vec![
+        let current_time = 550_i64;
+        ptc
+            .set_sysvar::<Clock>(
+                &Clock {
+                    unix_timestamp: current_time,
+                    ..Clock::default()
+                }
+            );
+
+        let new_data = "PooriaGG!";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
             AccountMeta::new(dsa_addr, false),
             AccountMeta::new_readonly(authority_keypair.pubkey(), true),
-            AccountMeta::new(authority_keypair.pubkey(), false)
+            AccountMeta::new(authority_keypair.pubkey(), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
         ];
-    
-        let instruction_data_1: &[u8] = &[ CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ];
-    
-        let instuction_close_account: Instruction = Instruction {
+
+        let instruction = Instruction {
             program_id: data_storage_program_id,
-            accounts: instruction_accounts_1,
-            data: instruction_data_1.to_vec()
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
         };
 
-        // invoke instruction create new account
-        {
-            // instruction create new account
-            let instruction_accounts_2: [AccountMeta; 4] = [
-                AccountMeta::new(dsa_addr, false),
-                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
-                AccountMeta::new(ptc.payer.pubkey(), true),
-                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
-            ];
-            let data_storage_account_data = String::from("PooriaGG..");
-        
-            let instruction_data_2: &[u8] = &[
-                &[ CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
-                data_storage_account_label.as_slice(),
-                data_storage_account_data.as_bytes()
-            ].concat();
-        
-            let instruction_create_new_account = Instruction {
-                program_id: data_storage_program_id,
-                accounts: instruction_accounts_2.to_vec(),
-                data: instruction_data_2.to_vec()
-            };
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
 
-            let transaction = Transaction::new_signed_with_payer(
-                &[
-                    instuction_close_account.clone(),
-                    instruction_create_new_account
-                ],
-                Some(&ptc.payer.pubkey()),
-                &[
-                    &ptc.payer,
-                    &authority_keypair
-                ],
-                ptc.last_blockhash
-            );
+        let funding_account_before_tx_lamport_balance = ptc
+            .banks_client
+            .get_balance(authority_keypair.pubkey())
+            .await
+            .unwrap();
 
-            let error = ptc
-                .banks_client
-                .process_transaction(transaction)
-                .await
-                .unwrap_err()
-                .unwrap();
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
 
-            assert_eq!(
-                error,
-                TransactionError::InstructionError(
-                    1,
-                    SystemError::AccountAlreadyInUse.into()
-                )
-            );
-        }
-        // invoke instruction create new account
+        let event = Events::DataStorageAccountEdited {
+            data_storage_account: dsa_addr,
+            authority_account: authority_keypair.pubkey(),
+            old_data_len: old_data.len(),
+            new_data_len: new_data.len(),
+            new_revision: 1,
+            last_editor: authority_keypair.pubkey()
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
 
-        // invoke instruction edit account
-        {
-            // instruction edit account
-            let new_data = "Pooria";
-            let instruction_data_2 = &[
-                &[ EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ],
-                new_data.as_bytes()
-            ].concat();
-    
-            let instruction_accounts_2 = vec![
-                AccountMeta::new(dsa_addr, false),
-                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
-            ];
-    
-            let instruction_edit_account = Instruction {
-                program_id: data_storage_program_id,
-                accounts: instruction_accounts_2,
-                data: instruction_data_2.to_vec()
-            };
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
 
-            let transaction = Transaction::new_signed_with_payer(
-                &[
-                    instuction_close_account,
-                    instruction_edit_account
-                ],
-                Some(&ptc.payer.pubkey()),
-                &[
-                    &ptc.payer,
-                    &authority_keypair
-                ],
-                ptc.last_blockhash
-            );
+        let SolanaAccount { data, lamports: dsa_after_tx_lamport_balance, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
 
-            let error = ptc
-                .banks_client
-                .process_transaction(transaction)
-                .await
-                .unwrap_err()
-                .unwrap();
+        let funding_account_after_tx_lamport_balance = ptc
+            .banks_client
+            .get_balance(authority_keypair.pubkey())
+            .await
+            .unwrap();
 
-            assert_eq!(
-                error,
-                TransactionError::InstructionError(
-                    1,
-                    InstructionError::UninitializedAccount
-                )
-            );
-        }
-        // invoke instruction edit account
-    }
-    // failure - Revival Attack
-}
\ No newline at end of file
+        let extra_rent_exempt_lamports = (
+            (new_data.len() - old_data.len()) as u64 * DEFAULT_LAMPORTS_PER_BYTE_YEAR
+        ) * DEFAULT_EXEMPTION_THRESHOLD as u64;
+
+        assert_eq!(
+            dsa_account_lamport_balance + extra_rent_exempt_lamports,
+            dsa_after_tx_lamport_balance,
+            "Invalid data-storage-account's lamport balance."
+        );
+
+        assert_eq!(
+            funding_account_before_tx_lamport_balance - extra_rent_exempt_lamports,
+            funding_account_after_tx_lamport_balance,
+            "Invalid funding_account's lamport balance."
+        );
+
+        let new_account_data_len = account_data_len + ( new_data.len() - old_data.len() );
+        assert_eq!(
+            new_account_data_len,
+            data.len(),
+            "Invalid data-storage-account's data len."
+        );
+
+        let dsa_data = array_ref![ data, 0, 465 ];
+        let (
+            _,
+            _,
+            expected_last_updated,
+            _,
+            _,
+            expected_data_len,
+            _,
+            _,
+            expected_data
+        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 4, 378, 9 ];
+
+        assert_eq!(
+            expected_data_len,
+            &u16::to_le_bytes(new_data.len() as u16),
+            "Invalid data len."
+        );
+
+        assert_eq!(
+            expected_data.as_slice(),
+            new_data.as_bytes(),
+            "Invalid new data."
+        );
+
+        assert_eq!(
+            expected_last_updated,
+            &i64::to_le_bytes(current_time),
+            "Invalid last-updated-time."
+        );
+    }  
+    // success - new len > old len
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - immutable data storage account
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(82);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "Solana";
+        let account_data = data_storage_account_data(
+            &SYSTEM_PROGRAM_ID,
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: dsa_account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let new_data = "Pooria";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::ImmutableDataStorage as u32
+                )
+            )
+        );
+    }
+    // failure - immutable data storage account
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - authority account is not signer
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(82);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "Solana";
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: dsa_account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let new_data = "Pooria";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), false),
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::MissingRequiredSignature
+            )
+        );
+    }
+    // failure - authority account is not signer
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - invalid data-storage-account's program owner
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(82);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "Solana";
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: SYSTEM_PROGRAM_ID,
+                    lamports: dsa_account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let new_data = "Pooria";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidAccountOwner
+            )
+        );
+    }
+    // failure - invalid data-storage-account's program owner
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - account is not initialized
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(82);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "Solana";
+        let mut account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+        account_data[INIT_OFFSET] = false as u8;
+
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: dsa_account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let new_data = "Pooria";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::UninitializedAccount
+            )
+        );
+    }
+    // failure - account is not initialized
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - invalid authority
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(82);
+
+        let unknown_wallet = Keypair::new();
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "Solana";
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: dsa_account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let new_data = "Pooria";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(unknown_wallet.pubkey(), true),
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &unknown_wallet
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::IncorrectAuthority
+            )
+        );
+    }
+    // failure - invalid authority
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - truncated data storage account
+    {
+        //? add data storage account
+        let dsa_addr = Pubkey::new_unique();
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: vec![ 1; 10 ],
+                    owner: data_storage_program_id,
+                    lamports: sol_to_lamports(0.01),
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidAccountData
+            )
+        );
+    }
+    // failure - truncated data storage account
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - zero-length data storage account
+    {
+        //? add data storage account
+        let dsa_addr = Pubkey::new_unique();
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: vec![],
+                    owner: data_storage_program_id,
+                    lamports: sol_to_lamports(0.01),
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        for instruction_data in [
+            [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat(),
+            encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        ] {
+            let instruction = Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(authority_keypair.pubkey(), false)
+                ],
+                data: instruction_data
+            };
+
+            let transaction = Transaction::new_signed_with_payer(
+                &[ instruction ],
+                Some( &ptc.payer.pubkey() ),
+                &[
+                    &ptc.payer,
+                    &authority_keypair
+                ],
+                ptc.last_blockhash
+            );
+
+            let error = ptc
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap();
+
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::InvalidAccountData
+                )
+            );
+        };
+    }
+    // failure - zero-length data storage account
+
+    // failure - data storage account passed as authority
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(83);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "Solana";
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: dsa_account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let new_data = "Pooria";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(dsa_addr, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::InvalidData as u32
+                )
+            )
+        );
+    }
+    // failure - data storage account passed as authority
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - new len == max-size
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(84);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "Solana";
+        let mut account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+        // set max-size
+        account_data[MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + 4].copy_from_slice(&u32::to_le_bytes(8));
+
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: dsa_account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let new_data = "Pooria!!";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(authority_keypair.pubkey(), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let dsa_data = array_ref![ data, 0, 464 ];
+        let (
+            _,
+            _,
+            _,
+            _,
+            _,
+            expected_data_len,
+            expected_max_size,
+            _,
+            expected_data
+        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 4, 378, 8 ];
+
+        assert_eq!(
+            expected_data_len,
+            &u16::to_le_bytes(new_data.len() as u16),
+            "Invalid data len."
+        );
+
+        assert_eq!(
+            expected_max_size,
+            &u32::to_le_bytes(8),
+            "Invalid max size."
+        );
+
+        assert_eq!(
+            expected_data.as_slice(),
+            new_data.as_bytes(),
+            "Invalid new data."
+        );
+    }
+    // success - new len == max-size
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - new len > max-size
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(85);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "Solana";
+        let mut account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+        // set max-size
+        account_data[MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + 4].copy_from_slice(&u32::to_le_bytes(8));
+
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: dsa_account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let new_data = "PooriaGG!";
+        let instruction_data = &[
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data.as_bytes()
+        ].concat();
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(authority_keypair.pubkey(), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::QuotaExceeded as u32
+                )
+            )
+        );
+    }
+    // failure - new len > max-size
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - new len == 0 (shrink down to the header)
+    {
+        //? create data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(86);
+
+        let (
+            dsa_addr,
+            _
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "PooriaGG..";
+        let instruction_data: &[u8] = &[
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            old_data.as_bytes()
+        ].concat();
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+        //? create data storage account
+
+        let instruction_data = &encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR);
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(authority_keypair.pubkey(), false)
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let rent_receiver_before_tx_lamport_balance = ptc
+            .banks_client
+            .get_balance(authority_keypair.pubkey())
+            .await
+            .unwrap();
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let rent_receiver_after_tx_lamport_balance = ptc
+            .banks_client
+            .get_balance(authority_keypair.pubkey())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            HEADER_LEN,
+            "Invalid data-storage-account's data len."
+        );
+
+        assert_eq!(
+            data[DATA_LEN_OFFSET..DATA_LEN_OFFSET + 2],
+            u16::to_le_bytes(0),
+            "Invalid data len."
+        );
+
+        let rent = ptc
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN),
+            "Data-storage-account must stay rent-exempt."
+        );
+
+        assert_eq!(
+            rent_receiver_before_tx_lamport_balance + (old_data.len() as u64 * DEFAULT_LAMPORTS_PER_BYTE_YEAR * DEFAULT_EXEMPTION_THRESHOLD as u64),
+            rent_receiver_after_tx_lamport_balance,
+            "Invalid rent_receiver_account's lamport balance."
+        );
+    }
+    // success - new len == 0 (shrink down to the header)
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(68);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                data: data_storage_account_data(
+                    &authority_keypair.pubkey(),
+                    &data_storage_account_label,
+                    dsa_bump,
+                    b"Solana"
+                ),
+                owner: data_storage_program_id,
+                lamports: sol_to_lamports(0.01),
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    let mut display_label: [u8; 30] = [0; 30];
+    display_label[..7].copy_from_slice(b"Display");
+
+    // success - new display label
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                &[ EDIT_FLAG_DISPLAY_LABEL ],
+                display_label.as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data[LABEL_OFFSET..LABEL_OFFSET + 30],
+            data_storage_account_label,
+            "Seed label must not change."
+        );
+        assert_eq!(
+            data[DISPLAY_LABEL_OFFSET..DISPLAY_LABEL_OFFSET + 30],
+            display_label,
+            "Invalid display label."
+        );
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Pooria",
+            "Invalid data."
+        );
+    }
+    // success - new display label
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - keep display label (pda still validates via the seed label)
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data[LABEL_OFFSET..LABEL_OFFSET + 30],
+            data_storage_account_label,
+            "Seed label must not change."
+        );
+        assert_eq!(
+            data[DISPLAY_LABEL_OFFSET..DISPLAY_LABEL_OFFSET + 30],
+            display_label,
+            "Invalid display label."
+        );
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Solana",
+            "Invalid data."
+        );
+    }
+    // success - keep display label (pda still validates via the seed label)
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - display label is not utf-8, has control characters or an interior NUL
+    let mut control_display_label = display_label;
+    control_display_label[7] = b'\n';
+    let mut interior_nul_display_label = display_label;
+    interior_nul_display_label[8] = b'!';
+    for invalid_display_label in [ [ 0xff; 30 ], control_display_label, interior_nul_display_label ] {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                &[ EDIT_FLAG_DISPLAY_LABEL ],
+                invalid_display_label.as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::InvalidLabel as u32)
+            )
+        );
+    };
+    // failure - display label is not utf-8, has control characters or an interior NUL
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - unknown edit flag
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                &[ 16 ],
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::InvalidData as u32)
+            )
+        );
+    }
+    // failure - unknown edit flag
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - missing accounts for a grow / shrink edit
+    for (accounts, new_data, expected_error) in [
+        (
+            vec![],
+            [ 7; 200 ].as_slice(),
+            DataStorageError::MissingFundingAccount
+        ),
+        (
+            vec![ AccountMeta::new(ptc.payer.pubkey(), true) ],
+            [ 7; 200 ].as_slice(),
+            DataStorageError::MissingSystemProgram
+        ),
+        (
+            vec![],
+            b"Sol".as_slice(),
+            DataStorageError::MissingRentReceiver
+        )
+    ] {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: [
+                vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+                ],
+                accounts
+            ].concat(),
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                new_data
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(expected_error as u32)
+            )
+        );
+    }
+    // failure - missing accounts for a grow / shrink edit
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - funding account is not signer
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                [ 7; 200 ].as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::MissingRequiredSignature
+            )
+        );
+    }
+    // failure - funding account is not signer
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - edit with a new idempotency key
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                &[ EDIT_FLAG_IDEMPOTENCY_KEY ],
+                u64::to_le_bytes(7).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            u64::from_le_bytes(*array_ref![ data, IDEMPOTENCY_KEY_OFFSET, 8 ]),
+            7,
+            "Invalid idempotency key."
+        );
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Pooria",
+            "Invalid data."
+        );
+    }
+    // success - edit with a new idempotency key
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - retried edit with the same idempotency key is a no-op
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                &[ EDIT_FLAG_IDEMPOTENCY_KEY ],
+                u64::to_le_bytes(7).as_slice(),
+                b"Rustyy".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            u64::from_le_bytes(*array_ref![ data, IDEMPOTENCY_KEY_OFFSET, 8 ]),
+            7,
+            "Invalid idempotency key."
+        );
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Pooria",
+            "Invalid data."
+        );
+    }
+    // success - retried edit with the same idempotency key is a no-op
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - edit with another idempotency key
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                &[ EDIT_FLAG_IDEMPOTENCY_KEY ],
+                u64::to_le_bytes(8).as_slice(),
+                b"Rustyy".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            u64::from_le_bytes(*array_ref![ data, IDEMPOTENCY_KEY_OFFSET, 8 ]),
+            8,
+            "Invalid idempotency key."
+        );
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Rustyy",
+            "Invalid data."
+        );
+    }
+    // success - edit with another idempotency key
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - stored data length exceeds the account's data-field
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(69);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let mut account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+        account_data[DATA_LEN_OFFSET..DATA_LEN_OFFSET + 2].copy_from_slice(&u16::to_le_bytes(100));
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data.clone(),
+                    owner: data_storage_program_id,
+                    lamports: sol_to_lamports(0.01),
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::MalformedAccount as u32)
+            )
+        );
+
+        ptc
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+
+        // same length as the stored one, which would otherwise be written in place past the account's end
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                [ 1; 100 ].as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::MalformedAccount as u32)
+            )
+        );
+
+        let dsa_data = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(dsa_data, account_data, "Malformed account must not be written to.");
+    }
+    // failure - stored data length exceeds the account's data-field
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - the data-field is stored as-is, even when it starts with an edit-flags byte
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(105);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: data_storage_account_data(
+                        &authority_keypair.pubkey(),
+                        &data_storage_account_label,
+                        dsa_bump,
+                        b"Solana"
+                    ),
+                    owner: data_storage_program_id,
+                    lamports: sol_to_lamports(0.01),
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let new_data = [ &[ EDIT_FLAG_IDEMPOTENCY_KEY ], b"Solan".as_slice() ].concat();
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                new_data.as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.get(DATA_OFFSET..).unwrap(),
+            new_data.as_slice(),
+            "Invalid data."
+        );
+    }
+    // success - the data-field is stored as-is, even when it starts with an edit-flags byte
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // faliure - invalid seeds OR failed to find program address
+    {
+        //? Impossible to get this error
+    }
+    // faliure - invalid seeds OR failed to find program address
+}
+
+#[tokio::test]
+async fn test_close_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+    
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    // success
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(100);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+
+        let account_lamport_balance = rent.minimum_balance(account_data.len());
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let rent_exempt_receiver_before_tx_balance = ptc
+            .banks_client
+            .get_balance(authority_keypair.pubkey())
+            .await
+            .unwrap();
+
+        let instruction_accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(authority_keypair.pubkey(), false)
+        ];
+
+        let instruction_data: &[u8] = &encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR);
+
+        let instuction: Instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instuction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+    
+        let event = Events::DataStorageAccountClosed {
+            data_storage_account: dsa_addr,
+            authority_account: authority_keypair.pubkey()
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+    
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let rent_exempt_receiver_after_tx_balance = ptc
+            .banks_client
+            .get_balance(authority_keypair.pubkey())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            rent_exempt_receiver_before_tx_balance + account_lamport_balance,
+            rent_exempt_receiver_after_tx_balance,
+            "Invalid rent_exempt_receiver lamport balance."
+        );
+
+        let dsa_account_info = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap();
+
+        if dsa_account_info.is_some() {
+            panic!("Account must be closed so far !");
+        };
+    }
+    // success
+
+    // failure - Revival Attack
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(65);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+
+        let account_lamport_balance = rent.minimum_balance(account_data.len());
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        // instruction close account
+        let instruction_accounts_1: Vec<AccountMeta> = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(authority_keypair.pubkey(), false)
+        ];
+    
+        let instruction_data_1: &[u8] = &encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR);
+    
+        let instuction_close_account: Instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts_1,
+            data: instruction_data_1.to_vec()
+        };
+
+        // invoke instruction create new account
+        {
+            // instruction create new account
+            let instruction_accounts_2: [AccountMeta; 5] = [
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ];
+            let data_storage_account_data = String::from("PooriaGG..");
+        
+            let instruction_data_2: &[u8] = &[
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                data_storage_account_data.as_bytes()
+            ].concat();
+        
+            let instruction_create_new_account = Instruction {
+                program_id: data_storage_program_id,
+                accounts: instruction_accounts_2.to_vec(),
+                data: instruction_data_2.to_vec()
+            };
+
+            let transaction = Transaction::new_signed_with_payer(
+                &[
+                    instuction_close_account.clone(),
+                    instruction_create_new_account
+                ],
+                Some(&ptc.payer.pubkey()),
+                &[
+                    &ptc.payer,
+                    &authority_keypair
+                ],
+                ptc.last_blockhash
+            );
+
+            let error = ptc
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap();
+
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    1,
+                    SystemError::AccountAlreadyInUse.into()
+                )
+            );
+        }
+        // invoke instruction create new account
+
+        // invoke instruction edit account
+        {
+            // instruction edit account
+            let new_data = "Pooria";
+            let instruction_data_2 = &[
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                new_data.as_bytes()
+            ].concat();
+    
+            let instruction_accounts_2 = vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ];
+    
+            let instruction_edit_account = Instruction {
+                program_id: data_storage_program_id,
+                accounts: instruction_accounts_2,
+                data: instruction_data_2.to_vec()
+            };
+
+            let transaction = Transaction::new_signed_with_payer(
+                &[
+                    instuction_close_account,
+                    instruction_edit_account
+                ],
+                Some(&ptc.payer.pubkey()),
+                &[
+                    &ptc.payer,
+                    &authority_keypair
+                ],
+                ptc.last_blockhash
+            );
+
+            let error = ptc
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap();
+
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    1,
+                    InstructionError::UninitializedAccount
+                )
+            );
+        }
+        // invoke instruction edit account
+    }
+    // failure - Revival Attack
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - a closed account revived in the same transaction has it's data wiped
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(87);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"my-secret"
+        );
+        let account_data_len = account_data.len();
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: rent.minimum_balance(account_data_len),
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let instuction_close_account = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(authority_keypair.pubkey(), false)
+            ],
+            data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        // re-fund the closed account so it (and it's data) survives the transaction
+        let instruction_revive_account = transfer_lamports(
+            &ptc.payer.pubkey(),
+            &dsa_addr,
+            rent.minimum_balance(account_data_len)
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                instuction_close_account,
+                instruction_revive_account
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            account_data_len,
+            "Invalid data len."
+        );
+        assert_eq!(
+            &data[OWNER_OFFSET..OWNER_OFFSET + 32],
+            authority_keypair.pubkey().as_ref(),
+            "The authority must be kept."
+        );
+        assert!(
+            data[LABEL_OFFSET..].iter().all(|byte| *byte == 0),
+            "Closed account's data must be wiped."
+        );
+    }
+    // success - a closed account revived in the same transaction has it's data wiped
+
+    // success & failure - lamports sent directly to the pda
+    {
+        //? add data storage account
+        let data_storage_account_label: [u8; 30] = [ 68; 30 ];
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+
+        let rent_exempt_lamports = rent.minimum_balance(account_data.len());
+        let excess_lamports = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: rent_exempt_lamports + excess_lamports,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let rent_receiver = Pubkey::new_unique();
+        let excess_receiver = Pubkey::new_unique();
+
+        let close_instruction = |excess_receiver: Option<Pubkey>| {
+            let mut accounts = vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(rent_receiver, false)
+            ];
+            if let Some(excess_receiver) = excess_receiver {
+                accounts.push(AccountMeta::new(excess_receiver, false));
+            };
+
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts,
+                data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+            }
+        };
+
+        // failure - no excess-receiver
+        let transaction = Transaction::new_signed_with_payer(
+            &[ close_instruction(None) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::UnexpectedLamports as u32)
+            ),
+            "Invalid error."
+        );
+        // failure - no excess-receiver
+
+        // success - the excess goes to the excess-receiver
+        let transaction = Transaction::new_signed_with_payer(
+            &[ close_instruction(Some(excess_receiver)) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ptc.banks_client.get_balance(rent_receiver).await.unwrap(),
+            rent_exempt_lamports,
+            "Invalid rent-receiver lamport balance."
+        );
+        assert_eq!(
+            ptc.banks_client.get_balance(excess_receiver).await.unwrap(),
+            excess_lamports,
+            "Invalid excess-receiver lamport balance."
+        );
+        assert!(
+            ptc.banks_client.get_account(dsa_addr).await.unwrap().is_none(),
+            "Account must be closed so far !"
+        );
+        // success - the excess goes to the excess-receiver
+    }
+    // success & failure - lamports sent directly to the pda
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - data storage account passed as authority
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(66);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let old_data = "Solana";
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            old_data.as_bytes()
+        );
+
+        let dsa_account_lamport_balance = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: dsa_account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let instruction_data: &[u8] = &encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR);
+
+        let instruction_accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(dsa_addr, false),
+            AccountMeta::new(authority_keypair.pubkey(), false)
+        ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::InvalidData as u32
+                )
+            )
+        );
+    }
+    // failure - data storage account passed as authority
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - invalid authority
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(67);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: sol_to_lamports(0.01),
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let unknown_wallet = Keypair::new();
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(unknown_wallet.pubkey(), true),
+                AccountMeta::new(unknown_wallet.pubkey(), false)
+            ],
+            data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some( &ptc.payer.pubkey() ),
+            &[
+                &ptc.payer,
+                &unknown_wallet
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::IncorrectAuthority
+            )
+        );
+    }
+    // failure - invalid authority
+}
+#[tokio::test]
+#[cfg_attr(not(feature = "test-sbf"), ignore = "program logs are only collected by the sbf runtime")]
+async fn test_log_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let pt = setup(&data_storage_program_id);
+
+    let authority = Pubkey::new_unique();
+
+    let mut ptc = pt.start_with_context().await;
+
+    // success
+    {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label[..6].copy_from_slice(b"config");
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority.to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let mut account_data = data_storage_account_data(
+            &authority,
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+        // set last-updated
+        account_data[LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + 8].copy_from_slice(&i64::to_le_bytes(1_700_000_000));
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: sol_to_lamports(0.01),
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(dsa_addr, false)
+            ],
+            data: encode_instruction_discriminator(LOG_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+
+        let log_line = format!(
+            "Program log: dsa authority={} label=config len=6 updated=1700000000 edited=false",
+            authority
+        );
+
+        assert!(
+            simulation_result
+                .simulation_details
+                .unwrap()
+                .logs
+                .contains(&log_line),
+            "Invalid log line!"
+        );
+    }
+    // success
+}
+
+#[tokio::test]
+async fn test_duplicate_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    //? create source data storage account
+    let mut source_account_label: [u8; 30] = [0; 30];
+    source_account_label.fill(65);
+
+    let (
+        source_dsa_addr,
+        _
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &source_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let source_data = "PooriaGG..";
+    {
+        let instruction_data: &[u8] = &[
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            source_account_label.as_slice(),
+            u32::to_le_bytes(64).as_slice(),
+            i64::to_le_bytes(0).as_slice(),
+            source_data.as_bytes()
+        ].concat();
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(source_dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: instruction_data.to_vec()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+    }
+    //? create source data storage account
+
+    let mut new_account_label: [u8; 30] = [0; 30];
+    new_account_label.fill(66);
+
+    let (
+        new_dsa_addr,
+        new_dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &new_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let instruction_data: &[u8] = &[
+        encode_instruction_discriminator(DUPLICATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+        new_account_label.as_slice()
+    ].concat();
+
+    let instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(source_dsa_addr, false),
+            AccountMeta::new(new_dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: instruction_data.to_vec()
+    };
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&instruction),
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountDuplicated {
+            source_data_storage_account: source_dsa_addr,
+            data_storage_account: new_dsa_addr,
+            account_label: new_account_label
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { owner, data, lamports, .. } = ptc
+            .banks_client
+            .get_account(new_dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            owner,
+            data_storage_program_id,
+            "Invalid data_storage_account's owner."
+        );
+
+        let rent = ptc
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN + source_data.len()),
+            "Invalid data_storage_account's lamport balance."
+        );
+
+        let dsa_data = array_ref![ data, 0, 466 ];
+        let (
+            expected_authority,
+            expected_label,
+            expected_last_updated,
+            expected_bump,
+            expected_is_initialize,
+            expected_data_length,
+            expected_max_size,
+            _,
+            expected_data
+        ) = array_refs![ dsa_data, 32, 30, 8, 1, 1, 2, 4, 378, 10 ];
+
+        assert_eq!(
+            *expected_authority,
+            authority_keypair.pubkey().to_bytes(),
+            "Invalid expected authority."
+        );
+        assert_eq!(
+            *expected_label,
+            new_account_label,
+            "Invalid expected label."
+        );
+        assert_eq!(
+            *expected_last_updated,
+            [ 0u8; 8 ],
+            "Invalid expected last_updated."
+        );
+        assert_eq!(
+            u8::from_le_bytes(*expected_bump),
+            new_dsa_bump,
+            "Invalid expected bump."
+        );
+        assert_eq!(
+            u8::from_le_bytes(*expected_is_initialize),
+            1u8,
+            "Invalid expected is_initialized flag."
+        );
+        assert_eq!(
+            u16::from_le_bytes(*expected_data_length),
+            source_data.len() as u16,
+            "Invalid expected data_length."
+        );
+        assert_eq!(
+            u32::from_le_bytes(*expected_max_size),
+            64u32,
+            "Invalid expected max_size."
+        );
+        assert_eq!(
+            expected_data.as_slice(),
+            source_data.as_bytes(),
+            "Invalid expected data."
+        );
+    }
+    // success
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - destination account already exists
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::AccountAlreadyInitialized
+            )
+        );
+    }
+    // failure - destination account already exists
+}
+
+#[tokio::test]
+async fn test_reserve_capacity_and_append_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(82);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    // success - reserve capacity
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: [
+                encode_instruction_discriminator(RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                u16::to_le_bytes(10).as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountCapacityReserved {
+            data_storage_account: dsa_addr,
+            added_capacity: 10,
+            total_capacity: 16
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            HEADER_LEN + 16,
+            "Invalid data_storage_account's size."
+        );
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN + 16),
+            "Invalid data_storage_account's lamport balance."
+        );
+        assert_eq!(
+            u16::from_le_bytes(*array_ref![ data, DATA_LEN_OFFSET, 2 ]),
+            6u16,
+            "Data length must not change when reserving capacity."
+        );
+        assert!(
+            data[DATA_OFFSET + 6..].iter().all(|byte| *byte == 0),
+            "Reserved capacity must be zeroed."
+        );
+    }
+    // success - reserve capacity
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - append within the reserved capacity (no funding account)
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Rust".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            HEADER_LEN + 16,
+            "Account must not be reallocated within the reserved capacity."
+        );
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN + 16),
+            "Invalid data_storage_account's lamport balance."
+        );
+        assert_eq!(
+            u16::from_le_bytes(*array_ref![ data, DATA_LEN_OFFSET, 2 ]),
+            10u16,
+            "Invalid expected data_length."
+        );
+        assert_eq!(
+            &data[DATA_OFFSET..DATA_OFFSET + 10],
+            b"SolanaRust",
+            "Invalid expected data."
+        );
+    }
+    // success - append within the reserved capacity (no funding account)
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - append beyond the reserved capacity without a funding account
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"ProgramRust".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::NotEnoughAccountKeys
+            )
+        );
+    }
+    // failure - append beyond the reserved capacity without a funding account
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - reserved capacity exceeds the quota
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: [
+                encode_instruction_discriminator(RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                u16::MAX.to_le_bytes().as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::QuotaExceeded as u32)
+            )
+        );
+    }
+    // failure - reserved capacity exceeds the quota
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - account would grow past solana's 10 MiB limit
+    {
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(77);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        // an account with an unbounded max-size, whose capacity has been reserved up to 5 bytes below the limit
+        let mut account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b""
+        );
+        account_data[MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        account_data.resize(MAX_PERMITTED_DATA_LENGTH as usize - 5, 0);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    lamports: rent.minimum_balance(account_data.len()),
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: [
+                encode_instruction_discriminator(RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                u16::to_le_bytes(10).as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::DataTooLarge as u32)
+            )
+        );
+    }
+    // failure - account would grow past solana's 10 MiB limit
+}
+
+#[tokio::test]
+async fn test_lock_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let lock_holder_keypair = Keypair::new();
+
+    let mut ptc = pt.start_with_context().await;
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(76);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                data: data_storage_account_data(
+                    &authority_keypair.pubkey(),
+                    &data_storage_account_label,
+                    dsa_bump,
+                    b"Solana"
+                ),
+                owner: data_storage_program_id,
+                lamports: sol_to_lamports(0.01),
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    ptc
+        .set_sysvar::<Clock>(
+            &Clock {
+                unix_timestamp: 100,
+                ..Clock::default()
+            }
+        );
+
+    // success - authority locks the account
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(LOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                lock_holder_keypair.pubkey().to_bytes().as_slice(),
+                i64::to_le_bytes(200).as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountLocked {
+            data_storage_account: dsa_addr,
+            lock_holder: lock_holder_keypair.pubkey(),
+            lock_expiry: 200
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let (
+            lock_holder,
+            lock_expiry
+        ) = array_refs![ array_ref![ data, LOCK_HOLDER_OFFSET, 40 ], 32, 8 ];
+
+        assert_eq!(
+            *lock_holder,
+            lock_holder_keypair.pubkey().to_bytes(),
+            "Invalid expected lock_holder."
+        );
+        assert_eq!(
+            i64::from_le_bytes(*lock_expiry),
+            200,
+            "Invalid expected lock_expiry."
+        );
+    }
+    // success - authority locks the account
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - authority edits a locked account
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::AccountLocked as u32)
+            )
+        );
+    }
+    // failure - authority edits a locked account
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - lock holder edits a locked account
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(lock_holder_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &lock_holder_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Pooria",
+            "Invalid expected data."
+        );
+    }
+    // success - lock holder edits a locked account
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - authority closes a locked account
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(authority_keypair.pubkey(), false)
+            ],
+            data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::AccountLocked as u32)
+            )
+        );
+    }
+    // failure - authority closes a locked account
+
+    ptc
+        .set_sysvar::<Clock>(
+            &Clock {
+                unix_timestamp: 200,
+                ..Clock::default()
+            }
+        );
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - lock holder edits after the lock expired
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(lock_holder_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &lock_holder_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::IncorrectAuthority
+            )
+        );
+    }
+    // failure - lock holder edits after the lock expired
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - authority edits after the lock expired
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Solana",
+            "Invalid expected data."
+        );
+    }
+    // success - authority edits after the lock expired
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - lock holder unlocks the account
+    {
+        let lock_instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(LOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                lock_holder_keypair.pubkey().to_bytes().as_slice(),
+                i64::to_le_bytes(300).as_slice()
+            ].concat()
+        };
+        let unlock_instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(lock_holder_keypair.pubkey(), true)
+            ],
+            data: encode_instruction_discriminator(UNLOCK_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                lock_instruction,
+                unlock_instruction
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair,
+                &lock_holder_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            *array_ref![ data, LOCK_HOLDER_OFFSET, 40 ],
+            [ 0u8; 40 ],
+            "Lock must be cleared."
+        );
+    }
+    // success - lock holder unlocks the account
+}
+
+#[tokio::test]
+async fn test_self_test() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let pt = setup(&data_storage_program_id);
+
+    let ptc = pt.start_with_context().await;
+
+    let instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![],
+        data: encode_instruction_discriminator(SELF_TEST_INSTRUCTION_DISCRIMINATOR)
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ instruction ],
+        Some(&ptc.payer.pubkey()),
+        &[ &ptc.payer ],
+        ptc.last_blockhash
+    );
+
+    let simulation_result = ptc
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        simulation_result.result,
+        Some(Ok(())),
+        "Self-test failed!"
+    );
+
+    // program logs are only collected by the sbf runtime
+    #[cfg(feature = "test-sbf")]
+    assert!(
+        simulation_result
+            .simulation_details
+            .unwrap()
+            .logs
+            .contains(&String::from("Program log: Self-test passed. ✅")),
+        "Invalid log line!"
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_data_storage_account_authority() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let new_authority = Pubkey::new_unique();
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(84);
+
+    // success
+    {
+        //? add data storage account
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+        let account_lamport_balance = rent.minimum_balance(account_data.len());
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: account_lamport_balance,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let (
+            new_dsa_addr,
+            new_dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                new_authority.to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new_readonly(new_authority, false),
+                AccountMeta::new(new_dsa_addr, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: encode_instruction_discriminator(TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountAuthorityTransferred {
+            old_data_storage_account: dsa_addr,
+            new_data_storage_account: new_dsa_addr,
+            old_authority_account: authority_keypair.pubkey(),
+            new_authority_account: new_authority
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let old_dsa_account = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap();
+
+        assert!(
+            old_dsa_account.is_none(),
+            "Old data_storage_account must be closed."
+        );
+
+        let SolanaAccount { owner, data, lamports, .. } = ptc
+            .banks_client
+            .get_account(new_dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            owner,
+            data_storage_program_id,
+            "Invalid data_storage_account's owner."
+        );
+        assert_eq!(
+            lamports,
+            account_lamport_balance,
+            "Invalid data_storage_account's lamport balance."
+        );
+        assert_eq!(
+            data,
+            data_storage_account_data(
+                &new_authority,
+                &data_storage_account_label,
+                new_dsa_bump,
+                b"Solana"
+            ),
+            "Invalid data_storage_account's data."
+        );
+    }
+    // success
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - immutable data storage account
+    {
+        //? add data storage account
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                SYSTEM_PROGRAM_ID.to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let account_data = data_storage_account_data(
+            &SYSTEM_PROGRAM_ID,
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    lamports: rent.minimum_balance(account_data.len()),
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let (
+            new_dsa_addr,
+            _
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                new_authority.to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(new_authority, false),
+                AccountMeta::new(new_dsa_addr, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: encode_instruction_discriminator(TRANSFER_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::ImmutableDataStorage as u32)
+            )
+        );
+    }
+    // failure - immutable data storage account
+}
+
+#[tokio::test]
+async fn test_seal_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(83);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    let (
+        sealed_dsa_addr,
+        sealed_dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            SYSTEM_PROGRAM_ID.to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    // success
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(sealed_dsa_addr, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: encode_instruction_discriminator(SEAL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountSealed {
+            old_data_storage_account: dsa_addr,
+            data_storage_account: sealed_dsa_addr,
+            authority_account: authority_keypair.pubkey()
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(sealed_dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data,
+            data_storage_account_data(
+                &SYSTEM_PROGRAM_ID,
+                &data_storage_account_label,
+                sealed_dsa_bump,
+                b"Solana"
+            ),
+            "Invalid sealed data_storage_account's data."
+        );
+    }
+    // success
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - edit a sealed data storage account
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(sealed_dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::ImmutableDataStorage as u32)
+            )
+        );
+    }
+    // failure - edit a sealed data storage account
+}
+
+#[tokio::test]
+async fn test_compare_and_close_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(67);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    // lamports sent directly to the pda
+    let rent_exempt_lamports = rent.minimum_balance(account_data.len());
+    let excess_lamports = sol_to_lamports(0.01);
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                data: account_data,
+                owner: data_storage_program_id,
+                lamports: rent_exempt_lamports + excess_lamports,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    // failure - data has changed since it was read
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(authority_keypair.pubkey(), false)
+            ],
+            data: [
+                encode_instruction_discriminator(COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                keccak_hash(b"Pooria").to_bytes().as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::ConcurrentModification as u32)
+            )
+        );
+    }
+    // failure - data has changed since it was read
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    let rent_receiver = Pubkey::new_unique();
+    let excess_receiver = Pubkey::new_unique();
+
+    // failure - no excess-receiver
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(rent_receiver, false)
+            ],
+            data: [
+                encode_instruction_discriminator(COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                keccak_hash(b"Solana").to_bytes().as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::UnexpectedLamports as u32)
+            )
+        );
+    }
+    // failure - no excess-receiver
+
+    // success - data matches the expected hash, the excess goes to the excess-receiver
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(rent_receiver, false),
+                AccountMeta::new(excess_receiver, false)
+            ],
+            data: [
+                encode_instruction_discriminator(COMPARE_AND_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                keccak_hash(b"Solana").to_bytes().as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let dsa_account = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap();
+
+        assert!(
+            dsa_account.is_none(),
+            "Data storage account must be closed."
+        );
+        assert_eq!(
+            ptc.banks_client.get_balance(rent_receiver).await.unwrap(),
+            rent_exempt_lamports,
+            "Invalid rent-receiver lamport balance."
+        );
+        assert_eq!(
+            ptc.banks_client.get_balance(excess_receiver).await.unwrap(),
+            excess_lamports,
+            "Invalid excess-receiver lamport balance."
+        );
+    }
+    // success - data matches the expected hash, the excess goes to the excess-receiver
+}
+
+#[tokio::test]
+async fn test_truncate_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    //? add rent receiver account
+    let rent_receiver = Pubkey::new_unique();
+    pt.add_account(
+        rent_receiver,
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add rent receiver account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(84);
+
+    //? add data storage accounts
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+
+    let (
+        immutable_dsa_addr,
+        immutable_dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            SYSTEM_PROGRAM_ID.to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &SYSTEM_PROGRAM_ID,
+        &data_storage_account_label,
+        immutable_dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &immutable_dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage accounts
+
+    ptc
+        .set_sysvar::<Clock>(
+            &Clock {
+                unix_timestamp: 500,
+                ..Clock::default()
+            }
+        );
+
+    // success - truncate to the same length
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(rent_receiver, false)
+            ],
+            data: [
+                encode_instruction_discriminator(TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                u16::to_le_bytes(6).as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            i64::from_le_bytes(*array_ref![ data, LAST_UPDATED_OFFSET, 8 ]),
+            500,
+            "Invalid last_updated."
+        );
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Solana",
+            "Data must not change."
+        );
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN + 6),
+            "Invalid data_storage_account's lamport balance."
+        );
+    }
+    // success - truncate to the same length
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - target length is bigger than the current length
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(rent_receiver, false)
+            ],
+            data: [
+                encode_instruction_discriminator(TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                u16::to_le_bytes(7).as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::InvalidData as u32)
+            )
+        );
+    }
+    // failure - target length is bigger than the current length
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - immutable data storage account
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(immutable_dsa_addr, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(rent_receiver, false)
+            ],
+            data: [
+                encode_instruction_discriminator(TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                u16::to_le_bytes(0).as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::ImmutableDataStorage as u32)
+            )
+        );
+    }
+    // failure - immutable data storage account
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - truncate to zero
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(rent_receiver, false)
+            ],
+            data: [
+                encode_instruction_discriminator(TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                u16::to_le_bytes(0).as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            HEADER_LEN,
+            "Invalid data_storage_account's size."
+        );
+        assert_eq!(
+            u16::from_le_bytes(*array_ref![ data, DATA_LEN_OFFSET, 2 ]),
+            0,
+            "Invalid data length."
+        );
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN),
+            "Data-storage-account must stay rent-exempt."
+        );
+
+        let rent_receiver_lamport_balance = ptc
+            .banks_client
+            .get_balance(rent_receiver)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            rent_receiver_lamport_balance,
+            sol_to_lamports(1.0) + rent.minimum_balance(HEADER_LEN + 6) - rent.minimum_balance(HEADER_LEN),
+            "Invalid rent_receiver_account's lamport balance."
+        );
+    }
+    // success - truncate to zero
+}
+
+#[tokio::test]
+async fn test_patch_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(80);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    ptc
+        .set_sysvar::<Clock>(
+            &Clock {
+                unix_timestamp: 700,
+                ..Clock::default()
+            }
+        );
+
+    // success - patch the first byte, a middle region and the final byte
+    {
+        let patches: [(u16, &[u8], &[u8]); 3] = [
+            (0, b"s", b"solana"),
+            (2, b"LA", b"soLAna"),
+            (5, b"A", b"soLAnA")
+        ];
+
+        for (offset, patch, expected_data) in patches {
+            let instruction = Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+                ],
+                data: [
+                    encode_instruction_discriminator(PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    u16::to_le_bytes(offset).as_slice(),
+                    patch
+                ].concat()
+            };
+
+            let transaction = Transaction::new_signed_with_payer(
+                &[ instruction ],
+                Some(&ptc.payer.pubkey()),
+                &[
+                    &ptc.payer,
+                    &authority_keypair
+                ],
+                ptc.last_blockhash
+            );
+
+            // validate emitted event
+            let simulation_result = ptc
+                .banks_client
+                .simulate_transaction(transaction.clone())
+                .await
+                .unwrap();
+
+            let event = Events::DataStorageAccountPatched {
+                data_storage_account: dsa_addr,
+                authority_account: authority_keypair.pubkey(),
+                offset: offset as usize,
+                len: patch.len()
+            };
+            assert!(
+                emitted_events(
+                    &simulation_result
+                        .simulation_details
+                        .unwrap()
+                        .logs
+                ).contains(&event),
+                "Invalid emitted event!"
+            );
+
+            ptc
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap();
+
+            let SolanaAccount { data, lamports, .. } = ptc
+                .banks_client
+                .get_account(dsa_addr)
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(
+                &data[DATA_OFFSET..],
+                expected_data,
+                "Invalid patched data."
+            );
+            assert_eq!(
+                u16::from_le_bytes(*array_ref![ data, DATA_LEN_OFFSET, 2 ]),
+                6u16,
+                "Data length must not change when patching."
+            );
+            assert_eq!(
+                i64::from_le_bytes(*array_ref![ data, LAST_UPDATED_OFFSET, 8 ]),
+                700,
+                "Invalid last_updated."
+            );
+            assert_eq!(
+                lamports,
+                rent.minimum_balance(HEADER_LEN + 6),
+                "Invalid data_storage_account's lamport balance."
+            );
+        };
+    }
+    // success - patch the first byte, a middle region and the final byte
+
+    // failure - patch runs past the current data-field length
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                u16::to_le_bytes(5).as_slice(),
+                b"NA"
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::InvalidData as u32)
+            ),
+            "Patches past the data-field's length must be rejected."
+        );
+    }
+    // failure - patch runs past the current data-field length
+}
+
+#[tokio::test]
+async fn test_funding_ledger() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority & second funder accounts
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+
+    let second_funder_keypair = Keypair::new();
+    pt.add_account(
+        second_funder_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority & second funder accounts
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(70);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    let (funding_ledger_addr, _) = Pubkey::find_program_address(
+        &[
+            FUNDING_LEDGER_SEED_PREFIX,
+            dsa_addr.as_ref()
+        ],
+        &data_storage_program_id
+    );
+
+    // fail - invalid funding-ledger address
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(Pubkey::new_unique(), false)
+            ],
+            data: [
+                encode_instruction_discriminator(RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                u16::to_le_bytes(10).as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(0, InstructionError::InvalidSeeds),
+            "Invalid funding-ledger address must be rejected."
+        );
+    }
+    // fail - invalid funding-ledger address
+
+    // success - two funders grow the account, each grow is appended to the ledger
+    {
+        let funders = [
+            (&ptc.payer.insecure_clone(), 10u16),
+            (&second_funder_keypair, 20u16)
+        ];
+
+        for (funder_keypair, additional_capacity) in funders {
+            let instruction = Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(funder_keypair.pubkey(), true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new(funding_ledger_addr, false)
+                ],
+                data: [
+                    encode_instruction_discriminator(RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    u16::to_le_bytes(additional_capacity).as_slice()
+                ].concat()
+            };
+
+            let transaction = Transaction::new_signed_with_payer(
+                &[ instruction ],
+                Some(&ptc.payer.pubkey()),
+                &[
+                    &ptc.payer,
+                    &authority_keypair,
+                    funder_keypair
+                ],
+                ptc.last_blockhash
+            );
+
+            ptc
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap();
+        };
+
+        let SolanaAccount { data, lamports, owner, .. } = ptc
+            .banks_client
+            .get_account(funding_ledger_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            owner,
+            data_storage_program_id,
+            "Invalid funding-ledger's owner."
+        );
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(2 * FUNDING_LEDGER_ENTRY_LEN),
+            "Invalid funding-ledger's lamport balance."
+        );
+        assert_eq!(
+            read_funding_ledger(&data).unwrap(),
+            vec![
+                (
+                    ptc.payer.pubkey(),
+                    rent.minimum_balance(HEADER_LEN + 16) - rent.minimum_balance(HEADER_LEN + 6)
+                ),
+                (
+                    second_funder_keypair.pubkey(),
+                    rent.minimum_balance(HEADER_LEN + 36) - rent.minimum_balance(HEADER_LEN + 16)
+                )
+            ],
+            "Invalid funding-ledger's entries."
+        );
+    }
+    // success - two funders grow the account, each grow is appended to the ledger
+
+    // fail - truncated ledger data
+    {
+        assert_eq!(
+            read_funding_ledger(&[ 0; FUNDING_LEDGER_ENTRY_LEN + 1 ]).unwrap_err(),
+            ProgramError::InvalidAccountData,
+            "Partial funding-ledger entries must be rejected."
+        );
+    }
+    // fail - truncated ledger data
+}
+
+// seed of the caller program's pda that 'cpi_caller_process_instruction' signs for (e.g. a program-owned funder or authority)
+const CPI_CALLER_SIGNER_SEED: &[u8] = b"signer";
+
+// forwards it's instruction-data to the data-storage program (accounts[0]) with the rest of the accounts, signing for it's
+// 'CPI_CALLER_SIGNER_SEED' pda when it's passed
+fn cpi_caller_process_instruction(
+    program_id: &Pubkey,
+    accounts_info: &[AccountInfo],
+    instruction_data: &[u8]
+) -> ProgramResult {
+    let (
+        data_storage_program_account_info,
+        accounts_info
+    ) = accounts_info.split_first().unwrap();
+
+    let (
+        signer_addr,
+        signer_bump
+    ) = Pubkey::find_program_address(&[ CPI_CALLER_SIGNER_SEED ], program_id);
+
+    invoke_signed(
+        &Instruction {
+            program_id: *data_storage_program_account_info.key,
+            accounts: accounts_info
+                .iter()
+                .map(|account_info| AccountMeta {
+                    pubkey: *account_info.key,
+                    is_signer: account_info.is_signer || *account_info.key == signer_addr,
+                    is_writable: account_info.is_writable
+                })
+                .collect(),
+            data: instruction_data.to_vec()
+        },
+        accounts_info,
+        &[ &[ CPI_CALLER_SIGNER_SEED, &[ signer_bump ] ] ]
+    )
+}
+
+#[tokio::test]
+async fn test_cpi_allowlist() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let trusted_program_id = Pubkey::new_from_array([2; 32]);
+    let untrusted_program_id = Pubkey::new_from_array([3; 32]);
+
+    let mut pt = setup(&data_storage_program_id);
+    pt.add_program(
+        "trusted_program",
+        trusted_program_id,
+        processor!(cpi_caller_process_instruction)
+    );
+    pt.add_program(
+        "untrusted_program",
+        untrusted_program_id,
+        processor!(cpi_caller_process_instruction)
+    );
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(65);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    let edit_instruction = |program_id: Pubkey, new_data: &[u8]| {
+        let mut accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new_readonly(INSTRUCTIONS_SYSVAR_ID, false)
+        ];
+        if program_id != data_storage_program_id {
+            accounts.insert(0, AccountMeta::new_readonly(data_storage_program_id, false));
+        };
+
+        Instruction {
+            program_id,
+            accounts,
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                new_data
+            ].concat()
+        }
+    };
+
+    // failure - the program itself can't be allowed
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_program_id.as_ref()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::InvalidData as u32)
+            ),
+            "The data-storage program can't be in the cpi-allowlist."
+        );
+    }
+    // failure - the program itself can't be allowed
+
+    // success - set cpi-allowlist
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                trusted_program_id.as_ref()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountCpiAllowlistSet {
+            data_storage_account: dsa_addr,
+            cpi_allowlist: [
+                trusted_program_id,
+                Pubkey::default(),
+                Pubkey::default()
+            ]
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            array_ref![ data, CPI_ALLOWLIST_OFFSET, 96 ],
+            &[
+                trusted_program_id.to_bytes(),
+                [ 0; 32 ],
+                [ 0; 32 ]
+            ].concat()[..],
+            "Invalid cpi-allowlist."
+        );
+    }
+    // success - set cpi-allowlist
+
+    // failure - direct edit
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ edit_instruction(data_storage_program_id, b"Direct") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::UnauthorizedCaller as u32)
+            ),
+            "Direct edits must be rejected."
+        );
+    }
+    // failure - direct edit
+
+    // failure - edit via a program that isn't allowed
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ edit_instruction(untrusted_program_id, b"Hacked") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::UnauthorizedCaller as u32)
+            ),
+            "Edits via programs outside the cpi-allowlist must be rejected."
+        );
+    }
+    // failure - edit via a program that isn't allowed
+
+    // success - edit via an allowed program
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ edit_instruction(trusted_program_id, b"Sealed") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Sealed",
+            "Invalid data."
+        );
+    }
+    // success - edit via an allowed program
+
+    // success - clear cpi-allowlist, direct edits work again
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: encode_instruction_discriminator(SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                instruction,
+                edit_instruction(data_storage_program_id, b"Direct")
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Direct",
+            "Invalid data."
+        );
+    }
+    // success - clear cpi-allowlist, direct edits work again
+}
+
+#[tokio::test]
+async fn test_realloc_increase_limit() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(71);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    let reserve_instruction = |additional_capacity: u16| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ],
+        data: [
+            encode_instruction_discriminator(RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            u16::to_le_bytes(additional_capacity).as_slice()
+        ].concat()
+    };
+
+    // failure - grow by more than 10 KiB
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ reserve_instruction(MAX_PERMITTED_DATA_INCREASE as u16 + 1) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::ReallocTooLarge as u32)
+            ),
+            "Growing by more than 10 KiB must be rejected."
+        );
+    }
+    // failure - grow by more than 10 KiB
+
+    // success - grow by exactly 10 KiB
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ reserve_instruction(MAX_PERMITTED_DATA_INCREASE as u16) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            HEADER_LEN + 6 + MAX_PERMITTED_DATA_INCREASE,
+            "Invalid data_storage_account's size."
+        );
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN + 6 + MAX_PERMITTED_DATA_INCREASE),
+            "Invalid data_storage_account's lamport balance."
+        );
+    }
+    // success - grow by exactly 10 KiB
+}
+
+#[tokio::test]
+async fn test_has_been_edited() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(72);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    assert!(
+        !has_been_edited(&account_data).unwrap(),
+        "New accounts must not be marked as edited."
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    // an edit at unix-timestamp '0' leaves 'last-updated' at '0'
+    ptc
+        .set_sysvar::<Clock>(
+            &Clock {
+                unix_timestamp: 0,
+                ..Clock::default()
+            }
+        );
+
+    // success - the first edit flips the flag
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            i64::from_le_bytes(*array_ref![ data, LAST_UPDATED_OFFSET, 8 ]),
+            0,
+            "Invalid last_updated."
+        );
+        assert!(
+            has_been_edited(&data).unwrap(),
+            "Edited accounts must be marked as edited."
+        );
+    }
+    // success - the first edit flips the flag
+
+    // failure - account data too short
+    {
+        assert_eq!(
+            has_been_edited(&[ 0; 10 ]).unwrap_err(),
+            ProgramError::InvalidAccountData,
+            "Short account data must be rejected."
+        );
+    }
+    // failure - account data too short
+}
+
+#[tokio::test]
+async fn test_revision() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(82);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    // success - sequential edits produce revisions 1 and 2
+    for (expected_revision, new_data) in [ (0u32, b"Pooria"), (1u32, b"Sol...") ] {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                &[ EDIT_FLAG_EXPECTED_REVISION ],
+                u32::to_le_bytes(expected_revision).as_slice(),
+                new_data.as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            u32::from_le_bytes(*array_ref![ data, REVISION_OFFSET, 4 ]),
+            expected_revision + 1,
+            "Invalid revision."
+        );
+        assert_eq!(
+            data.get(DATA_OFFSET..).unwrap(),
+            new_data.as_slice(),
+            "Invalid data."
+        );
+    }
+    // success - sequential edits produce revisions 1 and 2
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - stale expected-revision
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                &[ EDIT_FLAG_EXPECTED_REVISION ],
+                u32::to_le_bytes(1).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::RevisionMismatch as u32)
+            )
+        );
+    }
+    // failure - stale expected-revision
+}
+
+#[tokio::test]
+async fn test_repair_data_storage_account_authority() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account, with it's authority corrupted to a random on-curve key
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(89);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let corrupted_authority_keypair = Keypair::new();
+    let account_data = data_storage_account_data(
+        &corrupted_authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account, with it's authority corrupted to a random on-curve key
+
+    let repair_instruction = |original_authority: Pubkey| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(original_authority, true)
+        ],
+        data: encode_instruction_discriminator(REPAIR_DATA_STORAGE_ACCOUNT_AUTHORITY_INSTRUCTION_DISCRIMINATOR)
+    };
+
+    // failure - claimed authority doesn't derive the pda
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ repair_instruction(corrupted_authority_keypair.pubkey()) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &corrupted_authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(0, InstructionError::InvalidSeeds),
+            "Only the original authority can be restored."
+        );
+    }
+    // failure - claimed authority doesn't derive the pda
+
+    // success - repair the authority
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ repair_instruction(authority_keypair.pubkey()) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountAuthorityRepaired {
+            data_storage_account: dsa_addr,
+            authority_account: authority_keypair.pubkey()
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            array_ref![ data, OWNER_OFFSET, 32 ],
+            &authority_keypair.pubkey().to_bytes(),
+            "Invalid repaired authority."
+        );
+    }
+    // success - repair the authority
+
+    // success - the repaired account can be edited again
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+    }
+    // success - the repaired account can be edited again
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - nothing to repair
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ repair_instruction(authority_keypair.pubkey()) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::InvalidData as u32)
+            ),
+            "Healthy accounts can't be repaired."
+        );
+    }
+    // failure - nothing to repair
+}
+
+#[tokio::test]
+async fn test_is_data_storage_account_immutable() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let pt = setup(&data_storage_program_id);
+
+    let mut ptc = pt.start_with_context().await;
+
+    for (authority, expected_return_data) in [
+        (Pubkey::new_unique(), false as u8),
+        (SYSTEM_PROGRAM_ID, true as u8)
+    ] {
+        //? add data storage account
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(73);
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                DATA_STORAGE_SEED_PREFIX,
+                authority.to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let account_data = data_storage_account_data(
+            &authority,
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: sol_to_lamports(0.01),
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+        //? add data storage account
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(dsa_addr, false)
+            ],
+            data: encode_instruction_discriminator(IS_DATA_STORAGE_ACCOUNT_IMMUTABLE_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert!(
+            simulation_result
+                .result
+                .unwrap()
+                .is_ok(),
+            "Query must not fail."
+        );
+
+        let return_data = simulation_result
+            .simulation_details
+            .unwrap()
+            .return_data
+            .unwrap();
+
+        assert_eq!(
+            return_data.program_id,
+            data_storage_program_id,
+            "Invalid return-data program."
+        );
+        assert_eq!(
+            return_data.data,
+            vec![ expected_return_data ],
+            "Invalid return-data."
+        );
+    };
+}
+
+#[tokio::test]
+async fn test_factory_create_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let pt = setup(&data_storage_program_id);
+
+    // the user never signs, the factory (payer) funds & signs
+    let user_keypair = Keypair::new();
+
+    let mut ptc = pt.start_with_context().await;
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(70);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            user_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    // failure - a regular create still needs the authority's signature
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(user_keypair.pubkey(), false),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::MissingRequiredSignature
+            )
+        );
+    }
+    // failure - a regular create still needs the authority's signature
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - the factory creates the account for the user
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(user_keypair.pubkey(), false),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                u32::to_le_bytes(0).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, owner, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            owner,
+            data_storage_program_id,
+            "Invalid data_storage_account's owner."
+        );
+        assert_eq!(
+            *array_ref![ data, OWNER_OFFSET, 32 ],
+            user_keypair.pubkey().to_bytes(),
+            "The user must be the authority."
+        );
+        assert_eq!(
+            data[BUMP_OFFSET],
+            dsa_bump,
+            "Invalid bump."
+        );
+        assert_eq!(
+            data.get(DATA_OFFSET..).unwrap(),
+            b"Solana".as_slice(),
+            "Invalid data."
+        );
+        assert_eq!(
+            i64::from_le_bytes(*array_ref![ data, EXPIRES_AT_OFFSET, 8 ]),
+            0,
+            "The legacy payload has no expires-at."
+        );
+    }
+    // success - the factory creates the account for the user
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - the user manages the account afterwards
+    {
+        ptc.set_account(
+            &user_keypair.pubkey(),
+            &AccountSharedData::new(
+                sol_to_lamports(1.0),
+                0,
+                &SYSTEM_PROGRAM_ID
+            )
+        );
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(user_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &user_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.get(DATA_OFFSET..).unwrap(),
+            b"Pooria".as_slice(),
+            "Invalid data."
+        );
+    }
+    // success - the user manages the account afterwards
+
+    // success - the factory creates an expiring account with a max-size
+    {
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(71);
+
+        let (dsa_addr, _) = find_data_storage_pda(
+            &user_keypair.pubkey(),
+            &data_storage_account_label,
+            &data_storage_program_id
+        );
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(user_keypair.pubkey(), false),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(FACTORY_CREATE_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                u32::to_le_bytes(64).as_slice(),
+                i64::to_le_bytes(1_000).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            u32::from_le_bytes(*array_ref![ data, MAX_SIZE_OFFSET, 4 ]),
+            64,
+            "Invalid max_size."
+        );
+        assert_eq!(
+            i64::from_le_bytes(*array_ref![ data, EXPIRES_AT_OFFSET, 8 ]),
+            1_000,
+            "Invalid expires_at."
+        );
+        assert_eq!(
+            data.get(DATA_OFFSET..).unwrap(),
+            b"Solana".as_slice(),
+            "Invalid data."
+        );
+    }
+    // success - the factory creates an expiring account with a max-size
+}
+
+#[tokio::test]
+async fn test_verify_data_storage_account_data() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(86);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+    let old_data_hash = *array_ref![ account_data, DATA_HASH_OFFSET, 32 ];
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    let verify_instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(dsa_addr, false)
+        ],
+        data: encode_instruction_discriminator(VERIFY_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR)
+    };
+
+    // success - an edit updates the stored hash
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                instruction,
+                verify_instruction.clone()
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_ne!(
+            *array_ref![ data, DATA_HASH_OFFSET, 32 ],
+            old_data_hash,
+            "The stored hash must change after an edit."
+        );
+        assert_eq!(
+            *array_ref![ data, DATA_HASH_OFFSET, 32 ],
+            hashv(&[ b"Pooria" ]).to_bytes(),
+            "Invalid stored hash."
+        );
+    }
+    // success - an edit updates the stored hash
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - corrupted data-field
+    {
+        let mut dsa_account = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+        dsa_account.data[DATA_OFFSET] ^= 0xff;
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(dsa_account)
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ verify_instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::HashMismatch as u32)
+            )
+        );
+    }
+    // failure - corrupted data-field
+}
+
+#[tokio::test]
+async fn test_patch_log() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(80);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana Data Storage"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    let (patch_log_addr, _) = Pubkey::find_program_address(
+        &[
+            PATCH_LOG_SEED_PREFIX,
+            dsa_addr.as_ref()
+        ],
+        &data_storage_program_id
+    );
+
+    let payer_pubkey = ptc.payer.pubkey();
+    let append_patch_log_instruction = |offset: u16, patch: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(patch_log_addr, false),
+            AccountMeta::new(payer_pubkey, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ],
+        data: [
+            encode_instruction_discriminator(APPEND_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            u16::to_le_bytes(offset).as_slice(),
+            patch
+        ].concat()
+    };
+    let compact_patch_log_instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(patch_log_addr, false)
+        ],
+        data: encode_instruction_discriminator(COMPACT_DATA_STORAGE_ACCOUNT_PATCH_LOG_INSTRUCTION_DISCRIMINATOR)
+    };
+
+    // success - patches are queued, the base stays the same
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                append_patch_log_instruction(0, b"solana"),
+                append_patch_log_instruction(7, b"DATA"),
+                append_patch_log_instruction(0, b"S")
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data: dsa_data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+        let SolanaAccount { data: patch_log_data, owner, .. } = ptc
+            .banks_client
+            .get_account(patch_log_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            owner,
+            data_storage_program_id,
+            "Invalid patch-log owner."
+        );
+        assert_eq!(
+            patch_log_data.len(),
+            PATCH_LOG_LEN,
+            "Invalid patch-log length."
+        );
+        assert_eq!(
+            patch_log_data[1],
+            3,
+            "Invalid pending patches count."
+        );
+        assert_eq!(
+            data_as_str(&dsa_data).unwrap(),
+            "Solana Data Storage",
+            "The base must not change."
+        );
+        assert_eq!(
+            read_materialized_data(&dsa_data, &patch_log_data).unwrap(),
+            b"Solana DATA Storage".to_vec(),
+            "Invalid materialized data."
+        );
+        assert_eq!(
+            u32::from_le_bytes(*array_ref![ dsa_data, REVISION_OFFSET, 4 ]),
+            3,
+            "Invalid revision."
+        );
+    }
+    // success - patches are queued, the base stays the same
+
+    // success - read-data applies the pending patches when the patch-log is passed
+    for (accounts, expected_data) in [
+        (
+            vec![
+                AccountMeta::new_readonly(dsa_addr, false),
+                AccountMeta::new_readonly(patch_log_addr, false)
+            ],
+            b"Solana DATA Storage"
+        ),
+        (
+            vec![
+                AccountMeta::new_readonly(dsa_addr, false)
+            ],
+            b"Solana Data Storage"
+        )
+    ] {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts,
+                    data: DataStorageInstruction::ReadData.pack()
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let return_data = ptc
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap()
+            .simulation_details
+            .unwrap()
+            .return_data
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            return_data,
+            [ [ 0 ].as_slice(), expected_data.as_slice() ].concat(),
+            "Invalid return-data."
+        );
+    };
+    // success - read-data applies the pending patches when the patch-log is passed
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - compact folds the patches into the base
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                compact_patch_log_instruction.clone(),
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(dsa_addr, false)
+                    ],
+                    data: encode_instruction_discriminator(VERIFY_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR)
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data: dsa_data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+        let SolanaAccount { data: patch_log_data, .. } = ptc
+            .banks_client
+            .get_account(patch_log_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data_as_str(&dsa_data).unwrap(),
+            "Solana DATA Storage",
+            "Invalid compacted base."
+        );
+        assert_eq!(
+            patch_log_data,
+            vec![ 0; PATCH_LOG_LEN ],
+            "The patch-log must be empty."
+        );
+        assert_eq!(
+            read_materialized_data(&dsa_data, &patch_log_data).unwrap(),
+            b"Solana DATA Storage".to_vec(),
+            "Invalid materialized data."
+        );
+    }
+    // success - compact folds the patches into the base
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - a full ring folds it's oldest patch into the base
+    {
+        let instructions = (0..=PATCH_LOG_CAPACITY as u16)
+            .map(|offset| append_patch_log_instruction(offset, b"_"))
+            .collect::<Vec<_>>();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data: dsa_data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+        let SolanaAccount { data: patch_log_data, .. } = ptc
+            .banks_client
+            .get_account(patch_log_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data_as_str(&dsa_data).unwrap(),
+            "_olana DATA Storage",
+            "The oldest patch must be folded into the base."
+        );
+        assert_eq!(
+            (patch_log_data[0], patch_log_data[1]),
+            (1, PATCH_LOG_CAPACITY as u8),
+            "Invalid patch-log head & count."
+        );
+        assert_eq!(
+            read_materialized_data(&dsa_data, &patch_log_data).unwrap(),
+            b"_________TA Storage".to_vec(),
+            "Invalid materialized data."
+        );
+    }
+    // success - a full ring folds it's oldest patch into the base
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - patch longer than a patch-log entry
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ append_patch_log_instruction(0, &[ 0; MAX_PATCH_LOG_ENTRY_DATA_LEN + 1 ]) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::InvalidData as u32)
+            )
+        );
+    }
+    // failure - patch longer than a patch-log entry
+
+    let read_accounts = |ptc: &ProgramTestContext| {
+        let banks_client = ptc.banks_client.clone();
+        async move {
+            (
+                banks_client.get_account(dsa_addr).await.unwrap().unwrap().data,
+                banks_client.get_account(patch_log_addr).await.unwrap().unwrap().data
+            )
+        }
+    };
+
+    // success - an edit makes the pending patches stale, compact drops them
+    {
+        let edit_instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: DataStorageInstruction::Edit {
+                data: b"Solana Data Storage".to_vec()
+            }.pack()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ edit_instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let (dsa_data, patch_log_data) = read_accounts(&ptc).await;
+
+        assert_eq!(
+            patch_log_data[1],
+            PATCH_LOG_CAPACITY as u8,
+            "The stale patches are still in the patch-log."
+        );
+        assert_eq!(
+            read_materialized_data(&dsa_data, &patch_log_data).unwrap(),
+            b"Solana Data Storage".to_vec(),
+            "Stale patches must be ignored."
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&compact_patch_log_instruction),
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let (dsa_data, _) = read_accounts(&ptc).await;
+
+        assert_eq!(
+            data_as_str(&dsa_data).unwrap(),
+            "Solana Data Storage",
+            "Stale patches must not be compacted into the base."
+        );
+    }
+    // success - an edit makes the pending patches stale, compact drops them
+
+    // success - duplicating the account folds it's pending patches into the copy
+    {
+        let mut copy_label: [u8; 30] = [0; 30];
+        copy_label.fill(81);
+
+        let (copy_dsa_addr, _) = find_data_storage_pda(
+            &authority_keypair.pubkey(),
+            &copy_label,
+            &data_storage_program_id
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                append_patch_log_instruction(0, b"s"),
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(dsa_addr, false),
+                        AccountMeta::new(copy_dsa_addr, false),
+                        AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                        AccountMeta::new(payer_pubkey, true),
+                        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                        AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false),
+                        AccountMeta::new_readonly(patch_log_addr, false)
+                    ],
+                    data: DataStorageInstruction::Duplicate {
+                        label: copy_label
+                    }.pack()
+                },
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(copy_dsa_addr, false)
+                    ],
+                    data: DataStorageInstruction::VerifyData.pack()
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let (dsa_data, patch_log_data) = read_accounts(&ptc).await;
+        let SolanaAccount { data: copy_dsa_data, .. } = ptc
+            .banks_client
+            .get_account(copy_dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data_as_str(&copy_dsa_data).unwrap(),
+            "solana Data Storage",
+            "The pending patches must be folded into the copy."
+        );
+        assert_eq!(
+            data_as_str(&dsa_data).unwrap(),
+            "Solana Data Storage",
+            "The source's base must not change."
+        );
+        assert_eq!(
+            patch_log_data[1],
+            1,
+            "The source's patches must stay pending."
+        );
+    }
+    // success - duplicating the account folds it's pending patches into the copy
+
+    let close_instruction = |patch_log: bool| {
+        let mut accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(payer_pubkey, false)
+        ];
+        if patch_log {
+            accounts.push(AccountMeta::new(payer_pubkey, false));
+            accounts.push(AccountMeta::new(patch_log_addr, false));
+        };
+
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts,
+            data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        }
+    };
+    let create_instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(payer_pubkey, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: [
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            b"Solana Data Storage"
+        ].concat()
+    };
+
+    // success - a re-created account doesn't inherit the closed one's pending patches
+    {
+        ptc
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+
+        // a closed account can only be re-created in a later transaction
+        for instructions in [
+            vec![ append_patch_log_instruction(0, b"s"), close_instruction(false) ],
+            vec![ create_instruction.clone(), compact_patch_log_instruction.clone() ]
+        ] {
+            let transaction = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&ptc.payer.pubkey()),
+                &[
+                    &ptc.payer,
+                    &authority_keypair
+                ],
+                ptc.last_blockhash
+            );
+
+            ptc
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap();
+        };
+
+        let (dsa_data, patch_log_data) = read_accounts(&ptc).await;
+
+        assert_eq!(
+            data_as_str(&dsa_data).unwrap(),
+            "Solana Data Storage",
+            "The closed account's patches must not be compacted into the re-created one."
+        );
+        assert_eq!(
+            patch_log_data,
+            vec![ 0; PATCH_LOG_LEN ],
+            "The patch-log must be empty."
+        );
+    }
+    // success - a re-created account doesn't inherit the closed one's pending patches
+
+    // success - closing the account closes it's patch-log
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                append_patch_log_instruction(0, b"s"),
+                close_instruction(true)
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert!(
+            ptc
+                .banks_client
+                .get_account(patch_log_addr)
+                .await
+                .unwrap()
+                .is_none(),
+            "The patch-log must be closed."
+        );
+    }
+    // success - closing the account closes it's patch-log
+
+    let mut new_label: [u8; 30] = [0; 30];
+    new_label.fill(82);
+
+    let new_authority_pubkey = Pubkey::new_unique();
+    let (transferred_dsa_addr, _) = find_data_storage_pda(
+        &new_authority_pubkey,
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+    let (sealed_dsa_addr, _) = find_data_storage_pda(
+        &SYSTEM_PROGRAM_ID,
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+    let (renamed_dsa_addr, _) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &new_label,
+        &data_storage_program_id
+    );
+
+    // success - moving the account folds it's pending patches and closes it's patch-log
+    for (move_instruction, moved_dsa_addr) in [
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new_readonly(new_authority_pubkey, false),
+                    AccountMeta::new(transferred_dsa_addr, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new(patch_log_addr, false),
+                    AccountMeta::new(payer_pubkey, false)
+                ],
+                data: DataStorageInstruction::TransferAuthority.pack()
+            },
+            transferred_dsa_addr
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(sealed_dsa_addr, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new(patch_log_addr, false),
+                    AccountMeta::new(payer_pubkey, false)
+                ],
+                data: DataStorageInstruction::Seal.pack()
+            },
+            sealed_dsa_addr
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new(renamed_dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(payer_pubkey, true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new(patch_log_addr, false)
+                ],
+                data: DataStorageInstruction::Rename {
+                    label: new_label
+                }.pack()
+            },
+            renamed_dsa_addr
+        )
+    ] {
+        ptc
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+
+        // the moved account is closed in the previous transaction, re-create it
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                create_instruction.clone(),
+                append_patch_log_instruction(0, b"s"),
+                move_instruction,
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(moved_dsa_addr, false)
+                    ],
+                    data: DataStorageInstruction::VerifyData.pack()
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data: moved_dsa_data, .. } = ptc
+            .banks_client
+            .get_account(moved_dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data_as_str(&moved_dsa_data).unwrap(),
+            "solana Data Storage",
+            "The pending patches must be folded into the moved account."
+        );
+        assert!(
+            ptc
+                .banks_client
+                .get_account(patch_log_addr)
+                .await
+                .unwrap()
+                .is_none(),
+            "The patch-log must be closed."
+        );
+    };
+    // success - moving the account folds it's pending patches and closes it's patch-log
+
+    // success - compare-and-close & close-expired close the patch-log too
+    for close_instruction in [
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(payer_pubkey, false),
+                AccountMeta::new(payer_pubkey, false),
+                AccountMeta::new(patch_log_addr, false)
+            ],
+            data: DataStorageInstruction::CompareAndClose {
+                expected_data_hash: keccak_hash(b"Solana Data Storage").to_bytes()
+            }.pack()
+        },
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new(payer_pubkey, false),
+                AccountMeta::new(payer_pubkey, false),
+                AccountMeta::new(patch_log_addr, false)
+            ],
+            data: DataStorageInstruction::CloseExpired.pack()
+        }
+    ] {
+        let mut account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana Data Storage"
+        );
+        account_data[EXPIRES_AT_OFFSET..EXPIRES_AT_OFFSET + 8].copy_from_slice(&i64::to_le_bytes(1_000));
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    lamports: rent.minimum_balance(account_data.len()),
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+
+        ptc
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                append_patch_log_instruction(0, b"s"),
+                close_instruction
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert!(
+            ptc
+                .banks_client
+                .get_account(patch_log_addr)
+                .await
+                .unwrap()
+                .is_none(),
+            "The patch-log must be closed."
+        );
+    };
+    // success - compare-and-close & close-expired close the patch-log too
+}
+
+#[tokio::test]
+async fn test_data_storage_account_delegate() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority & delegate accounts
+    let authority_keypair = Keypair::new();
+    let delegate_keypair = Keypair::new();
+    for keypair in [ &authority_keypair, &delegate_keypair ] {
+        pt.add_account(
+            keypair.pubkey(),
+            SolanaAccount::new(
+                sol_to_lamports(1.0),
+                0,
+                &SYSTEM_PROGRAM_ID
+            )
+        );
+    };
+    //? add authority & delegate accounts
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(68);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    let set_delegate_instruction = |signer: &Pubkey, delegate: &Pubkey| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(*signer, true)
+        ],
+        data: [
+            encode_instruction_discriminator(SET_DATA_STORAGE_ACCOUNT_DELEGATE_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            delegate.as_ref()
+        ].concat()
+    };
+    let edit_instruction = |signer: &Pubkey, new_data: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(*signer, true)
+        ],
+        data: [
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            new_data
+        ].concat()
+    };
+
+    // success - the authority sets a delegate, which can edit
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                set_delegate_instruction(&authority_keypair.pubkey(), &delegate_keypair.pubkey()),
+                edit_instruction(&delegate_keypair.pubkey(), b"Pooria")
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair,
+                &delegate_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            *array_ref![ data, DELEGATE_OFFSET, 32 ],
+            delegate_keypair.pubkey().to_bytes(),
+            "Invalid delegate."
+        );
+        assert_eq!(
+            data_as_str(&data).unwrap(),
+            "Pooria",
+            "Invalid data."
+        );
+    }
+    // success - the authority sets a delegate, which can edit
+
+    // failure - the delegate can't close the account or change the delegate
+    for instruction in [
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(delegate_keypair.pubkey(), true),
+                AccountMeta::new(delegate_keypair.pubkey(), false)
+            ],
+            data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        },
+        set_delegate_instruction(&delegate_keypair.pubkey(), &delegate_keypair.pubkey())
+    ] {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &delegate_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::IncorrectAuthority
+            )
+        );
+    };
+    // failure - the delegate can't close the account or change the delegate
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - a removed delegate can't edit anymore
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                set_delegate_instruction(&authority_keypair.pubkey(), &SYSTEM_PROGRAM_ID),
+                edit_instruction(&delegate_keypair.pubkey(), b"Solana")
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair,
+                &delegate_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                1,
+                InstructionError::IncorrectAuthority
+            )
+        );
+    }
+    // failure - a removed delegate can't edit anymore
+}
+
+#[tokio::test]
+async fn test_attest_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let pt = setup(&data_storage_program_id);
+
+    let authority = Pubkey::new_unique();
+
+    let mut ptc = pt.start_with_context().await;
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(65);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority.to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let mut account_data = data_storage_account_data(
+        &authority,
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+    // set last-updated
+    account_data[LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + 8].copy_from_slice(&i64::to_le_bytes(1_700_000_000));
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                data: account_data,
+                owner: data_storage_program_id,
+                lamports: sol_to_lamports(0.01),
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    ptc
+        .set_sysvar::<Clock>(
+            &Clock {
+                slot: 42,
+                ..Clock::default()
+            }
+        );
+
+    // success - the attestation can be recomputed client-side
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(dsa_addr, false)
+            ],
+            data: encode_instruction_discriminator(ATTEST_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+
+        let return_data = simulation_result
+            .simulation_details
+            .unwrap()
+            .return_data
+            .unwrap()
+            .data;
+
+        let expected_attestation = keccak_hashv(
+            &[
+                dsa_addr.as_ref(),
+                authority.as_ref(),
+                b"Solana",
+                &i64::to_le_bytes(1_700_000_000)
+            ]
+        );
+
+        assert_eq!(
+            return_data.len(),
+            40,
+            "Invalid return-data length."
+        );
+        assert_eq!(
+            *array_ref![ return_data, 0, 32 ],
+            expected_attestation.to_bytes(),
+            "Invalid attestation."
+        );
+        assert_eq!(
+            u64::from_le_bytes(*array_ref![ return_data, 32, 8 ]),
+            42,
+            "Invalid slot."
+        );
+    }
+    // success - the attestation can be recomputed client-side
+}
+
+#[tokio::test]
+async fn test_close_expired_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let pt = setup(&data_storage_program_id);
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent_receiver = Pubkey::new_unique();
+
+    ptc
+        .set_sysvar::<Clock>(
+            &Clock {
+                unix_timestamp: 500,
+                ..Clock::default()
+            }
+        );
+
+    // immutable accounts can expire too
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(69);
+
+    let (dsa_addr, _) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            SYSTEM_PROGRAM_ID.as_ref(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let close_expired_instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new(rent_receiver, false)
+        ],
+        data: encode_instruction_discriminator(CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+    };
+
+    // success - create an account that expires at '1000'
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                u32::to_le_bytes(0).as_slice(),
+                i64::to_le_bytes(1_000).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            i64::from_le_bytes(*array_ref![ data, EXPIRES_AT_OFFSET, 8 ]),
+            1_000,
+            "Invalid expires_at."
+        );
+    }
+    // success - create an account that expires at '1000'
+
+    // failure - not expired yet
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&close_expired_instruction),
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::NotExpired as u32)
+            )
+        );
+    }
+    // failure - not expired yet
+
+    ptc
+        .set_sysvar::<Clock>(
+            &Clock {
+                unix_timestamp: 1_000,
+                ..Clock::default()
+            }
+        );
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success - anyone can close the expired account
+    {
+        let dsa_lamports = ptc
+            .banks_client
+            .get_balance(dsa_addr)
+            .await
+            .unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ close_expired_instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert!(
+            ptc
+                .banks_client
+                .get_account(dsa_addr)
+                .await
+                .unwrap()
+                .is_none(),
+            "Expired account must be closed."
+        );
+        assert_eq!(
+            ptc
+                .banks_client
+                .get_balance(rent_receiver)
+                .await
+                .unwrap(),
+            dsa_lamports,
+            "Invalid rent receiver balance."
+        );
+    }
+    // success - anyone can close the expired account
+
+    // failure - accounts without an expires-at never expire
+    {
+        let authority = Pubkey::new_unique();
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                DATA_STORAGE_SEED_PREFIX,
+                authority.as_ref(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let account_data = data_storage_account_data(
+            &authority,
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: sol_to_lamports(0.01),
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new(dsa_addr, false),
+                        AccountMeta::new(rent_receiver, false)
+                    ],
+                    data: encode_instruction_discriminator(CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::NotExpired as u32)
+            )
+        );
+    }
+    // failure - accounts without an expires-at never expire
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // success & failure - lamports sent directly to the pda
+    {
+        let authority = Pubkey::new_unique();
+
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                DATA_STORAGE_SEED_PREFIX,
+                authority.as_ref(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        let mut account_data = data_storage_account_data(
+            &authority,
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+        account_data[EXPIRES_AT_OFFSET..EXPIRES_AT_OFFSET + 8].copy_from_slice(&i64::to_le_bytes(1_000));
+
+        let rent = ptc
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap();
+        let rent_exempt_lamports = rent.minimum_balance(account_data.len());
+        let excess_lamports = sol_to_lamports(0.01);
+
+        ptc.set_account(
+            &dsa_addr,
+            &AccountSharedData::from(
+                SolanaAccount {
+                    data: account_data,
+                    owner: data_storage_program_id,
+                    lamports: rent_exempt_lamports + excess_lamports,
+                    rent_epoch: Epoch::default(),
+                    executable: false
+                }
+            )
+        );
+
+        let rent_receiver = Pubkey::new_unique();
+        let excess_receiver = Pubkey::new_unique();
+
+        let close_expired_instruction = |excess_receiver: Option<Pubkey>| {
+            let mut accounts = vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new(rent_receiver, false)
+            ];
+            if let Some(excess_receiver) = excess_receiver {
+                accounts.push(AccountMeta::new(excess_receiver, false));
+            };
+
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts,
+                data: encode_instruction_discriminator(CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+            }
+        };
+
+        // failure - no excess-receiver
+        let transaction = Transaction::new_signed_with_payer(
+            &[ close_expired_instruction(None) ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::UnexpectedLamports as u32)
+            )
+        );
+        // failure - no excess-receiver
+
+        // success - the excess goes to the excess-receiver
+        let transaction = Transaction::new_signed_with_payer(
+            &[ close_expired_instruction(Some(excess_receiver)) ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ptc.banks_client.get_balance(rent_receiver).await.unwrap(),
+            rent_exempt_lamports,
+            "Invalid rent-receiver lamport balance."
+        );
+        assert_eq!(
+            ptc.banks_client.get_balance(excess_receiver).await.unwrap(),
+            excess_lamports,
+            "Invalid excess-receiver lamport balance."
+        );
+        assert!(
+            ptc.banks_client.get_account(dsa_addr).await.unwrap().is_none(),
+            "Expired account must be closed."
+        );
+        // success - the excess goes to the excess-receiver
+    }
+    // success & failure - lamports sent directly to the pda
+}
+
+#[tokio::test]
+async fn test_rent_exactness_across_lifecycle() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority, funding & rent-receiver accounts
+    let authority_keypair = Keypair::new();
+    let funding_keypair = Keypair::new();
+    // the fee-payer is neither the funding account nor the rent receiver, so fees don't show up in the lamport flow
+    let rent_receiver = Pubkey::new_unique();
+    for account in [ authority_keypair.pubkey(), funding_keypair.pubkey(), rent_receiver ] {
+        pt.add_account(
+            account,
+            SolanaAccount::new(
+                sol_to_lamports(1.0),
+                0,
+                &SYSTEM_PROGRAM_ID
+            )
+        );
+    };
+    //? add authority, funding & rent-receiver accounts
+
+    let ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(76);
+
+    let (dsa_addr, _) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let funding_balance_before = ptc
+        .banks_client
+        .get_balance(funding_keypair.pubkey())
+        .await
+        .unwrap();
+    let rent_receiver_balance_before = ptc
+        .banks_client
+        .get_balance(rent_receiver)
+        .await
+        .unwrap();
+
+    let steps = [
+        // create
+        (Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(funding_keypair.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                b"Sol".as_slice()
+            ].concat()
+        }, true),
+        // grow
+        (Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(funding_keypair.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        }, true),
+        // grow again
+        (Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(funding_keypair.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Solana Data Storage".as_slice()
+            ].concat()
+        }, true),
+        // shrink
+        (Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(rent_receiver, false)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Solana Data".as_slice()
+            ].concat()
+        }, false)
+    ];
+
+    // success - the account is exactly rent-exempt after every step
+    for (instruction, is_funded) in steps {
+        let mut signers = vec![
+            &ptc.payer,
+            &authority_keypair
+        ];
+        if is_funded {
+            signers.push(&funding_keypair);
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &signers,
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(data.len()),
+            "The account must be exactly rent-exempt for it's size."
+        );
+    };
+    // success - the account is exactly rent-exempt after every step
+
+    // success - close, every lamport the funding account paid ends up at the rent receiver
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new(dsa_addr, false),
+                        AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                        AccountMeta::new(rent_receiver, false)
+                    ],
+                    data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert!(
+            ptc
+                .banks_client
+                .get_account(dsa_addr)
+                .await
+                .unwrap()
+                .is_none(),
+            "The account must be closed."
+        );
+
+        let funding_balance_after = ptc
+            .banks_client
+            .get_balance(funding_keypair.pubkey())
+            .await
+            .unwrap();
+        let rent_receiver_balance_after = ptc
+            .banks_client
+            .get_balance(rent_receiver)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            funding_balance_before - funding_balance_after,
+            rent_receiver_balance_after - rent_receiver_balance_before,
+            "Lamports paid in must equal the lamports refunded."
+        );
+    }
+    // success - close, every lamport the funding account paid ends up at the rent receiver
+}
+
+#[tokio::test]
+async fn test_data_storage_account_unpack() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let ptc = pt.start_with_context().await;
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(85);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    // success - unpack the bytes written by the create handler
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                u32::to_le_bytes(64).as_slice(),
+                i64::to_le_bytes(4_000_000_000).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert_eq!(account.owner, authority_keypair.pubkey(), "Invalid owner.");
+        assert_eq!(account.label, data_storage_account_label, "Invalid label.");
+        assert_eq!(account.last_updated, 0, "Invalid last_updated.");
+        assert_eq!(account.bump, dsa_bump, "Invalid bump.");
+        assert!(account.is_initialized, "Invalid is_initialized.");
+        assert_eq!(account.data_len, 6, "Invalid data_len.");
+        assert_eq!(account.max_size, 64, "Invalid max_size.");
+        assert_eq!(account.lock_holder, Pubkey::default(), "Invalid lock_holder.");
+        assert_eq!(account.lock_expiry, 0, "Invalid lock_expiry.");
+        assert_eq!(account.display_label, data_storage_account_label, "Invalid display_label.");
+        assert_eq!(account.idempotency_key, 0, "Invalid idempotency_key.");
+        assert_eq!(account.cpi_allowlist, [ Pubkey::default(); 3 ], "Invalid cpi_allowlist.");
+        assert!(!account.has_been_edited, "Invalid has_been_edited.");
+        assert_eq!(account.created_at, i64::from_le_bytes(*array_ref![ data, CREATED_AT_OFFSET, 8 ]), "Invalid created_at.");
+        assert_eq!(account.revision, 0, "Invalid revision.");
+        assert_eq!(account.data_hash, hashv(&[ b"Solana" ]).to_bytes(), "Invalid data_hash.");
+        assert_eq!(account.delegate, Pubkey::default(), "Invalid delegate.");
+        assert_eq!(account.expires_at, 4_000_000_000, "Invalid expires_at.");
+        assert_eq!(account.last_editor, authority_keypair.pubkey(), "Invalid last_editor.");
+        assert_eq!(account.layout_version, LAYOUT_VERSION_FIXED_LABEL, "Invalid layout_version.");
+        assert!(account.variable_label.is_empty(), "Invalid variable_label.");
+        assert_eq!(account.header_version, HEADER_VERSION, "Invalid header_version.");
+        assert!(!account.compressed, "Invalid compressed.");
+        assert_eq!(account.kind, ACCOUNT_KIND_BYTES, "Invalid kind.");
+        assert_eq!(account.lock_until, 0, "Invalid lock_until.");
+        assert_eq!(account.data, b"Solana".to_vec(), "Invalid data.");
+
+        // borsh round-trip
+        assert_eq!(
+            DataStorageAccount::try_from_slice(&borsh::to_vec(&account).unwrap()).unwrap(),
+            account,
+            "Invalid borsh round-trip."
+        );
+    }
+    // success - unpack the bytes written by the create handler
+
+    // failure - malformed & uninitialized account data
+    {
+        assert_eq!(
+            DataStorageAccount::unpack(&[ 0; 10 ]).unwrap_err(),
+            ProgramError::InvalidAccountData,
+            "Short account data must be rejected."
+        );
+
+        let mut account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+        // data-length beyond the data-field
+        account_data[DATA_LEN_OFFSET..DATA_LEN_OFFSET + 2].copy_from_slice(&u16::to_le_bytes(7));
+        assert_eq!(
+            DataStorageAccount::unpack(&account_data).unwrap_err(),
+            ProgramError::InvalidAccountData,
+            "Data-length beyond the data-field must be rejected."
+        );
+
+        // uninitialized
+        account_data[INIT_OFFSET] = false as u8;
+        assert_eq!(
+            DataStorageAccount::unpack(&account_data).unwrap_err(),
+            ProgramError::UninitializedAccount,
+            "Uninitialized accounts must be rejected."
+        );
+    }
+    // failure - malformed & uninitialized account data
+}
+
+#[tokio::test]
+async fn test_find_data_storage_pda() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let ptc = setup(&data_storage_program_id).start_with_context().await;
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label[..6].copy_from_slice(b"Solana");
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &ptc.payer.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(ptc.payer.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: [
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            b"Solana".as_slice()
+        ].concat()
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ instruction ],
+        Some(&ptc.payer.pubkey()),
+        &[ &ptc.payer ],
+        ptc.last_blockhash
+    );
+
+    ptc
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let SolanaAccount { data, .. } = ptc
+        .banks_client
+        .get_account(dsa_addr)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        data[BUMP_OFFSET],
+        dsa_bump,
+        "Invalid bump."
+    );
+    assert_eq!(
+        create_data_storage_pda(
+            &ptc.payer.pubkey(),
+            &data_storage_account_label,
+            data[BUMP_OFFSET],
+            &data_storage_program_id
+        ).unwrap(),
+        dsa_addr,
+        "Invalid data storage account address."
+    );
+    assert_eq!(
+        DATA_STORAGE_SEED_PREFIX,
+        b"data_storage_account",
+        "Invalid seed prefix."
+    );
+}
+
+#[tokio::test]
+async fn test_anchor_discriminators() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let ptc = setup(&data_storage_program_id).start_with_context().await;
+
+    // table
+    for (i, (anchor_discriminator, discriminator)) in ANCHOR_INSTRUCTION_DISCRIMINATORS.iter().enumerate() {
+        assert_eq!(
+            *discriminator as usize,
+            i,
+            "Anchor discriminators must be ordered by their 'u8' discriminator."
+        );
+        assert!(
+            ANCHOR_INSTRUCTION_DISCRIMINATORS[..i]
+                .iter()
+                .all(|(other, _)| other != anchor_discriminator),
+            "Duplicate anchor discriminator."
+        );
+    }
+    assert_eq!(
+        ANCHOR_INSTRUCTION_DISCRIMINATORS[CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR as usize].0,
+        hashv(&[ b"global:create_new_data_storage_account" ]).to_bytes()[..8],
+        "Invalid create anchor discriminator."
+    );
+    assert_eq!(
+        ANCHOR_INSTRUCTION_DISCRIMINATORS[CLOSE_EXPIRED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR as usize].0,
+        hashv(&[ b"global:close_expired_data_storage_account" ]).to_bytes()[..8],
+        "Invalid close-expired anchor discriminator."
+    );
+
+    #[cfg(feature = "anchor_discriminators")]
+    let (
+        enabled_discriminator,
+        disabled_discriminator
+    ) = (
+        ANCHOR_INSTRUCTION_DISCRIMINATORS[CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR as usize].0.to_vec(),
+        vec![ CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR ]
+    );
+    #[cfg(not(feature = "anchor_discriminators"))]
+    let (
+        enabled_discriminator,
+        disabled_discriminator
+    ) = (
+        encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+        ANCHOR_INSTRUCTION_DISCRIMINATORS[CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR as usize].0.to_vec()
+    );
+    assert_eq!(
+        encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR),
+        enabled_discriminator,
+        "Invalid encoded discriminator."
+    );
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(65);
+
+    let (dsa_addr, _) = find_data_storage_pda(
+        &ptc.payer.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let create_instruction = |discriminator: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(ptc.payer.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: [
+            discriminator,
+            data_storage_account_label.as_slice(),
+            b"Solana".as_slice()
+        ].concat()
+    };
+
+    // failure - the other mode's discriminator
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_instruction(&disabled_discriminator) ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidInstructionData
+            ),
+            "Only the enabled mode's discriminators must be accepted."
+        );
+    }
+    // failure - the other mode's discriminator
+
+    // success - the enabled mode's discriminator
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_instruction(&enabled_discriminator) ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data_as_str(&data).unwrap(),
+            "Solana",
+            "Invalid data."
+        );
+    }
+    // success - the enabled mode's discriminator
+}
+
+#[tokio::test]
+async fn test_edit_grow_size_deltas() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut ptc = setup(&data_storage_program_id).start_with_context().await;
+    let payer = ptc.payer.insecure_clone();
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(71);
+
+    let (dsa_addr, _) = find_data_storage_pda(
+        &payer.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let create_instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(payer.pubkey(), true),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: [
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            &[ 1 ]
+        ].concat()
+    };
+    let reserve_instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(payer.pubkey(), true),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ],
+        data: [
+            encode_instruction_discriminator(RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            u16::to_le_bytes(100).as_slice()
+        ].concat()
+    };
+
+    // (instruction, expected data-length, expected data-field capacity)
+    let mut steps = vec![
+        (create_instruction, 1, 1)
+    ];
+    for (new_data_length, expected_capacity) in [ (2, 2), (257, 257), (957, 957) ] {
+        steps.push(
+            (
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new(dsa_addr, false),
+                        AccountMeta::new_readonly(payer.pubkey(), true),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+                    ],
+                    data: [
+                        encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                        vec![ new_data_length as u8; new_data_length ].as_slice()
+                    ].concat()
+                },
+                new_data_length,
+                expected_capacity
+            )
+        );
+    }
+    steps.push((reserve_instruction, 957, 1057));
+    // within the reserved capacity, the account doesn't grow
+    steps.push(
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(payer.pubkey(), true)
+                ],
+                data: [
+                    encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    [ 9; 1007 ].as_slice()
+                ].concat()
+            },
+            1007,
+            1057
+        )
+    );
+
+    for (instruction, expected_data_length, expected_capacity) in steps {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer.pubkey()),
+            &[ &payer ],
+            ptc.get_new_latest_blockhash().await.unwrap()
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            HEADER_LEN + expected_capacity,
+            "Invalid account size."
+        );
+        assert_eq!(
+            u16::from_le_bytes(*array_ref![ data, DATA_LEN_OFFSET, 2 ]) as usize,
+            expected_data_length,
+            "Invalid stored data-length."
+        );
+        assert!(
+            data[DATA_OFFSET..DATA_OFFSET + expected_data_length]
+                .iter()
+                .all(|byte| *byte == data[DATA_OFFSET]),
+            "Invalid data-field."
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_rent_exactness_across_edit_cycles() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut ptc = setup(&data_storage_program_id).start_with_context().await;
+    let payer = ptc.payer.insecure_clone();
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(72);
+
+    let (dsa_addr, _) = find_data_storage_pda(
+        &payer.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    let mut instructions = vec![
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        }
+    ];
+    // grow & shrink cycles with uneven sizes
+    for cycle in 1..=8 {
+        for new_data_length in [ 6 + cycle * 97, 3 + cycle * 13 ] {
+            instructions.push(
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new(dsa_addr, false),
+                        AccountMeta::new_readonly(payer.pubkey(), true),
+                        AccountMeta::new(payer.pubkey(), true),
+                        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+                    ],
+                    data: [
+                        encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                        vec![ cycle as u8; new_data_length ].as_slice()
+                    ].concat()
+                }
+            );
+        }
+    }
+
+    for instruction in instructions {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer.pubkey()),
+            &[ &payer ],
+            ptc.get_new_latest_blockhash().await.unwrap()
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(data.len()),
+            "Data storage account must stay exactly rent-exempt."
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_shrink_keeps_rent_exemption() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    let rent = Rent::default();
+    let authority_keypair = Keypair::new();
+    let rent_receiver = Pubkey::new_unique();
+
+    //? add authority & rent-receiver accounts
+    for account in [ authority_keypair.pubkey(), rent_receiver ] {
+        pt.add_account(
+            account,
+            SolanaAccount::new(
+                sol_to_lamports(1.0),
+                0,
+                &SYSTEM_PROGRAM_ID
+            )
+        );
+    }
+    //? add authority & rent-receiver accounts
+
+    // (label, data-storage account's lamports, expected refund)
+    let cases = [
+        // over-funded, only the freed bytes' rent is refunded
+        (
+            [ 73; 30 ],
+            rent.minimum_balance(HEADER_LEN + 19) + sol_to_lamports(0.5),
+            rent.minimum_balance(HEADER_LEN + 19) - rent.minimum_balance(HEADER_LEN + 3)
+        ),
+        // under-funded (e.g. other rent parameters), only what's above the new rent-exempt minimum is refunded
+        (
+            [ 74; 30 ],
+            rent.minimum_balance(HEADER_LEN + 3) + 10,
+            10
+        )
+    ];
+
+    let mut dsa_addrs = vec![];
+    for (label, lamports, _) in cases {
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = find_data_storage_pda(
+            &authority_keypair.pubkey(),
+            &label,
+            &data_storage_program_id
+        );
+
+        pt.add_account(
+            dsa_addr,
+            SolanaAccount {
+                data: data_storage_account_data(
+                    &authority_keypair.pubkey(),
+                    &label,
+                    dsa_bump,
+                    b"Solana Data Storage"
+                ),
+                owner: data_storage_program_id,
+                lamports,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        );
+
+        dsa_addrs.push(dsa_addr);
+    }
+
+    let ptc = pt.start_with_context().await;
+
+    for (dsa_addr, (_, _, expected_refund)) in dsa_addrs.into_iter().zip(cases) {
+        let receiver_lamports_before = ptc
+            .banks_client
+            .get_balance(rent_receiver)
+            .await
+            .unwrap();
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(rent_receiver, false)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Sol".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            HEADER_LEN + 3,
+            "Invalid data storage account size."
+        );
+        assert!(
+            lamports >= rent.minimum_balance(data.len()),
+            "Data storage account must stay rent-exempt."
+        );
+        assert_eq!(
+            ptc
+                .banks_client
+                .get_balance(rent_receiver)
+                .await
+                .unwrap() - receiver_lamports_before,
+            expected_refund,
+            "Invalid refund."
+        );
+    }
+}
+
+// Invokes "READ_DATA_STORAGE_ACCOUNT_DATA" and echoes the data-field it read back as it's own return-data.
+fn read_data_caller_process_instruction(
+    _program_id: &Pubkey,
+    accounts_info: &[AccountInfo],
+    _instruction_data: &[u8]
+) -> ProgramResult {
+    let (
+        data_storage_program_account_info,
+        accounts_info
+    ) = accounts_info.split_first().unwrap();
+
+    invoke(
+        &Instruction {
+            program_id: *data_storage_program_account_info.key,
+            accounts: vec![
+                AccountMeta::new_readonly(*accounts_info[0].key, false)
+            ],
+            data: encode_instruction_discriminator(READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR)
+        },
+        accounts_info
+    )?;
+
+    let (return_data_program_id, data) = get_return_data().unwrap();
+    assert_eq!(
+        &return_data_program_id,
+        data_storage_program_account_info.key,
+        "Invalid return-data program."
+    );
+
+    set_return_data(&data);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_data_storage_account_data() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let caller_program_id = Pubkey::new_from_array([2; 32]);
+
+    let mut pt = setup(&data_storage_program_id);
+    pt.add_program(
+        "caller_program",
+        caller_program_id,
+        processor!(read_data_caller_process_instruction)
+    );
+
+    let authority = Pubkey::new_unique();
+
+    // (label, data-field, data-storage account's program owner)
+    let accounts = [
+        ([ 75; 30 ], b"Solana".to_vec(), data_storage_program_id),
+        ([ 76; 30 ], vec![ 7; 1024 ], data_storage_program_id),
+        ([ 77; 30 ], b"Solana".to_vec(), SYSTEM_PROGRAM_ID)
+    ];
+
+    let mut dsa_addrs = vec![];
+    for (label, data, owner) in &accounts {
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = find_data_storage_pda(
+            &authority,
+            label,
+            &data_storage_program_id
+        );
+
+        pt.add_account(
+            dsa_addr,
+            SolanaAccount {
+                data: data_storage_account_data(
+                    &authority,
+                    label,
+                    dsa_bump,
+                    data
+                ),
+                owner: *owner,
+                lamports: sol_to_lamports(1.0),
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        );
+
+        dsa_addrs.push(dsa_addr);
+    }
+
+    let ptc = pt.start_with_context().await;
+
+    // success - direct call & cpi
+    for (program_id, accounts) in [
+        (
+            data_storage_program_id,
+            vec![ AccountMeta::new_readonly(dsa_addrs[0], false) ]
+        ),
+        (
+            caller_program_id,
+            vec![
+                AccountMeta::new_readonly(data_storage_program_id, false),
+                AccountMeta::new_readonly(dsa_addrs[0], false)
+            ]
+        )
+    ] {
+        let instruction = Instruction {
+            program_id,
+            accounts,
+            data: encode_instruction_discriminator(READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert!(
+            simulation_result
+                .result
+                .unwrap()
+                .is_ok(),
+            "Read must not fail."
+        );
+
+        let return_data = simulation_result
+            .simulation_details
+            .unwrap()
+            .return_data
+            .unwrap();
+
+        assert_eq!(
+            return_data.program_id,
+            program_id,
+            "Invalid return-data program."
+        );
+        assert_eq!(
+            return_data.data,
+            [ &[ false as u8 ], b"Solana".as_slice() ].concat(),
+            "Invalid return-data."
+        );
+    }
+    // success - direct call & cpi
+
+    // failure - data-field bigger than the return-data limit & invalid program owner
+    for (dsa_addr, expected_error) in [
+        (dsa_addrs[1], InstructionError::Custom(DataStorageError::InvalidData as u32)),
+        (dsa_addrs[2], InstructionError::InvalidAccountOwner)
+    ] {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(dsa_addr, false)
+            ],
+            data: encode_instruction_discriminator(READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                expected_error
+            )
+        );
+    }
+    // failure - data-field bigger than the return-data limit & invalid program owner
+}
+
+#[tokio::test]
+async fn test_last_editor() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority & delegate accounts
+    let authority_keypair = Keypair::new();
+    let delegate_keypair = Keypair::new();
+    for keypair in [ &authority_keypair, &delegate_keypair ] {
+        pt.add_account(
+            keypair.pubkey(),
+            SolanaAccount::new(
+                sol_to_lamports(1.0),
+                0,
+                &SYSTEM_PROGRAM_ID
+            )
+        );
+    };
+    //? add authority & delegate accounts
+
+    let ptc = pt.start_with_context().await;
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(76);
+
+    let (
+        dsa_addr,
+        _
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    // success - the owner creates the account and is the last editor
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            *array_ref![ data, LAST_EDITOR_OFFSET, 32 ],
+            authority_keypair.pubkey().to_bytes(),
+            "Invalid last editor."
+        );
+    }
+    // success - the owner creates the account and is the last editor
+
+    // success - a delegate edits and becomes the last editor
+    {
+        let set_delegate_instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(SET_DATA_STORAGE_ACCOUNT_DELEGATE_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                delegate_keypair.pubkey().as_ref()
+            ].concat()
+        };
+        let edit_instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(delegate_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                set_delegate_instruction,
+                edit_instruction
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair,
+                &delegate_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            *array_ref![ data, LAST_EDITOR_OFFSET, 32 ],
+            delegate_keypair.pubkey().to_bytes(),
+            "Invalid last editor."
+        );
+        assert_eq!(
+            *array_ref![ data, OWNER_OFFSET, 32 ],
+            authority_keypair.pubkey().to_bytes(),
+            "The owner must not change."
+        );
+    }
+    // success - a delegate edits and becomes the last editor
+}
+
+#[tokio::test]
+async fn test_variable_label() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let ptc = pt.start_with_context().await;
+
+    let create_instruction = |dsa_addr: Pubkey, account_label: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: [
+            encode_instruction_discriminator(CREATE_NEW_VARIABLE_LABEL_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            &[ account_label.len() as u8 ],
+            account_label,
+            u32::to_le_bytes(0).as_slice(),
+            i64::to_le_bytes(0).as_slice(),
+            b"Solana".as_slice()
+        ].concat()
+    };
+
+    // success - create accounts with 1, 30 & 64-byte labels, each at it's own pda
+    {
+        let labels = [
+            vec![ 65; 1 ],
+            vec![ 65; 30 ],
+            vec![ 65; MAX_VARIABLE_LABEL_LENGTH ]
+        ];
+
+        let mut dsa_addrs = vec![];
+        for account_label in &labels {
+            let (
+                dsa_addr,
+                dsa_bump
+            ) = find_variable_label_data_storage_pda(
+                &authority_keypair.pubkey(),
+                account_label,
+                &data_storage_program_id
+            );
+
+            let edit_instruction = Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+                ],
+                data: [
+                    encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    b"Pooria".as_slice()
+                ].concat()
+            };
+
+            let transaction = Transaction::new_signed_with_payer(
+                &[
+                    create_instruction(dsa_addr, account_label),
+                    edit_instruction
+                ],
+                Some(&ptc.payer.pubkey()),
+                &[
+                    &ptc.payer,
+                    &authority_keypair
+                ],
+                ptc.last_blockhash
+            );
+
+            ptc
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap();
+
+            let SolanaAccount { data, .. } = ptc
+                .banks_client
+                .get_account(dsa_addr)
+                .await
+                .unwrap()
+                .unwrap();
+
+            let account = DataStorageAccount::unpack(&data).unwrap();
+
+            let mut display_label = [ 0; 30 ];
+            display_label[..account_label.len().min(30)].copy_from_slice(&account_label[..account_label.len().min(30)]);
+
+            assert_eq!(account.layout_version, LAYOUT_VERSION_VARIABLE_LABEL, "Invalid layout_version.");
+            assert_eq!(&account.variable_label, account_label, "Invalid variable_label.");
+            assert_eq!(account.label, [ 0; 30 ], "Invalid label.");
+            assert_eq!(account.display_label, display_label, "Invalid display_label.");
+            assert_eq!(account.bump, dsa_bump, "Invalid bump.");
+            assert_eq!(account.data, b"Pooria".to_vec(), "Invalid data.");
+
+            dsa_addrs.push(dsa_addr);
+        };
+
+        // the 30-byte variable label doesn't derive the fixed-label account's pda either
+        let (
+            fixed_label_dsa_addr,
+            _
+        ) = find_data_storage_pda(
+            &authority_keypair.pubkey(),
+            &[ 65; 30 ],
+            &data_storage_program_id
+        );
+        dsa_addrs.push(fixed_label_dsa_addr);
+
+        for (index, dsa_addr) in dsa_addrs.iter().enumerate() {
+            assert!(
+                !dsa_addrs[index + 1..].contains(dsa_addr),
+                "Pdas must be distinct."
+            );
+        };
+    }
+    // success - create accounts with 1, 30 & 64-byte labels, each at it's own pda
+
+    // failure - empty & too long labels
+    for account_label in [
+        vec![],
+        vec![ 65; MAX_VARIABLE_LABEL_LENGTH + 1 ]
+    ] {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_instruction(Pubkey::new_unique(), &account_label) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::InvalidLabel as u32
+                )
+            )
+        );
+    };
+    // failure - empty & too long labels
+}
+
+#[tokio::test]
+async fn test_soft_close_and_revive_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority & rent-receiver accounts
+    let authority_keypair = Keypair::new();
+    let rent_receiver = Pubkey::new_unique();
+    for account in [ authority_keypair.pubkey(), rent_receiver ] {
+        pt.add_account(
+            account,
+            SolanaAccount::new(
+                sol_to_lamports(1.0),
+                0,
+                &SYSTEM_PROGRAM_ID
+            )
+        );
+    };
+    //? add authority & rent-receiver accounts
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(84);
+
+    let (
+        dsa_addr,
+        _
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let create_instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: [
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            b"Solana".as_slice()
+        ].concat()
+    };
+    let payer = ptc.payer.pubkey();
+    let revive_instruction = |new_data: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ],
+        data: [
+            encode_instruction_discriminator(REVIVE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            u32::to_le_bytes(0).as_slice(),
+            i64::to_le_bytes(0).as_slice(),
+            new_data
+        ].concat()
+    };
+
+    // success - soft-close leaves a tombstone with the closed-at timestamp
+    {
+        let soft_close_instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(rent_receiver, false)
+            ],
+            data: encode_instruction_discriminator(SOFT_CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&create_instruction),
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let current_time = 500_i64;
+        ptc
+            .set_sysvar::<Clock>(
+                &Clock {
+                    unix_timestamp: current_time,
+                    ..Clock::default()
+                }
+            );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ soft_close_instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { lamports, data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            HEADER_LEN,
+            "The data-field must be dropped."
+        );
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN),
+            "Only the header's rent-exempt minimum must be kept."
+        );
+        assert_eq!(
+            i64::from_le_bytes(*array_ref![ data, CLOSED_AT_OFFSET, 8 ]),
+            current_time,
+            "Invalid closed-at."
+        );
+
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert!(account.tombstone, "Invalid tombstone.");
+        assert!(!account.is_initialized, "Invalid is_initialized.");
+        assert_eq!(account.closed_at, current_time, "Invalid closed_at.");
+        assert_eq!(account.owner, authority_keypair.pubkey(), "Invalid owner.");
+        assert_eq!(account.label, data_storage_account_label, "Invalid label.");
+        assert!(account.data.is_empty(), "Invalid data.");
+
+        let rent_receiver_account = ptc
+            .banks_client
+            .get_account(rent_receiver)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            rent_receiver_account.lamports,
+            sol_to_lamports(1.0) + rent.minimum_balance(HEADER_LEN + 6) - rent.minimum_balance(HEADER_LEN),
+            "Invalid refund."
+        );
+    }
+    // success - soft-close leaves a tombstone with the closed-at timestamp
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - create can't reuse the tombstone's pda
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::AccountTombstoned as u32
+                )
+            )
+        );
+    }
+    // failure - create can't reuse the tombstone's pda
+
+    // success - revive the tombstone
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ revive_instruction(b"Pooria") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { lamports, data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN + 6),
+            "The revived account must be rent-exempt."
+        );
+
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert!(!account.tombstone, "Invalid tombstone.");
+        assert!(account.is_initialized, "Invalid is_initialized.");
+        assert_eq!(account.closed_at, 0, "Invalid closed_at.");
+        assert_eq!(account.data, b"Pooria".to_vec(), "Invalid data.");
+        assert_eq!(account.data_hash, hashv(&[ b"Pooria" ]).to_bytes(), "Invalid data_hash.");
+        assert_eq!(account.last_editor, authority_keypair.pubkey(), "Invalid last_editor.");
+    }
+    // success - revive the tombstone
+
+    // failure - only tombstones can be revived
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ revive_instruction(b"Solana") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::AccountAlreadyInitialized
+            )
+        );
+    }
+    // failure - only tombstones can be revived
+}
+
+#[tokio::test]
+async fn test_batch_create_data_storage_accounts() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let ptc = pt.start_with_context().await;
+
+    let batch_create_instruction = |accounts: &[([u8; 30], &[u8])]| {
+        let mut instruction_accounts = vec![
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ];
+        let mut instruction_data = encode_instruction_discriminator(BATCH_CREATE_DATA_STORAGE_ACCOUNTS_INSTRUCTION_DISCRIMINATOR);
+        instruction_data.push(accounts.len() as u8);
+        for (account_label, account_data) in accounts {
+            let (
+                dsa_addr,
+                _
+            ) = find_data_storage_pda(
+                &authority_keypair.pubkey(),
+                account_label,
+                &data_storage_program_id
+            );
+
+            instruction_accounts.push(AccountMeta::new(dsa_addr, false));
+            instruction_accounts.push(AccountMeta::new_readonly(authority_keypair.pubkey(), true));
+
+            instruction_data.extend_from_slice(account_label);
+            instruction_data.extend_from_slice(&(account_data.len() as u16).to_le_bytes());
+            instruction_data.extend_from_slice(account_data);
+        };
+
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data
+        }
+    };
+
+    // success - create three accounts in one instruction
+    {
+        let accounts: [([u8; 30], &[u8]); 3] = [
+            ([ 88; 30 ], b"Solana"),
+            ([ 89; 30 ], b"Pooria"),
+            ([ 90; 30 ], b"")
+        ];
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ batch_create_instruction(&accounts) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        for (account_label, account_data) in accounts {
+            let (
+                dsa_addr,
+                dsa_bump
+            ) = find_data_storage_pda(
+                &authority_keypair.pubkey(),
+                &account_label,
+                &data_storage_program_id
+            );
+
+            let SolanaAccount { data, .. } = ptc
+                .banks_client
+                .get_account(dsa_addr)
+                .await
+                .unwrap()
+                .unwrap();
+
+            let account = DataStorageAccount::unpack(&data).unwrap();
+
+            assert!(account.is_initialized, "Invalid is_initialized.");
+            assert_eq!(account.owner, authority_keypair.pubkey(), "Invalid owner.");
+            assert_eq!(account.label, account_label, "Invalid label.");
+            assert_eq!(account.bump, dsa_bump, "Invalid bump.");
+            assert_eq!(account.data, account_data.to_vec(), "Invalid data.");
+            assert_eq!(account.data_hash, hashv(&[ account_data ]).to_bytes(), "Invalid data_hash.");
+        };
+    }
+    // success - create three accounts in one instruction
+
+    // failure - batch is bigger than 'MAX_BATCH_CREATE_LEN'
+    {
+        let accounts = (0..MAX_BATCH_CREATE_LEN as u8 + 1)
+            .map(|index| ([ 97 + index; 30 ], b"Solana".as_slice()))
+            .collect::<Vec<_>>();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ batch_create_instruction(&accounts) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    DataStorageError::InvalidData as u32
+                )
+            )
+        );
+    }
+    // failure - batch is bigger than 'MAX_BATCH_CREATE_LEN'
+}
+
+#[tokio::test]
+async fn test_create_compute_units() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let ptc = pt.start_with_context().await;
+
+    let data_storage_account_label: [u8; 30] = [ 91; 30 ];
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: [
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            b"Solana".as_slice()
+        ].concat()
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ instruction ],
+        Some(&ptc.payer.pubkey()),
+        &[
+            &ptc.payer,
+            &authority_keypair
+        ],
+        ptc.last_blockhash
+    );
+
+    let simulation_result = ptc
+        .banks_client
+        .simulate_transaction(transaction.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        simulation_result.result,
+        Some(Ok(())),
+        "Create failed!"
+    );
+
+    // compute units are only metered for program logic by the sbf runtime, this is a ceiling, not a comparison against
+    // the multi-borrow write path, so the single-borrow write's saving isn't measured here
+    #[cfg(feature = "test-sbf")]
+    assert!(
+        simulation_result
+            .simulation_details
+            .unwrap()
+            .units_consumed < 25_000,
+        "Create exceeded it's compute budget!"
+    );
+
+    // what's verified: the single-borrow write path keeps the header's byte layout
+    ptc
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let SolanaAccount { data, .. } = ptc
+        .banks_client
+        .get_account(dsa_addr)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut expected_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+    // fields the helper leaves zeroed (created-at & last-editor)
+    expected_data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + 8].copy_from_slice(&data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + 8]);
+    expected_data[LAST_EDITOR_OFFSET..LAST_EDITOR_OFFSET + 32].copy_from_slice(&authority_keypair.pubkey().to_bytes());
+
+    assert_eq!(
+        data,
+        expected_data,
+        "Invalid account data!"
+    );
+}
+
+// Compute-unit ceilings ('units_consumed' of the simulated transaction) by data-field length: create, edit (to a 100-byte
+// data-field) & close. Conservative on purpose, a handler change that crosses one is a regression to look into, not a
+// reason to raise it. Creates of 1000+ bytes don't fit a transaction (they have to be appended), so they have no ceiling.
+// NOTE: They're only asserted with 'test-sbf' ('cargo test-sbf --features test-sbf', the 'test-sbf' CI job), the native
+//       'cargo test' runtime doesn't meter program logic so there the test only checks the instructions succeed.
+const COMPUTE_UNIT_THRESHOLDS: [(usize, Option<u64>, u64, u64); 4] = [
+    (0, Some(25_000), 30_000, 20_000),
+    (100, Some(25_000), 30_000, 20_000),
+    (1_000, None, 30_000, 20_000),
+    (10_000, None, 35_000, 20_000)
+];
+
+#[tokio::test]
+async fn test_compute_unit_thresholds() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    for (i, (data_len, create_threshold, edit_threshold, close_threshold)) in COMPUTE_UNIT_THRESHOLDS.into_iter().enumerate() {
+        let [
+            create_label,
+            edit_label,
+            close_label
+        ] = [ 110, 120, 130 ].map(|label_byte: u8| [ label_byte + i as u8; 30 ]);
+
+        let [
+            create_dsa_addr,
+            edit_dsa_addr,
+            close_dsa_addr
+        ] = [ create_label, edit_label, close_label ].map(|label| {
+            find_data_storage_pda(
+                &authority_keypair.pubkey(),
+                &label,
+                &data_storage_program_id
+            ).0
+        });
+
+        //? add the (data_len bytes long) accounts to edit & close
+        for label in [ edit_label, close_label ] {
+            let (
+                dsa_addr,
+                dsa_bump
+            ) = find_data_storage_pda(
+                &authority_keypair.pubkey(),
+                &label,
+                &data_storage_program_id
+            );
+
+            let account_data = data_storage_account_data(
+                &authority_keypair.pubkey(),
+                &label,
+                dsa_bump,
+                &vec![ 7; data_len ]
+            );
+
+            ptc.set_account(
+                &dsa_addr,
+                &AccountSharedData::from(
+                    SolanaAccount {
+                        lamports: rent.minimum_balance(account_data.len()),
+                        data: account_data,
+                        owner: data_storage_program_id,
+                        rent_epoch: Epoch::default(),
+                        executable: false
+                    }
+                )
+            );
+        };
+        //? add the (data_len bytes long) accounts to edit & close
+
+        let mut instructions = vec![
+            (
+                "Edit",
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new(edit_dsa_addr, false),
+                        AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                        AccountMeta::new(ptc.payer.pubkey(), true),
+                        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+                    ],
+                    data: [
+                        encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                        [ 8; 100 ].as_slice()
+                    ].concat()
+                },
+                edit_threshold
+            ),
+            (
+                "Close",
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new(close_dsa_addr, false),
+                        AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                        AccountMeta::new(ptc.payer.pubkey(), false)
+                    ],
+                    data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+                },
+                close_threshold
+            )
+        ];
+        if let Some(create_threshold) = create_threshold {
+            instructions.push(
+                (
+                    "Create",
+                    Instruction {
+                        program_id: data_storage_program_id,
+                        accounts: vec![
+                            AccountMeta::new(create_dsa_addr, false),
+                            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                            AccountMeta::new(ptc.payer.pubkey(), true),
+                            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+                        ],
+                        data: [
+                            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                            create_label.as_slice(),
+                            &vec![ 7; data_len ]
+                        ].concat()
+                    },
+                    create_threshold
+                )
+            );
+        };
+
+        for (name, instruction, threshold) in instructions {
+            let transaction = Transaction::new_signed_with_payer(
+                &[ instruction ],
+                Some(&ptc.payer.pubkey()),
+                &[
+                    &ptc.payer,
+                    &authority_keypair
+                ],
+                ptc.last_blockhash
+            );
+
+            let simulation_result = ptc
+                .banks_client
+                .simulate_transaction(transaction)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                simulation_result.result,
+                Some(Ok(())),
+                "{} of a {} bytes data-field failed!",
+                name,
+                data_len
+            );
+
+            // compute units are only metered for program logic by the sbf runtime
+            #[cfg(feature = "test-sbf")]
+            assert!(
+                simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .units_consumed < threshold,
+                "{} of a {} bytes data-field exceeded it's compute-unit threshold!",
+                name,
+                data_len
+            );
+            #[cfg(not(feature = "test-sbf"))]
+            let _ = threshold;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_migrate_data_storage_account_layout() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    //? add a version '1' (original layout) account, built byte by byte
+    let rent = Rent::default();
+    let label = [ 92; 30 ];
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &label,
+        &data_storage_program_id
+    );
+
+    let mut old_dsa_data = vec![];
+    old_dsa_data.extend_from_slice(authority_keypair.pubkey().as_ref());
+    old_dsa_data.extend_from_slice(&label);
+    old_dsa_data.extend_from_slice(&1_700_000_000_i64.to_le_bytes());
+    old_dsa_data.push(dsa_bump);
+    old_dsa_data.push(true as u8);
+    old_dsa_data.extend_from_slice(&6_u16.to_le_bytes());
+    assert_eq!(old_dsa_data.len(), V1_HEADER_LEN);
+    old_dsa_data.extend_from_slice(b"Solana");
+
+    pt.add_account(
+        dsa_addr,
+        SolanaAccount {
+            lamports: rent.minimum_balance(old_dsa_data.len()),
+            data: old_dsa_data.clone(),
+            owner: data_storage_program_id,
+            executable: false,
+            rent_epoch: Epoch::default()
+        }
+    );
+    //? add a version '1' (original layout) account, built byte by byte
+
+    let mut ptc = pt.start_with_context().await;
+    let payer = ptc.payer.insecure_clone();
+
+    let migrate_instruction = |dsa_addr: Pubkey| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ],
+        data: encode_instruction_discriminator(MIGRATE_DATA_STORAGE_ACCOUNT_LAYOUT_INSTRUCTION_DISCRIMINATOR)
+    };
+
+    // failure - the authority must sign
+    {
+        let mut instruction = migrate_instruction(dsa_addr);
+        instruction.accounts[1].is_signer = false;
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer.pubkey()),
+            &[ &payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::MissingRequiredSignature
+            )
+        );
+    }
+    // failure - the authority must sign
+
+    // success - migrate the version '1' account, the new fields are defaulted & the data-field is kept
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ migrate_instruction(dsa_addr) ],
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            HEADER_LEN + 6,
+            "Invalid data length."
+        );
+
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN + 6),
+            "Invalid lamports."
+        );
+
+        assert_eq!(
+            data[..V1_HEADER_LEN],
+            old_dsa_data[..V1_HEADER_LEN],
+            "The old header must be kept."
+        );
+
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert_eq!(account.header_version, HEADER_VERSION, "Invalid header_version.");
+        assert_eq!(account.display_label, label, "Invalid display_label.");
+        assert_eq!(account.data_hash, hashv(&[ b"Solana" ]).to_bytes(), "Invalid data_hash.");
+        assert_eq!(account.layout_version, LAYOUT_VERSION_FIXED_LABEL, "Invalid layout_version.");
+        assert_eq!(account.max_size, 0, "Invalid max_size.");
+        assert_eq!(account.revision, 0, "Invalid revision.");
+        assert!(!account.tombstone, "Invalid tombstone.");
+        assert!(!account.compressed, "Invalid compressed.");
+        assert_eq!(account.kind, ACCOUNT_KIND_BYTES, "Invalid kind.");
+        assert_eq!(account.lock_until, 0, "Invalid lock_until.");
+        assert_eq!(account.account_discriminator, DATA_STORAGE_ACCOUNT_DISCRIMINATOR, "Invalid account_discriminator.");
+        assert_eq!(account.data, b"Solana".to_vec(), "Invalid data.");
+    }
+
+    // success - migrating a current account is a no-op
+    {
+        ptc.get_new_latest_blockhash().await.unwrap();
+
+        let SolanaAccount { data: old_data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ migrate_instruction(dsa_addr) ],
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data,
+            old_data,
+            "A current account must not change."
+        );
+    }
+    // success - migrating a current account is a no-op
+}
+
+#[tokio::test]
+async fn test_compressed_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+    let payer = ptc.payer.insecure_clone();
+
+    // stands in for a client-side compressed blob, the program stores it as-is
+    let compressed_data = [ 0x78, 0x9c, 0x0b, 0xce, 0xcf, 0x49, 0xcc, 0x4b, 0x04, 0x00 ];
+
+    // (label, create discriminator, data-field, compressed)
+    let accounts = [
+        ([ 94; 30 ], CREATE_NEW_COMPRESSED_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR, compressed_data.as_slice(), true),
+        ([ 95; 30 ], CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR, b"Solana".as_slice(), false)
+    ];
+
+    let read_return_data = async |ptc: &mut ProgramTestContext, dsa_addr: Pubkey| {
+        ptc.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![ AccountMeta::new_readonly(dsa_addr, false) ],
+                    data: encode_instruction_discriminator(READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR)
+                }
+            ],
+            Some(&payer.pubkey()),
+            &[ &payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap()
+            .simulation_details
+            .unwrap()
+            .return_data
+            .unwrap()
+            .data
+    };
+
+    // success - create a compressed and an uncompressed account, the flag round-trips through unpack & read
+    for (label, discriminator, account_data, compressed) in accounts {
+        let (
+            dsa_addr,
+            _
+        ) = find_data_storage_pda(
+            &authority_keypair.pubkey(),
+            &label,
+            &data_storage_program_id
+        );
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(discriminator).as_slice(),
+                label.as_slice(),
+                u32::to_le_bytes(0).as_slice(),
+                i64::to_le_bytes(0).as_slice(),
+                account_data
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert_eq!(account.compressed, compressed, "Invalid compressed.");
+        assert_eq!(account.data, account_data.to_vec(), "The data-field must be stored as-is.");
+        assert_eq!(
+            data.len(),
+            HEADER_LEN + account_data.len(),
+            "Invalid data length."
+        );
+
+        assert_eq!(
+            read_return_data(&mut ptc, dsa_addr).await,
+            [ &[ compressed as u8 ], account_data ].concat(),
+            "Invalid return-data."
+        );
+    };
+    // success - create a compressed and an uncompressed account, the flag round-trips through unpack & read
+
+    // success - edits set the flag to 'EDIT_FLAG_COMPRESSED'
+    let (
+        dsa_addr,
+        _
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &accounts[1].0,
+        &data_storage_program_id
+    );
+    for (edit_flags, compressed) in [ (EDIT_FLAG_COMPRESSED, true), (0, false) ] {
+        ptc.get_new_latest_blockhash().await.unwrap();
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                &[ edit_flags ],
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            DataStorageAccount::unpack(&data).unwrap().compressed,
+            compressed,
+            "Invalid compressed."
+        );
+    };
+    // success - edits set the flag to 'EDIT_FLAG_COMPRESSED'
+
+    // success - compare-and-swap keeps the compressed flag
+    {
+        let (
+            dsa_addr,
+            _
+        ) = find_data_storage_pda(
+            &authority_keypair.pubkey(),
+            &accounts[0].0,
+            &data_storage_program_id
+        );
+        let new_compressed_data = [ 0x78, 0x9c, 0x0b, 0xc8, 0xcf, 0xcf, 0x02, 0x00 ];
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(payer.pubkey(), false)
+            ],
+            data: [
+                encode_instruction_discriminator(COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                hashv(&[ compressed_data.as_slice() ]).to_bytes().as_slice(),
+                new_compressed_data.as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert!(account.compressed, "The compressed flag must be kept.");
+        assert_eq!(account.data, new_compressed_data.to_vec(), "Invalid data.");
+    }
+    // success - compare-and-swap keeps the compressed flag
+}
+
+#[tokio::test]
+async fn test_compare_and_swap_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    //? add data-storage account
+    let data_storage_account_label: [u8; 30] = [ 96; 30 ];
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+    pt.add_account(
+        dsa_addr,
+        SolanaAccount {
+            data: data_storage_account_data(
+                &authority_keypair.pubkey(),
+                &data_storage_account_label,
+                dsa_bump,
+                b"Solana"
+            ),
+            owner: data_storage_program_id,
+            lamports: sol_to_lamports(1.0),
+            rent_epoch: Epoch::default(),
+            executable: false
+        }
+    );
+    //? add data-storage account
+
+    let mut ptc = pt.start_with_context().await;
+    let payer = ptc.payer.insecure_clone();
+
+    let compare_and_swap_instruction = |expected_data: &[u8], new_data: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+        ],
+        data: [
+            encode_instruction_discriminator(COMPARE_AND_SWAP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            hashv(&[ expected_data ]).to_bytes().as_slice(),
+            new_data
+        ].concat()
+    };
+
+    // success - the expected hash matches the current data-field
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ compare_and_swap_instruction(b"Solana", b"Pooria") ],
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert_eq!(account.data, b"Pooria".to_vec(), "Invalid data.");
+        assert_eq!(account.data_hash, hashv(&[ b"Pooria" ]).to_bytes(), "Invalid data_hash.");
+        assert_eq!(account.revision, 1, "Invalid revision.");
+    }
+    // success - the expected hash matches the current data-field
+
+    // failure - stale expected hash, the account isn't modified
+    {
+        ptc.get_new_latest_blockhash().await.unwrap();
+
+        let SolanaAccount { data: old_data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ compare_and_swap_instruction(b"Solana", b"Stale") ],
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::HashMismatch as u32)
+            )
+        );
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data,
+            old_data,
+            "A failed compare-and-swap must not modify the account."
+        );
+    }
+    // failure - stale expected hash, the account isn't modified
+
+    // failure - the expected hash is missing
+    {
+        let mut instruction = compare_and_swap_instruction(b"Pooria", b"");
+        instruction.data.truncate(1 + 31);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::InvalidData as u32)
+            )
+        );
+    }
+    // failure - the expected hash is missing
+}
+
+#[tokio::test]
+async fn test_top_up_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add funder account, anyone can top up an account
+    let funder_keypair = Keypair::new();
+    pt.add_account(
+        funder_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add funder account, anyone can top up an account
+
+    //? add an underfunded data-storage account
+    let authority = Pubkey::new_unique();
+    let data_storage_account_label: [u8; 30] = [ 97; 30 ];
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &authority,
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let dsa_data = data_storage_account_data(
+        &authority,
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+    let rent = Rent::default();
+    let rent_exempt_lamports = rent.minimum_balance(dsa_data.len());
+    pt.add_account(
+        dsa_addr,
+        SolanaAccount {
+            lamports: rent_exempt_lamports - 1_000,
+            data: dsa_data,
+            owner: data_storage_program_id,
+            executable: false,
+            rent_epoch: Epoch::default()
+        }
+    );
+    //? add an underfunded data-storage account
+
+    let mut ptc = pt.start_with_context().await;
+    let payer = ptc.payer.insecure_clone();
+
+    let top_up_instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new(funder_keypair.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ],
+        data: encode_instruction_discriminator(TOP_UP_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+    };
+
+    // success - the shortfall is transferred, the account is exactly rent-exempt
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&top_up_instruction),
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &funder_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountToppedUp {
+            data_storage_account: dsa_addr,
+            funder_account: funder_keypair.pubkey(),
+            lamports: 1_000
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            rent_exempt_lamports,
+            "The account must be exactly rent-exempt."
+        );
+
+        let SolanaAccount { lamports, .. } = ptc
+            .banks_client
+            .get_account(funder_keypair.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            sol_to_lamports(1.0) - 1_000,
+            "Invalid funder's lamports."
+        );
+    }
+    // success - the shortfall is transferred, the account is exactly rent-exempt
+
+    // success - a rent-exempt account is left as-is
+    {
+        ptc.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&top_up_instruction),
+            Some(&payer.pubkey()),
+            &[
+                &payer,
+                &funder_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            rent_exempt_lamports,
+            "A rent-exempt account must not be topped up."
+        );
+    }
+    // success - a rent-exempt account is left as-is
+}
+
+#[tokio::test]
+async fn test_append_up_to_max_size() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data storage account
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(77);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority_keypair.pubkey().as_ref(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let mut account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+    account_data[MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + 4].copy_from_slice(&u32::to_le_bytes(10));
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data storage account
+
+    let payer = ptc.payer.pubkey();
+    let append_instruction = |data: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ],
+        data: [
+            encode_instruction_discriminator(APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data
+        ].concat()
+    };
+
+    // success - append up to the max-size
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ append_instruction(b"Rust") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"SolanaRust",
+            "Invalid data."
+        );
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN + 10),
+            "Invalid data_storage_account's lamport balance."
+        );
+    }
+    // success - append up to the max-size
+
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - append one byte over the max-size
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ append_instruction(b"!") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::QuotaExceeded as u32)
+            )
+        );
+    }
+    // failure - append one byte over the max-size
+}
+
+#[tokio::test]
+async fn test_create_funded_by_caller_pda() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let factory_program_id = Pubkey::new_from_array([2; 32]);
+
+    let mut pt = setup(&data_storage_program_id);
+    pt.add_program(
+        "factory_program",
+        factory_program_id,
+        processor!(cpi_caller_process_instruction)
+    );
+
+    //? add factory's funder pda
+    let (
+        funder_addr,
+        _
+    ) = Pubkey::find_program_address(&[ CPI_CALLER_SIGNER_SEED ], &factory_program_id);
+    pt.add_account(
+        funder_addr,
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add factory's funder pda
+
+    let user_keypair = Keypair::new();
+
+    let ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(70);
+
+    let (
+        dsa_addr,
+        _
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            user_keypair.pubkey().as_ref(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    // success - the factory's pda funds the account for the user
+    {
+        let instruction = Instruction {
+            program_id: factory_program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(data_storage_program_id, false),
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(user_keypair.pubkey(), false),
+                AccountMeta::new(funder_addr, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(FACTORY_CREATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                u32::to_le_bytes(0).as_slice(),
+                b"Solana".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, owner, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            owner,
+            data_storage_program_id,
+            "Invalid data_storage_account's owner."
+        );
+        assert_eq!(
+            lamports,
+            rent.minimum_balance(HEADER_LEN + 6),
+            "Invalid data_storage_account's lamport balance."
+        );
+        assert_eq!(
+            data.get(DATA_OFFSET..).unwrap(),
+            b"Solana".as_slice(),
+            "Invalid data."
+        );
+
+        let funder_account = ptc
+            .banks_client
+            .get_account(funder_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            funder_account.lamports,
+            sol_to_lamports(1.0) - rent.minimum_balance(HEADER_LEN + 6),
+            "The funder pda must pay the account's rent."
+        );
+    }
+    // success - the factory's pda funds the account for the user
+}
+
+#[tokio::test]
+async fn test_rent_for_data() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let ptc = setup(&data_storage_program_id).start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    // header-only account
+    assert_eq!(
+        rent_for_data(0, &rent),
+        rent.minimum_balance(HEADER_LEN),
+        "Invalid header-only rent."
+    );
+
+    let data_storage_account_label = [ 78; 30 ];
+    let data_storage_account_data = b"Solana Data Storage";
+    let (dsa_addr, _) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            ptc.payer.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(ptc.payer.pubkey(), true),
+                    AccountMeta::new(ptc.payer.pubkey(), true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+                ],
+                data: [
+                    encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    data_storage_account_label.as_slice(),
+                    data_storage_account_data
+                ].concat()
+            }
+        ],
+        Some(&ptc.payer.pubkey()),
+        &[ &ptc.payer ],
+        ptc.last_blockhash
+    );
+
+    ptc
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let dsa_account = ptc
+        .banks_client
+        .get_account(dsa_addr)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // exactly what the create charged
+    assert_eq!(
+        dsa_account.lamports,
+        rent_for_data(data_storage_account_data.len(), &rent),
+        "Invalid rent for the data-field."
+    );
+    assert_eq!(
+        dsa_account.lamports,
+        rent.minimum_balance(dsa_account.data.len()),
+        "The created account must be exactly rent-exempt."
+    );
+}
+
+#[tokio::test]
+async fn test_key_value_entries() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut ptc = setup(&data_storage_program_id).start_with_context().await;
+    let payer = ptc.payer.pubkey();
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    let dsa_address = |label: &[u8; 30]| find_data_storage_pda(&payer, label, &data_storage_program_id).0;
+    let create_instruction = |discriminator: u8, label: &[u8; 30], data: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_address(label), false),
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: [
+            encode_instruction_discriminator(discriminator).as_slice(),
+            label.as_slice(),
+            u32::to_le_bytes(0).as_slice(),
+            i64::to_le_bytes(0).as_slice(),
+            data
+        ].concat()
+    };
+    // grows pass the funding account & system program, shrinks the rent-receiver
+    let entry_instruction = |discriminator: u8, label: &[u8; 30], payload: &[u8], grow: bool| {
+        let mut accounts = vec![
+            AccountMeta::new(dsa_address(label), false),
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new(payer, true)
+        ];
+        if grow {
+            accounts.push(AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false));
+        };
+
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts,
+            data: [
+                encode_instruction_discriminator(discriminator).as_slice(),
+                payload
+            ].concat()
+        }
+    };
+    let set_entry = |key: &[u8], value: &[u8]| [ &[ key.len() as u8 ], key, value ].concat();
+
+    let kv_label = [ 79; 30 ];
+    let bytes_label = [ 80; 30 ];
+
+    // success - create an empty key-value account & a bytes account
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                create_instruction(CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR, &kv_label, &[]),
+                create_instruction(CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR, &bytes_label, b"Solana")
+            ],
+            Some(&payer),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_address(&kv_label))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            DataStorageAccount::unpack(&data).unwrap().kind,
+            ACCOUNT_KIND_KEY_VALUE,
+            "Invalid kind."
+        );
+    }
+    // success - create an empty key-value account & a bytes account
+
+    // success - set three keys, overwrite one & remove one, the data-field stays a well-formed entry list
+    for (instruction, expected_entries) in [
+        (
+            entry_instruction(SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR, &kv_label, &set_entry(b"a", b"1"), true),
+            vec![ (b"a".as_slice(), b"1".as_slice()) ]
+        ),
+        (
+            entry_instruction(SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR, &kv_label, &set_entry(b"bb", b"22"), true),
+            vec![ (b"a".as_slice(), b"1".as_slice()), (b"bb", b"22") ]
+        ),
+        (
+            entry_instruction(SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR, &kv_label, &set_entry(b"ccc", b"333"), true),
+            vec![ (b"a".as_slice(), b"1".as_slice()), (b"bb", b"22"), (b"ccc", b"333") ]
+        ),
+        (
+            entry_instruction(SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR, &kv_label, &set_entry(b"bb", b"2"), false),
+            vec![ (b"a".as_slice(), b"1".as_slice()), (b"bb", b"2"), (b"ccc", b"333") ]
+        ),
+        (
+            entry_instruction(REMOVE_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR, &kv_label, b"a", false),
+            vec![ (b"bb".as_slice(), b"2".as_slice()), (b"ccc", b"333") ]
+        )
+    ] {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_address(&kv_label))
+            .await
+            .unwrap()
+            .unwrap();
+
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert_eq!(
+            read_entries(&account.data).unwrap(),
+            expected_entries,
+            "Invalid entries."
+        );
+        assert_eq!(
+            data.len(),
+            HEADER_LEN + account.data_len as usize,
+            "The account must be resized to the data-field."
+        );
+        assert_eq!(
+            lamports,
+            rent_for_data(account.data_len as usize, &rent),
+            "Invalid lamports."
+        );
+    };
+    // success - set three keys, overwrite one & remove one, the data-field stays a well-formed entry list
+
+    // success - get a key's value as the return-data
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(dsa_address(&kv_label), false)
+                    ],
+                    data: [
+                        encode_instruction_discriminator(GET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                        b"ccc"
+                    ].concat()
+                }
+            ],
+            Some(&payer),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let return_data = ptc
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap()
+            .simulation_details
+            .unwrap()
+            .return_data
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            return_data,
+            b"333".to_vec(),
+            "Invalid return-data."
+        );
+    }
+    // success - get a key's value as the return-data
+
+    // the removal below repeats an already processed transaction
+    ptc
+        .get_new_latest_blockhash()
+        .await
+        .unwrap();
+
+    // failure - missing keys, bytes accounts & malformed entry lists
+    for (instruction, expected_error) in [
+        (
+            entry_instruction(REMOVE_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR, &kv_label, b"a", false),
+            DataStorageError::EntryNotFound
+        ),
+        (
+            entry_instruction(GET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR, &kv_label, b"a", false),
+            DataStorageError::EntryNotFound
+        ),
+        (
+            entry_instruction(SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR, &bytes_label, &set_entry(b"a", b"1"), true),
+            DataStorageError::NotKeyValueAccount
+        ),
+        (
+            entry_instruction(SET_DATA_STORAGE_ACCOUNT_ENTRY_INSTRUCTION_DISCRIMINATOR, &kv_label, &set_entry(b"", b"1"), true),
+            DataStorageError::InvalidData
+        ),
+        (
+            create_instruction(CREATE_NEW_KEY_VALUE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR, &[ 81; 30 ], &[ 3, b'a' ]),
+            DataStorageError::InvalidData
+        )
+    ] {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(expected_error as u32)
+            ),
+            "Invalid error."
+        );
+    };
+    // failure - missing keys, bytes accounts & malformed entry lists
+}
+
+#[tokio::test]
+async fn test_raw_mutators_reject_non_bytes_accounts() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    //? add a key-value & a chunked data storage account
+    let mut dsa_addresses = vec![];
+    for (label, kind) in [ ([ 82; 30 ], ACCOUNT_KIND_KEY_VALUE), ([ 83; 30 ], ACCOUNT_KIND_CHUNKED) ] {
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = find_data_storage_pda(&authority_keypair.pubkey(), &label, &data_storage_program_id);
+
+        let mut account_data = data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &label,
+            dsa_bump,
+            b"Solana"
+        );
+        account_data[KIND_OFFSET] = kind;
+
+        pt.add_account(
+            dsa_addr,
+            SolanaAccount {
+                lamports: sol_to_lamports(1.0),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        );
+
+        dsa_addresses.push(dsa_addr);
+    };
+    //? add a key-value & a chunked data storage account
+
+    let ptc = pt.start_with_context().await;
+
+    // failure - edit, append, truncate, patch & the patch-log's append & compact only write to bytes accounts
+    for dsa_addr in dsa_addresses {
+        let (patch_log_addr, _) = Pubkey::find_program_address(
+            &[
+                PATCH_LOG_SEED_PREFIX,
+                dsa_addr.as_ref()
+            ],
+            &data_storage_program_id
+        );
+
+        for (data, third_account_addr) in [
+            ([ encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(), b"Solana".as_slice() ].concat(), ptc.payer.pubkey()),
+            ([ encode_instruction_discriminator(APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(), b"Rust".as_slice() ].concat(), ptc.payer.pubkey()),
+            ([ encode_instruction_discriminator(TRUNCATE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(), u16::to_le_bytes(1).as_slice() ].concat(), ptc.payer.pubkey()),
+            ([ encode_instruction_discriminator(PATCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(), u16::to_le_bytes(0).as_slice(), b"S".as_slice() ].concat(), ptc.payer.pubkey()),
+            (DataStorageInstruction::AppendPatchLog { offset: 0, data: b"S".to_vec() }.pack(), patch_log_addr),
+            (DataStorageInstruction::CompactPatchLog.pack(), patch_log_addr)
+        ] {
+            let instruction = Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(third_account_addr, false)
+                ],
+                data
+            };
+
+            let transaction = Transaction::new_signed_with_payer(
+                &[ instruction ],
+                Some(&ptc.payer.pubkey()),
+                &[ &ptc.payer, &authority_keypair ],
+                ptc.last_blockhash
+            );
+
+            let error = ptc
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap();
+
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(DataStorageError::NotBytesAccount as u32)
+                ),
+                "Invalid error."
+            );
+        };
+    };
+    // failure - edit, append, truncate, patch & the patch-log's append & compact only write to bytes accounts
+}
+
+#[tokio::test]
+async fn test_touch_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let data_storage_account_label: [u8; 30] = [ 85; 30 ];
+
+    //? add data storage accounts
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let account_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        dsa_bump,
+        b"Solana"
+    );
+
+    pt.add_account(
+        dsa_addr,
+        SolanaAccount {
+            lamports: sol_to_lamports(1.0),
+            data: account_data.clone(),
+            owner: data_storage_program_id,
+            rent_epoch: Epoch::default(),
+            executable: false
+        }
+    );
+
+    let (
+        immutable_dsa_addr,
+        immutable_dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            SYSTEM_PROGRAM_ID.to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    pt.add_account(
+        immutable_dsa_addr,
+        SolanaAccount {
+            lamports: sol_to_lamports(1.0),
+            data: data_storage_account_data(
+                &SYSTEM_PROGRAM_ID,
+                &data_storage_account_label,
+                immutable_dsa_bump,
+                b"Solana"
+            ),
+            owner: data_storage_program_id,
+            rent_epoch: Epoch::default(),
+            executable: false
+        }
+    );
+    //? add data storage accounts
+
+    let mut ptc = pt.start_with_context().await;
+
+    // success - only last-updated moves forward
+    for current_time in [ 500, 900 ] {
+        // the second touch repeats an already processed transaction
+        ptc
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+
+        ptc
+            .set_sysvar::<Clock>(
+                &Clock {
+                    unix_timestamp: current_time,
+                    ..Clock::default()
+                }
+            );
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: encode_instruction_discriminator(TOUCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            i64::from_le_bytes(*array_ref![ data, LAST_UPDATED_OFFSET, 8 ]),
+            current_time,
+            "Invalid last_updated."
+        );
+        assert_eq!(
+            &data[..LAST_UPDATED_OFFSET],
+            &account_data[..LAST_UPDATED_OFFSET],
+            "Header must not change."
+        );
+        assert_eq!(
+            &data[LAST_UPDATED_OFFSET + 8..],
+            &account_data[LAST_UPDATED_OFFSET + 8..],
+            "Header & data must not change."
+        );
+    };
+    // success - only last-updated moves forward
+
+    // failure - immutable data storage account
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(immutable_dsa_addr, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+            ],
+            data: encode_instruction_discriminator(TOUCH_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::ImmutableDataStorage as u32)
+            )
+        );
+    }
+    // failure - immutable data storage account
+}
+
+#[tokio::test]
+async fn test_immutable_window() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let data_storage_account_label: [u8; 30] = [ 87; 30 ];
+
+    //? add data storage account
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    pt.add_account(
+        dsa_addr,
+        SolanaAccount {
+            lamports: sol_to_lamports(1.0),
+            data: data_storage_account_data(
+                &authority_keypair.pubkey(),
+                &data_storage_account_label,
+                dsa_bump,
+                b"Solana"
+            ),
+            owner: data_storage_program_id,
+            rent_epoch: Epoch::default(),
+            executable: false
+        }
+    );
+    //? add data storage account
+
+    let mut ptc = pt.start_with_context().await;
+
+    ptc
+        .set_sysvar::<Clock>(
+            &Clock {
+                unix_timestamp: 100,
+                ..Clock::default()
+            }
+        );
+
+    let set_window_instruction = |lock_until: i64| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+        ],
+        data: DataStorageInstruction::SetImmutableWindow { lock_until }.pack()
+    };
+    let edit_instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+        ],
+        data: [
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            b"Pooria".as_slice()
+        ].concat()
+    };
+
+    // failure - the window must end in the future
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ set_window_instruction(100) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::InvalidData as u32)
+            )
+        );
+    }
+    // failure - the window must end in the future
+
+    // success - authority sets the window
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ set_window_instruction(200) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            i64::from_le_bytes(*array_ref![ data, LOCK_UNTIL_OFFSET, 8 ]),
+            200,
+            "Invalid lock_until."
+        );
+    }
+    // success - authority sets the window
+
+    // failure - editing, appending & closing before the unlock
+    for instruction in [
+        edit_instruction.clone(),
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"!".as_slice()
+            ].concat()
+        },
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(authority_keypair.pubkey(), false)
+            ],
+            data: encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+        }
+    ] {
+        ptc
+            .set_sysvar::<Clock>(
+                &Clock {
+                    unix_timestamp: 199,
+                    ..Clock::default()
+                }
+            );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::TimeLocked as u32)
+            )
+        );
+    };
+    // failure - editing, appending & closing before the unlock
+
+    // failure - an active window can't be shortened
+    {
+        ptc
+            .set_sysvar::<Clock>(
+                &Clock {
+                    unix_timestamp: 120,
+                    ..Clock::default()
+                }
+            );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ set_window_instruction(150) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::TimeLocked as u32)
+            )
+        );
+    }
+    // failure - an active window can't be shortened
+
+    // success - editing after the unlock
+    {
+        // the failed edit has already been processed
+        ptc
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+
+        ptc
+            .set_sysvar::<Clock>(
+                &Clock {
+                    unix_timestamp: 200,
+                    ..Clock::default()
+                }
+            );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ edit_instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            &data[DATA_OFFSET..],
+            b"Pooria",
+            "Invalid data."
+        );
+    }
+    // success - editing after the unlock
+}
+
+#[tokio::test]
+async fn test_rename_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let old_label: [u8; 30] = [ 79; 30 ];
+    let new_label: [u8; 30] = [ 78; 30 ];
+    let taken_label: [u8; 30] = [ 84; 30 ];
+
+    let derive_dsa = |label: &[u8; 30]| Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            label
+        ],
+        &data_storage_program_id
+    );
+
+    let (old_dsa_addr, old_dsa_bump) = derive_dsa(&old_label);
+    let (new_dsa_addr, new_dsa_bump) = derive_dsa(&new_label);
+    let (taken_dsa_addr, taken_dsa_bump) = derive_dsa(&taken_label);
+
+    //? add data storage accounts
+    for (addr, label, bump) in [
+        (old_dsa_addr, old_label, old_dsa_bump),
+        (taken_dsa_addr, taken_label, taken_dsa_bump)
+    ] {
+        pt.add_account(
+            addr,
+            SolanaAccount {
+                lamports: sol_to_lamports(1.0),
+                data: data_storage_account_data(
+                    &authority_keypair.pubkey(),
+                    &label,
+                    bump,
+                    b"Solana"
+                ),
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        );
+    };
+    //? add data storage accounts
+
+    let ptc = pt.start_with_context().await;
+
+    let rename_instruction = |new_dsa_addr: Pubkey, label: &[u8; 30]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(old_dsa_addr, false),
+            AccountMeta::new(new_dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ],
+        data: [
+            encode_instruction_discriminator(RENAME_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            label.as_slice()
+        ].concat()
+    };
+
+    // failure - the new label is already taken
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ rename_instruction(taken_dsa_addr, &taken_label) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::AccountAlreadyInitialized
+            ),
+            "Invalid error."
+        );
+    }
+    // failure - the new label is already taken
+
+    // success
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ rename_instruction(new_dsa_addr, &new_label) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        // validate emitted event
+        let simulation_result = ptc
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+
+        let event = Events::DataStorageAccountRenamed {
+            old_data_storage_account: old_dsa_addr,
+            data_storage_account: new_dsa_addr,
+            account_label: new_label
+        };
+        assert!(
+            emitted_events(
+                &simulation_result
+                    .simulation_details
+                    .unwrap()
+                    .logs
+            ).contains(&event),
+            "Invalid emitted event!"
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert!(
+            ptc.banks_client.get_account(old_dsa_addr).await.unwrap().is_none(),
+            "Old data storage account must be closed."
+        );
+
+        let SolanaAccount { data, owner, .. } = ptc
+            .banks_client
+            .get_account(new_dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            owner,
+            data_storage_program_id,
+            "Invalid owner."
+        );
+        assert_eq!(
+            data,
+            data_storage_account_data(
+                &authority_keypair.pubkey(),
+                &new_label,
+                new_dsa_bump,
+                b"Solana"
+            ),
+            "Only the label & bump must change."
+        );
+    }
+    // success
+}
+
+#[tokio::test]
+async fn test_chunked_data_storage_account() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    //? add data storage accounts
+    let mut dsa_addrs = Vec::new();
+    for (label, data) in [
+        ([ 67; 30 ], b"".as_slice()),
+        ([ 66; 30 ], b"Solana".as_slice())
+    ] {
+        let (
+            dsa_addr,
+            dsa_bump
+        ) = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority_keypair.pubkey().to_bytes().as_slice(),
+                &label
+            ],
+            &data_storage_program_id
+        );
+
+        pt.add_account(
+            dsa_addr,
+            SolanaAccount {
+                lamports: sol_to_lamports(1.0),
+                data: data_storage_account_data(
+                    &authority_keypair.pubkey(),
+                    &label,
+                    dsa_bump,
+                    data
+                ),
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        );
+
+        dsa_addrs.push(dsa_addr);
+    };
+    let (dsa_addr, bytes_dsa_addr) = (dsa_addrs[0], dsa_addrs[1]);
+    //? add data storage accounts
+
+    let ptc = pt.start_with_context().await;
+    let payer = ptc.payer.pubkey();
+
+    // a 25 KiB payload, spread over 3 chunks
+    let payload: Vec<u8> = (0..25 * 1024).map(|index| (index % 251) as u8).collect();
+    let chunk_addrs: Vec<Pubkey> = (0..3)
+        .map(|chunk_index| find_chunk_pda(&dsa_addr, chunk_index, &data_storage_program_id).0)
+        .collect();
+
+    let write_instruction = |dsa_addr: Pubkey, chunk_index: u16, offset: u16, bytes: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(chunk_addrs[chunk_index as usize], false)
+        ],
+        data: DataStorageInstruction::WriteChunk {
+            chunk_index,
+            offset,
+            data: bytes.to_vec()
+        }.pack()
+    };
+    let read_instruction = |dsa_addr: Pubkey, chunk_index: u16, offset: u16| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(dsa_addr, false),
+            AccountMeta::new_readonly(chunk_addrs[chunk_index as usize], false)
+        ],
+        data: DataStorageInstruction::ReadChunk { chunk_index, offset }.pack()
+    };
+
+    // success - allocate the chunks
+    {
+        let mut accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ];
+        accounts.extend(chunk_addrs.iter().map(|chunk_addr| AccountMeta::new(*chunk_addr, false)));
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts,
+            data: DataStorageInstruction::AllocateChunks { total_len: payload.len() as u32 }.pack()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert_eq!(
+            account.kind,
+            ACCOUNT_KIND_CHUNKED,
+            "Invalid account kind."
+        );
+        assert_eq!(
+            account.data,
+            [ u32::to_le_bytes(25 * 1024).as_slice(), u16::to_le_bytes(3).as_slice() ].concat(),
+            "Invalid chunks metadata."
+        );
+
+        for (chunk_addr, chunk_len) in chunk_addrs.iter().zip([ CHUNK_LEN, CHUNK_LEN, 25 * 1024 - 2 * CHUNK_LEN ]) {
+            let SolanaAccount { data, owner, .. } = ptc
+                .banks_client
+                .get_account(*chunk_addr)
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(
+                (data.len(), owner),
+                (chunk_len, data_storage_program_id),
+                "Invalid chunk account."
+            );
+        };
+    }
+    // success - allocate the chunks
+
+    // success - write the payload
+    for (chunk_index, chunk) in payload.chunks(CHUNK_LEN).enumerate() {
+        for (piece_index, piece) in chunk.chunks(900).enumerate() {
+            let transaction = Transaction::new_signed_with_payer(
+                &[ write_instruction(dsa_addr, chunk_index as u16, (piece_index * 900) as u16, piece) ],
+                Some(&payer),
+                &[
+                    &ptc.payer,
+                    &authority_keypair
+                ],
+                ptc.last_blockhash
+            );
+
+            ptc
+                .banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap();
+        };
+    };
+    // success - write the payload
+
+    // success - read the payload back
+    {
+        let mut read_payload = Vec::new();
+        for chunk_index in 0..3 {
+            let mut offset = 0;
+            loop {
+                let transaction = Transaction::new_signed_with_payer(
+                    &[ read_instruction(dsa_addr, chunk_index, offset) ],
+                    Some(&payer),
+                    &[ &ptc.payer ],
+                    ptc.last_blockhash
+                );
+
+                let return_data = ptc
+                    .banks_client
+                    .simulate_transaction(transaction)
+                    .await
+                    .unwrap()
+                    .simulation_details
+                    .unwrap()
+                    .return_data
+                    .map(|return_data| return_data.data)
+                    .unwrap_or_default();
+                if return_data.is_empty() {
+                    break;
+                };
+
+                offset += return_data.len() as u16;
+                read_payload.extend(return_data);
+            };
+        };
+
+        assert!(
+            read_payload == payload,
+            "Invalid read payload."
+        );
+    }
+    // success - read the payload back
+
+    // failure - out of chunk bounds, wrong chunks & non-chunked accounts
+    for (instruction, expected_error) in [
+        (
+            write_instruction(dsa_addr, 2, (25 * 1024 - 2 * CHUNK_LEN - 1) as u16, b"ab"),
+            InstructionError::Custom(DataStorageError::InvalidData as u32)
+        ),
+        (
+            {
+                let mut instruction = write_instruction(dsa_addr, 1, 0, b"ab");
+                instruction.accounts[2] = AccountMeta::new(chunk_addrs[0], false);
+                instruction
+            },
+            InstructionError::InvalidSeeds
+        ),
+        (
+            write_instruction(bytes_dsa_addr, 0, 0, b"ab"),
+            InstructionError::Custom(DataStorageError::NotChunkedAccount as u32)
+        )
+    ] {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(0, expected_error),
+            "Invalid error."
+        );
+    };
+
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ read_instruction(bytes_dsa_addr, 0, 0) ],
+            Some(&payer),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::NotChunkedAccount as u32)
+            ),
+            "Invalid error."
+        );
+    }
+    // failure - out of chunk bounds, wrong chunks & non-chunked accounts
+
+    // failure - a chunked account can't be moved (it's chunks are derived from it's Pubkey), soft-closed nor closed
+    for (instruction, authority_signs) in [
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new_readonly(payer, false),
+                    AccountMeta::new(find_data_storage_pda(&payer, &[ 67; 30 ], &data_storage_program_id).0, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+                ],
+                data: DataStorageInstruction::TransferAuthority.pack()
+            },
+            true
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(find_data_storage_pda(&SYSTEM_PROGRAM_ID, &[ 67; 30 ], &data_storage_program_id).0, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+                ],
+                data: DataStorageInstruction::Seal.pack()
+            },
+            true
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new(find_data_storage_pda(&authority_keypair.pubkey(), &[ 68; 30 ], &data_storage_program_id).0, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+                ],
+                data: DataStorageInstruction::Rename { label: [ 68; 30 ] }.pack()
+            },
+            true
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(dsa_addr, false),
+                    AccountMeta::new(find_data_storage_pda(&authority_keypair.pubkey(), &[ 68; 30 ], &data_storage_program_id).0, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+                ],
+                data: DataStorageInstruction::Duplicate { label: [ 68; 30 ] }.pack()
+            },
+            true
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(payer, false)
+                ],
+                data: DataStorageInstruction::SoftClose.pack()
+            },
+            true
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(payer, false)
+                ],
+                data: DataStorageInstruction::Close.pack()
+            },
+            true
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(payer, false)
+                ],
+                data: DataStorageInstruction::CompareAndClose { expected_data_hash: [ 0; 32 ] }.pack()
+            },
+            true
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new(payer, false)
+                ],
+                data: DataStorageInstruction::CloseExpired.pack()
+            },
+            false
+        )
+    ] {
+        let transaction = if authority_signs {
+            Transaction::new_signed_with_payer(
+                &[ instruction ],
+                Some(&payer),
+                &[
+                    &ptc.payer,
+                    &authority_keypair
+                ],
+                ptc.last_blockhash
+            )
+        } else {
+            Transaction::new_signed_with_payer(
+                &[ instruction ],
+                Some(&payer),
+                &[ &ptc.payer ],
+                ptc.last_blockhash
+            )
+        };
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::ChunkedAccount as u32)
+            ),
+            "Invalid error."
+        );
+    };
+    // failure - a chunked account can't be moved (it's chunks are derived from it's Pubkey), soft-closed nor closed
+
+    // success - close the chunks, the account is an empty bytes one again
+    {
+        let rent_receiver = Pubkey::new_unique();
+
+        let mut accounts = vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(rent_receiver, false)
+        ];
+        accounts.extend(chunk_addrs.iter().map(|chunk_addr| AccountMeta::new(*chunk_addr, false)));
+
+        let mut chunks_lamports = 0;
+        for chunk_addr in chunk_addrs.iter() {
+            chunks_lamports += ptc
+                .banks_client
+                .get_balance(*chunk_addr)
+                .await
+                .unwrap();
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts,
+                    data: DataStorageInstruction::CloseChunks.pack()
+                }
+            ],
+            Some(&payer),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        for chunk_addr in chunk_addrs.iter() {
+            assert!(
+                ptc
+                    .banks_client
+                    .get_account(*chunk_addr)
+                    .await
+                    .unwrap()
+                    .is_none(),
+                "The chunk must be closed."
+            );
+        };
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+        let account = DataStorageAccount::unpack(&data).unwrap();
+
+        assert_eq!(
+            (account.kind, account.data.len(), data.len()),
+            (ACCOUNT_KIND_BYTES, 0, HEADER_LEN),
+            "Invalid account."
+        );
+        assert!(
+            ptc
+                .banks_client
+                .get_balance(rent_receiver)
+                .await
+                .unwrap() > chunks_lamports,
+            "The chunks' & their metadata's rent must go to the rent-receiver."
+        );
+    }
+    // success - close the chunks, the account is an empty bytes one again
+}
+
+#[tokio::test]
+async fn test_data_storage_config() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add admin & authority accounts
+    let admin_keypair = Keypair::new();
+    let authority_keypair = Keypair::new();
+    for keypair in [ &admin_keypair, &authority_keypair ] {
+        pt.add_account(
+            keypair.pubkey(),
+            SolanaAccount::new(
+                sol_to_lamports(1.0),
+                0,
+                &SYSTEM_PROGRAM_ID
+            )
+        );
+    };
+    //? add admin & authority accounts
+
+    //? add treasury account
+    let treasury = Pubkey::new_unique();
+    pt.add_account(
+        treasury,
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add treasury account
+
+    //? add program-data account, the admin is the program's upgrade-authority
+    let (
+        program_data_addr,
+        _
+    ) = Pubkey::find_program_address(
+        &[ data_storage_program_id.as_ref() ],
+        &BPF_LOADER_UPGRADEABLE_ID
+    );
+    // 'UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address: Some(admin) }'
+    let program_data = [
+        u32::to_le_bytes(3).as_slice(),
+        u64::to_le_bytes(0).as_slice(),
+        &[ 1 ],
+        admin_keypair.pubkey().as_ref()
+    ].concat();
+    assert_eq!(program_data.len(), UpgradeableLoaderState::size_of_programdata_metadata());
+    pt.add_account(
+        program_data_addr,
+        SolanaAccount {
+            lamports: sol_to_lamports(1.0),
+            data: program_data,
+            owner: BPF_LOADER_UPGRADEABLE_ID,
+            executable: false,
+            rent_epoch: Epoch::default()
+        }
+    );
+    //? add program-data account, the admin is the program's upgrade-authority
+
+    let mut ptc = pt.start_with_context().await;
+
+    let (
+        config_addr,
+        config_bump
+    ) = find_config_pda(&data_storage_program_id);
+    let payer = ptc.payer.pubkey();
+
+    let init_config_instruction = |admin: &Pubkey| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(config_addr, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(program_data_addr, false)
+        ],
+        data: DataStorageInstruction::InitConfig {
+            max_data_len: 4,
+            creation_fee: 5_000,
+            treasury
+        }.pack()
+    };
+    let update_config_instruction = |admin: &Pubkey, max_data_len: u32| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(config_addr, false),
+            AccountMeta::new_readonly(*admin, true)
+        ],
+        data: DataStorageInstruction::UpdateConfig {
+            admin: admin_keypair.pubkey(),
+            max_data_len,
+            creation_fee: 5_000,
+            treasury
+        }.pack()
+    };
+    let create_instruction = |label: [u8; 30], data: &[u8]| {
+        let (
+            dsa_addr,
+            _
+        ) = find_data_storage_pda(
+            &authority_keypair.pubkey(),
+            &label,
+            &data_storage_program_id
+        );
+
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(config_addr, false),
+                AccountMeta::new(treasury, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                label.as_slice(),
+                data
+            ].concat()
+        }
+    };
+
+    // success - creating an account before the config is initialized, nothing is enforced
+    {
+        let mut instruction = create_instruction([ 76; 30 ], b"Solana");
+        instruction.accounts.pop();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+    }
+    // success - creating an account before the config is initialized, nothing is enforced
+
+    // failure - only the program's upgrade-authority can initialize the config
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ init_config_instruction(&authority_keypair.pubkey()) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::IncorrectAuthority
+            )
+        );
+    }
+    // failure - only the program's upgrade-authority can initialize the config
+
+    // success - initializing the config
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ init_config_instruction(&admin_keypair.pubkey()) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &admin_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, owner, .. } = ptc
+            .banks_client
+            .get_account(config_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(owner, data_storage_program_id, "Invalid config owner.");
+        assert_eq!(data.len(), CONFIG_LEN, "Invalid config length.");
+        assert_eq!(
+            DataStorageConfig::unpack(&data).unwrap(),
+            DataStorageConfig {
+                admin: admin_keypair.pubkey(),
+                max_data_len: 4,
+                creation_fee: 5_000,
+                treasury,
+                bump: config_bump
+            },
+            "Invalid config."
+        );
+    }
+    // success - initializing the config
+
+    // failure - the config can only be initialized once
+    {
+        ptc
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ init_config_instruction(&admin_keypair.pubkey()) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &admin_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::AccountAlreadyInitialized
+            )
+        );
+    }
+    // failure - the config can only be initialized once
+
+    // failure - the config pda must be passed once it's initialized
+    {
+        let mut instruction = create_instruction([ 77; 30 ], b"Rust");
+        instruction.accounts.truncate(4);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::MissingConfig as u32)
+            )
+        );
+    }
+    // failure - the config pda must be passed once it's initialized
+
+    // failure - creating an account whose data-field exceeds the config's max data-length
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_instruction([ 77; 30 ], b"Solana") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::DataTooLarge as u32)
+            )
+        );
+    }
+    // failure - creating an account whose data-field exceeds the config's max data-length
+
+    // failure - only the admin can update the config
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ update_config_instruction(&authority_keypair.pubkey(), 16) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::IncorrectAuthority
+            )
+        );
+    }
+    // failure - only the admin can update the config
+
+    // success - the admin raises the max data-length
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ update_config_instruction(&admin_keypair.pubkey(), 16) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &admin_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(config_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            DataStorageConfig::unpack(&data).unwrap().max_data_len,
+            16,
+            "Invalid max data-length."
+        );
+    }
+    // success - the admin raises the max data-length
+
+    // success - creating an account within the limit pays the creation-fee to the treasury
+    {
+        // the same transaction has failed before
+        ptc
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_instruction([ 77; 30 ], b"Solana") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { lamports, .. } = ptc
+            .banks_client
+            .get_account(treasury)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            sol_to_lamports(1.0) + 5_000,
+            "Invalid treasury lamports."
+        );
+    }
+    // success - creating an account within the limit pays the creation-fee to the treasury
+
+    // failure - the treasury must be passed when the config has a creation-fee
+    {
+        let mut instruction = create_instruction([ 78; 30 ], b"Rust");
+        instruction.accounts.pop();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::MissingTreasury as u32)
+            )
+        );
+    }
+    // failure - the treasury must be passed when the config has a creation-fee
+
+    let batch_create_instruction = |labels: &[[u8; 30]], data: &[u8]| {
+        let mut instruction_accounts = vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(config_addr, false),
+            AccountMeta::new(treasury, false)
+        ];
+        let mut instruction_data = encode_instruction_discriminator(BATCH_CREATE_DATA_STORAGE_ACCOUNTS_INSTRUCTION_DISCRIMINATOR);
+        instruction_data.push(labels.len() as u8);
+        for label in labels {
+            let (
+                dsa_addr,
+                _
+            ) = find_data_storage_pda(
+                &authority_keypair.pubkey(),
+                label,
+                &data_storage_program_id
+            );
+
+            instruction_accounts.push(AccountMeta::new(dsa_addr, false));
+            instruction_accounts.push(AccountMeta::new_readonly(authority_keypair.pubkey(), true));
+
+            instruction_data.extend_from_slice(label);
+            instruction_data.extend_from_slice(&(data.len() as u16).to_le_bytes());
+            instruction_data.extend_from_slice(data);
+        };
+
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: instruction_accounts,
+            data: instruction_data
+        }
+    };
+
+    // failure - batch-creating accounts whose data-fields exceed the config's max data-length
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ batch_create_instruction(&[ [ 79; 30 ], [ 80; 30 ] ], &[ 7; 17 ]) ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::DataTooLarge as u32)
+            )
+        );
+    }
+    // failure - batch-creating accounts whose data-fields exceed the config's max data-length
+
+    // success - batch-creating accounts pays the creation-fee once per account
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ batch_create_instruction(&[ [ 79; 30 ], [ 80; 30 ] ], b"Solana") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { lamports, .. } = ptc
+            .banks_client
+            .get_account(treasury)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            lamports,
+            sol_to_lamports(1.0) + 3 * 5_000,
+            "Invalid treasury lamports."
+        );
+    }
+    // success - batch-creating accounts pays the creation-fee once per account
+}
+
+#[tokio::test]
+async fn test_create_idempotent() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let data_storage_account_label: [u8; 30] = [ 88; 30 ];
+    let (
+        dsa_addr,
+        _
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+    let payer = ptc.payer.pubkey();
+
+    let create_idempotent_instruction = |data: &[u8]| Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: [
+            encode_instruction_discriminator(CREATE_IDEMPOTENT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            &u32::to_le_bytes(0),
+            &i64::to_le_bytes(0),
+            data
+        ].concat()
+    };
+
+    // success - the first call creates the account, the identical retry is a no-op
+    let mut created_data: Option<Vec<u8>> = None;
+    for retry in [ false, true ] {
+        if retry {
+            ptc
+                .get_new_latest_blockhash()
+                .await
+                .unwrap();
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_idempotent_instruction(b"Solana") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let dsa_view = DataStorageAccountView(&data);
+        assert_eq!(
+            dsa_view.owner().unwrap(),
+            authority_keypair.pubkey(),
+            "Invalid authority."
+        );
+        assert_eq!(
+            dsa_view.data().unwrap(),
+            b"Solana",
+            "Invalid data-field."
+        );
+        if let Some(created_data) = &created_data {
+            assert_eq!(
+                &data,
+                created_data,
+                "The retry must not modify the account."
+            );
+        };
+        created_data = Some(data);
+    };
+    // success - the first call creates the account, the identical retry is a no-op
+
+    // failure - a retry with a different data-field
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_idempotent_instruction(b"Pooria") ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::DataMismatch as u32)
+            )
+        );
+    }
+    // failure - a retry with a different data-field
+}
+
+#[tokio::test]
+async fn test_total_account_size() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    let data_storage_account_label: [u8; 30] = [ 89; 30 ];
+    let (
+        dsa_addr,
+        _
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    // create, grow & shrink, the account's size (and rent) must line up with the data-field exactly after each of them
+    for (instruction, data_len) in [
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(ptc.payer.pubkey(), true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+                ],
+                data: [
+                    encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    data_storage_account_label.as_slice(),
+                    b"Solana"
+                ].concat()
+            },
+            6
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(ptc.payer.pubkey(), true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+                ],
+                data: [
+                    encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    [ 7; 200 ].as_slice()
+                ].concat()
+            },
+            200
+        ),
+        (
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(authority_keypair.pubkey(), false)
+                ],
+                data: [
+                    encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    b"Sol".as_slice()
+                ].concat()
+            },
+            3
+        )
+    ] {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data.len(),
+            total_account_size(data_len),
+            "Invalid account size."
+        );
+        assert_eq!(
+            DataStorageAccountView(&data).data_len().unwrap() as usize,
+            data_len,
+            "Invalid data-length."
+        );
+        assert_eq!(
+            lamports,
+            rent_for_data(data_len, &rent),
+            "Invalid lamport balance."
+        );
+    };
+    // create, grow & shrink, the account's size (and rent) must line up with the data-field exactly after each of them
+}
+
+#[tokio::test]
+async fn test_account_discriminator() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    //? add a data storage account without the account-discriminator
+    let missing_discriminator_label: [u8; 30] = [ 91; 30 ];
+    let (
+        missing_discriminator_dsa_addr,
+        missing_discriminator_dsa_bump
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &missing_discriminator_label,
+        &data_storage_program_id
+    );
+
+    let mut missing_discriminator_dsa_data = data_storage_account_data(
+        &authority_keypair.pubkey(),
+        &missing_discriminator_label,
+        missing_discriminator_dsa_bump,
+        b"Solana"
+    );
+    missing_discriminator_dsa_data[ACCOUNT_DISCRIMINATOR_OFFSET] = 0;
+
+    pt.add_account(
+        missing_discriminator_dsa_addr,
+        SolanaAccount {
+            lamports: sol_to_lamports(1.0),
+            data: missing_discriminator_dsa_data,
+            owner: data_storage_program_id,
+            rent_epoch: Epoch::default(),
+            executable: false
+        }
+    );
+    //? add a data storage account without the account-discriminator
+
+    let ptc = pt.start_with_context().await;
+
+    // success - create sets the account-discriminator
+    {
+        let data_storage_account_label: [u8; 30] = [ 90; 30 ];
+        let (
+            dsa_addr,
+            _
+        ) = find_data_storage_pda(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            &data_storage_program_id
+        );
+
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                b"Solana"
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            data[ACCOUNT_DISCRIMINATOR_OFFSET],
+            DATA_STORAGE_ACCOUNT_DISCRIMINATOR,
+            "Invalid account-discriminator."
+        );
+        assert_eq!(
+            DataStorageAccount::unpack(&data).unwrap().account_discriminator,
+            DATA_STORAGE_ACCOUNT_DISCRIMINATOR,
+            "Invalid unpacked account-discriminator."
+        );
+    }
+    // success - create sets the account-discriminator
+
+    // failure - edit rejects an account without the account-discriminator
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(missing_discriminator_dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Pooria".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidAccountData
+            )
+        );
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(missing_discriminator_dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            DataStorageAccount::unpack(&data),
+            Err(ProgramError::InvalidAccountData),
+            "Unpack must reject an account without the account-discriminator."
+        );
+    }
+    // failure - edit rejects an account without the account-discriminator
+}
+
+#[tokio::test]
+async fn test_create_pda_account_paths() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let empty_label: [u8; 30] = [ 91; 30 ];
+    let (
+        empty_dsa_addr,
+        _
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &empty_label,
+        &data_storage_program_id
+    );
+
+    //? add pre-funded data-storage pda
+    let pre_funded_label: [u8; 30] = [ 92; 30 ];
+    let (
+        pre_funded_dsa_addr,
+        _
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &pre_funded_label,
+        &data_storage_program_id
+    );
+    pt.add_account(
+        pre_funded_dsa_addr,
+        SolanaAccount::new(
+            1_000,
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add pre-funded data-storage pda
+
+    let ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    // success - empty pda (single 'create_account' cpi) & pre-funded pda (top-up, allocate & assign)
+    for (dsa_addr, label) in [
+        (empty_dsa_addr, empty_label),
+        (pre_funded_dsa_addr, pre_funded_label)
+    ] {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new(dsa_addr, false),
+                        AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                        AccountMeta::new(ptc.payer.pubkey(), true),
+                        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                        AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+                    ],
+                    data: [
+                        encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                        label.as_slice(),
+                        b"Solana"
+                    ].concat()
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, lamports, owner, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            owner,
+            data_storage_program_id,
+            "Invalid owner."
+        );
+        assert_eq!(
+            data.len(),
+            total_account_size(6),
+            "Invalid account size."
+        );
+        assert_eq!(
+            lamports,
+            rent_for_data(6, &rent),
+            "Invalid lamport balance."
+        );
+        assert_eq!(
+            &data[HEADER_LEN..],
+            b"Solana",
+            "Invalid data-field."
+        );
+    };
+    // success - empty pda (single 'create_account' cpi) & pre-funded pda (top-up, allocate & assign)
+}
+
+#[tokio::test]
+async fn test_get_data_storage_account_metadata() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add data-storage account with a data-field bigger than the return-data limit
+    let authority = Pubkey::new_unique();
+    let data_storage_account_label: [u8; 30] = [ 93; 30 ];
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &authority,
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let mut dsa_data = data_storage_account_data(
+        &authority,
+        &data_storage_account_label,
+        dsa_bump,
+        &[ 7; 2_000 ]
+    );
+    dsa_data[LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + 8].copy_from_slice(&i64::to_le_bytes(1_700_000_100));
+    dsa_data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + 8].copy_from_slice(&i64::to_le_bytes(1_700_000_000));
+    dsa_data[REVISION_OFFSET..REVISION_OFFSET + 4].copy_from_slice(&u32::to_le_bytes(3));
+
+    pt.add_account(
+        dsa_addr,
+        SolanaAccount {
+            data: dsa_data,
+            owner: data_storage_program_id,
+            lamports: sol_to_lamports(1.0),
+            rent_epoch: Epoch::default(),
+            executable: false
+        }
+    );
+    //? add data-storage account with a data-field bigger than the return-data limit
+
+    let ptc = pt.start_with_context().await;
+
+    // success - only the header fields are returned
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new_readonly(dsa_addr, false)
+                ],
+                data: encode_instruction_discriminator(GET_DATA_STORAGE_ACCOUNT_METADATA_INSTRUCTION_DISCRIMINATOR)
+            }
+        ],
+        Some(&ptc.payer.pubkey()),
+        &[ &ptc.payer ],
+        ptc.last_blockhash
+    );
+
+    let simulation_result = ptc
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap();
+
+    assert!(
+        simulation_result
+            .result
+            .unwrap()
+            .is_ok(),
+        "Get-metadata must not fail."
+    );
+
+    let return_data = simulation_result
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap();
+
+    assert_eq!(
+        return_data.program_id,
+        data_storage_program_id,
+        "Invalid return-data program."
+    );
+    assert_eq!(
+        return_data.data.len(),
+        32 + 30 + 8 + 8 + 4 + 2,
+        "Return-data must only hold the header fields."
+    );
+    assert_eq!(
+        DataStorageAccountMetadata::unpack(&return_data.data).unwrap(),
+        DataStorageAccountMetadata {
+            owner: authority,
+            label: data_storage_account_label,
+            last_updated: 1_700_000_100,
+            created_at: 1_700_000_000,
+            revision: 3,
+            data_len: 2_000
+        },
+        "Invalid metadata."
+    );
+    // success - only the header fields are returned
+}
+
+#[tokio::test]
+async fn test_pda_authority() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let multisig_program_id = Pubkey::new_from_array([2; 32]);
+
+    let mut pt = setup(&data_storage_program_id);
+    pt.add_program(
+        "multisig_program",
+        multisig_program_id,
+        processor!(cpi_caller_process_instruction)
+    );
+
+    let (
+        authority_addr,
+        _
+    ) = Pubkey::find_program_address(&[ CPI_CALLER_SIGNER_SEED ], &multisig_program_id);
+
+    //? add data-storage account owned by the multisig's pda
+    let data_storage_account_label: [u8; 30] = [ 94; 30 ];
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &authority_addr,
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    pt.add_account(
+        dsa_addr,
+        SolanaAccount {
+            data: data_storage_account_data(
+                &authority_addr,
+                &data_storage_account_label,
+                dsa_bump,
+                b"Solana"
+            ),
+            owner: data_storage_program_id,
+            lamports: sol_to_lamports(1.0),
+            rent_epoch: Epoch::default(),
+            executable: false
+        }
+    );
+    //? add data-storage account owned by the multisig's pda
+
+    let ptc = pt.start_with_context().await;
+
+    let edit_instruction_data = [
+        encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+        b"Anchor".as_slice()
+    ].concat();
+
+    // failure - the pda can't sign the transaction itself
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: data_storage_program_id,
+                    accounts: vec![
+                        AccountMeta::new(dsa_addr, false),
+                        AccountMeta::new_readonly(authority_addr, false)
+                    ],
+                    data: edit_instruction_data.clone()
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::MissingRequiredSignature
+            )
+        );
+    };
+    // failure - the pda can't sign the transaction itself
+
+    // success - the multisig program signs for it's pda through cpi
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                Instruction {
+                    program_id: multisig_program_id,
+                    accounts: vec![
+                        AccountMeta::new_readonly(data_storage_program_id, false),
+                        AccountMeta::new(dsa_addr, false),
+                        AccountMeta::new_readonly(authority_addr, false)
+                    ],
+                    data: edit_instruction_data
+                }
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            &data[HEADER_LEN..],
+            b"Anchor",
+            "Invalid data-field."
+        );
+        assert_eq!(
+            *array_ref![ data, LAST_EDITOR_OFFSET, 32 ],
+            authority_addr.to_bytes(),
+            "Invalid last-editor."
+        );
+    };
+    // success - the multisig program signs for it's pda through cpi
+}
+
+#[tokio::test]
+async fn test_edit_shrink_into_reserved_capacity() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let mut ptc = pt.start_with_context().await;
+
+    let rent = ptc
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap();
+
+    //? add data-storage account, a 10 bytes data-field followed by 5 bytes of reserved capacity
+    let data_storage_account_label: [u8; 30] = [ 95; 30 ];
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let account_data = [
+        data_storage_account_data(
+            &authority_keypair.pubkey(),
+            &data_storage_account_label,
+            dsa_bump,
+            b"SolanaRust"
+        ),
+        vec![ 0; 5 ]
+    ].concat();
+
+    ptc.set_account(
+        &dsa_addr,
+        &AccountSharedData::from(
+            SolanaAccount {
+                lamports: rent.minimum_balance(account_data.len()),
+                data: account_data,
+                owner: data_storage_program_id,
+                rent_epoch: Epoch::default(),
+                executable: false
+            }
+        )
+    );
+    //? add data-storage account, a 10 bytes data-field followed by 5 bytes of reserved capacity
+
+    // success - the old data-field's bytes behind the new one ("anaRu", kept by the realloc) are cleared
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            Instruction {
+                program_id: data_storage_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(ptc.payer.pubkey(), false)
+                ],
+                data: [
+                    encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    b"Sol".as_slice()
+                ].concat()
+            }
+        ],
+        Some(&ptc.payer.pubkey()),
+        &[
+            &ptc.payer,
+            &authority_keypair
+        ],
+        ptc.last_blockhash
+    );
+
+    ptc
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let SolanaAccount { data, lamports, .. } = ptc
+        .banks_client
+        .get_account(dsa_addr)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        data.len(),
+        total_account_size(3 + 5),
+        "Invalid account size."
+    );
+    assert_eq!(
+        DataStorageAccountView(&data).data().unwrap(),
+        b"Sol",
+        "Invalid data-field."
+    );
+    assert_eq!(
+        &data[HEADER_LEN + 3..],
+        &[ 0; 5 ],
+        "Reserved capacity must be cleared."
+    );
+    assert_eq!(
+        lamports,
+        rent.minimum_balance(total_account_size(3 + 5)),
+        "Invalid lamport balance."
+    );
+    // success - the old data-field's bytes behind the new one ("anaRu", kept by the realloc) are cleared
+}
+
+// runs the data-storage program twice in one instruction (so over the same serialized accounts), first with the
+// instruction-data's next 'instruction_data[0]' bytes & then with the rest
+fn twice_process_instruction(
+    program_id: &Pubkey,
+    accounts_info: &[AccountInfo],
+    instruction_data: &[u8]
+) -> ProgramResult {
+    let (
+        first_instruction_data_len,
+        instruction_data
+    ) = instruction_data.split_first().unwrap();
+
+    let (
+        first_instruction_data,
+        second_instruction_data
+    ) = instruction_data.split_at(*first_instruction_data_len as usize);
+
+    process_instruction(program_id, accounts_info, first_instruction_data)?;
+    process_instruction(program_id, accounts_info, second_instruction_data)
+}
+
+#[tokio::test]
+async fn test_shrink_then_grow_in_one_instruction() {
+    let twice_program_id = Pubkey::new_from_array([2; 32]);
+    let mut pt = ProgramTest::new(
+        "twice_program",
+        twice_program_id,
+        processor!(twice_process_instruction)
+    );
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    //? add data-storage account (of the twice program), a 100 bytes data-field
+    let data_storage_account_label: [u8; 30] = [ 96; 30 ];
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = find_data_storage_pda(
+        &authority_keypair.pubkey(),
+        &data_storage_account_label,
+        &twice_program_id
+    );
+
+    pt.add_account(
+        dsa_addr,
+        SolanaAccount {
+            lamports: sol_to_lamports(1.0),
+            data: data_storage_account_data(
+                &authority_keypair.pubkey(),
+                &data_storage_account_label,
+                dsa_bump,
+                &[ 7; 100 ]
+            ),
+            owner: twice_program_id,
+            rent_epoch: Epoch::default(),
+            executable: false
+        }
+    );
+    //? add data-storage account (of the twice program), a 100 bytes data-field
+
+    let ptc = pt.start_with_context().await;
+
+    // success - shrink to a 1 byte data-field, then reserve 99 bytes of capacity, the grow must not expose the freed bytes
+    let edit_instruction_data = [
+        encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+        b"S".as_slice()
+    ].concat();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            Instruction {
+                program_id: twice_program_id,
+                accounts: vec![
+                    AccountMeta::new(dsa_addr, false),
+                    AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+                    AccountMeta::new(ptc.payer.pubkey(), true),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+                ],
+                data: [
+                    &[ edit_instruction_data.len() as u8 ],
+                    edit_instruction_data.as_slice(),
+                    encode_instruction_discriminator(RESERVE_DATA_STORAGE_ACCOUNT_CAPACITY_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                    u16::to_le_bytes(99).as_slice()
+                ].concat()
+            }
+        ],
+        Some(&ptc.payer.pubkey()),
+        &[
+            &ptc.payer,
+            &authority_keypair
+        ],
+        ptc.last_blockhash
+    );
+
+    ptc
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let SolanaAccount { data, .. } = ptc
+        .banks_client
+        .get_account(dsa_addr)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        data.len(),
+        total_account_size(100),
+        "Invalid account size."
+    );
+    assert_eq!(
+        DataStorageAccountView(&data).data().unwrap(),
+        b"S",
+        "Invalid data-field."
+    );
+    assert!(
+        data[HEADER_LEN + 1..].iter().all(|byte| *byte == 0),
+        "Reserved capacity must be zeroed."
+    );
+    // success - shrink to a 1 byte data-field, then reserve 99 bytes of capacity, the grow must not expose the freed bytes
+}
+
+#[tokio::test]
+async fn test_min_data_len() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let ptc = setup(&data_storage_program_id).start_with_context().await;
+
+    let create_instruction = |label_byte: u8, data_len: usize| {
+        let mut data_storage_account_label: [u8; 30] = [0; 30];
+        data_storage_account_label.fill(label_byte);
+
+        let (dsa_addr, _) = Pubkey::find_program_address(
+            &[
+                DATA_STORAGE_SEED_PREFIX,
+                ptc.payer.pubkey().to_bytes().as_slice(),
+                &data_storage_account_label
+            ],
+            &data_storage_program_id
+        );
+
+        Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(ptc.payer.pubkey(), true),
+                AccountMeta::new(ptc.payer.pubkey(), true),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+            ],
+            data: [
+                encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                data_storage_account_label.as_slice(),
+                &vec![ 1; data_len ]
+            ].concat()
+        }
+    };
+
+    // success - exactly 'MIN_DATA_LEN' bytes
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_instruction(76, MIN_DATA_LEN) ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+    }
+    // success - exactly 'MIN_DATA_LEN' bytes
+
+    // failure - one byte fewer than 'MIN_DATA_LEN' (only when a minimum is configured)
+    if let Some(too_short_data_len) = MIN_DATA_LEN.checked_sub(1) {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ create_instruction(77, too_short_data_len) ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(DataStorageError::DataTooSmall as u32)
+            ),
+            "Data-fields shorter than MIN_DATA_LEN must be rejected."
+        );
+    };
+    // failure - one byte fewer than 'MIN_DATA_LEN' (only when a minimum is configured)
+}
+
+#[tokio::test]
+#[cfg(not(feature = "no-events"))]
+async fn test_events_are_emitted_by_default() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(0.01),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    let ptc = pt.start_with_context().await;
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(69);
+
+    let data_storage_pda = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let instruction_data: &[u8] = &[
+        encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+        data_storage_account_label.as_slice(),
+        b"events"
+    ].concat();
+
+    let instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(data_storage_pda.0, false),
+            AccountMeta::new_readonly(authority_keypair.pubkey(), true),
+            AccountMeta::new(ptc.payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&data_storage_program_id).0, false)
+        ],
+        data: instruction_data.to_vec()
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ instruction ],
+        Some(&ptc.payer.pubkey()),
+        &[
+            &ptc.payer,
+            &authority_keypair
+        ],
+        ptc.last_blockhash
+    );
+
+    let simulation_result = ptc
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap();
+
+    let event = Events::NewDataStorageAccountCreated {
+        data_storage_account: data_storage_pda.0,
+        authority_account: authority_keypair.pubkey(),
+        account_label: data_storage_account_label
+    };
+    assert!(
+        emitted_events(
+            &simulation_result
+                .simulation_details
+                .unwrap()
+                .logs
+        ).contains(&event),
+        "Invalid emitted event!"
+    );
+}
+
+#[test]
+fn test_events_borsh_round_trip() {
+    let data_storage_account = Pubkey::new_unique();
+    let authority_account = Pubkey::new_unique();
+
+    let events = [
+        Events::NewDataStorageAccountCreated {
+            data_storage_account,
+            authority_account,
+            account_label: [ 65; 30 ]
+        },
+        Events::DataStorageAccountEdited {
+            data_storage_account,
+            authority_account,
+            old_data_len: 6,
+            new_data_len: 19,
+            new_revision: 2,
+            last_editor: authority_account
+        },
+        Events::DataStorageAccountClosed {
+            data_storage_account,
+            authority_account
+        },
+        Events::DataStorageAccountCapacityReserved {
+            data_storage_account,
+            added_capacity: 10,
+            total_capacity: 16
+        },
+        Events::DataStorageAccountLocked {
+            data_storage_account,
+            lock_holder: authority_account,
+            lock_expiry: 300
+        },
+        Events::DataStorageAccountUnlocked {
+            data_storage_account
+        },
+        Events::DataStorageAccountAuthorityTransferred {
+            old_data_storage_account: data_storage_account,
+            new_data_storage_account: Pubkey::new_unique(),
+            old_authority_account: authority_account,
+            new_authority_account: Pubkey::new_unique()
+        },
+        Events::DataStorageAccountSealed {
+            old_data_storage_account: data_storage_account,
+            data_storage_account: Pubkey::new_unique(),
+            authority_account
+        },
+        Events::DataStorageAccountDuplicated {
+            source_data_storage_account: data_storage_account,
+            data_storage_account: Pubkey::new_unique(),
+            account_label: [ 66; 30 ]
+        },
+        Events::DataStorageAccountPatched {
+            data_storage_account,
+            authority_account,
+            offset: 2,
+            len: 3
+        },
+        Events::DataStorageAccountCpiAllowlistSet {
+            data_storage_account,
+            cpi_allowlist: [ Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::default() ]
+        },
+        Events::DataStorageAccountAuthorityRepaired {
+            data_storage_account,
+            authority_account
+        },
+        Events::DataStorageAccountPatchLogCompacted {
+            data_storage_account,
+            compacted_patches: 8
+        },
+        Events::DataStorageAccountDelegateSet {
+            data_storage_account,
+            delegate: Pubkey::new_unique()
+        },
+        Events::DataStorageAccountSoftClosed {
+            data_storage_account,
+            authority_account,
+            closed_at: 500
+        },
+        Events::DataStorageAccountRevived {
+            data_storage_account,
+            authority_account
+        },
+        Events::DataStorageAccountLayoutMigrated {
+            data_storage_account,
+            header_version: HEADER_VERSION
+        },
+        Events::DataStorageAccountToppedUp {
+            data_storage_account,
+            funder_account: Pubkey::new_unique(),
+            lamports: 1_000
+        },
+        Events::DataStorageAccountTouched {
+            data_storage_account,
+            last_updated: 1_700_000_000
+        },
+        Events::DataStorageAccountRenamed {
+            old_data_storage_account: data_storage_account,
+            data_storage_account: Pubkey::new_unique(),
+            account_label: [ 66; 30 ]
+        },
+        Events::DataStorageAccountChunksAllocated {
+            data_storage_account,
+            total_len: 25 * 1024,
+            chunk_count: 3
+        },
+        Events::DataStorageAccountChunkWritten {
+            data_storage_account,
+            chunk_index: 2,
+            new_revision: 7,
+            last_editor: authority_account
+        },
+        Events::DataStorageAccountImmutableWindowSet {
+            data_storage_account,
+            lock_until: 1_700_000_000
+        },
+        Events::DataStorageConfigInitialized {
+            config_account: Pubkey::new_unique(),
+            admin: authority_account,
+            max_data_len: 1024,
+            creation_fee: 5_000,
+            treasury: Pubkey::new_unique()
+        },
+        Events::DataStorageConfigUpdated {
+            config_account: Pubkey::new_unique(),
+            admin: authority_account,
+            max_data_len: 0,
+            creation_fee: 0,
+            treasury: Pubkey::new_unique()
+        },
+        Events::DataStorageAccountChunksClosed {
+            data_storage_account,
+            chunk_count: 3
+        }
+    ];
+
+    for event in events {
+        let bytes = borsh::to_vec(&event).unwrap();
+
+        assert_eq!(
+            Events::try_from_slice(&bytes).unwrap(),
+            event,
+            "Invalid borsh round-trip."
+        );
+        assert_eq!(
+            emitted_events(&[ format!("Program data: {}", BASE64_STANDARD.encode(&bytes)) ]),
+            vec![ event ],
+            "Invalid decoded \"Program data: \" line."
+        );
+    }
+}
+
+#[test]
+fn test_rent_exempt_lamports_delta() {
+    let rent = Rent::default();
+
+    // success - same as 'Rent::minimum_balance', both ways
+    for (old_account_size, new_account_size) in [ (HEADER_LEN, HEADER_LEN + 19), (HEADER_LEN + 19, HEADER_LEN + 3), (HEADER_LEN, HEADER_LEN) ] {
+        assert_eq!(
+            rent_exempt_lamports_delta(&rent, old_account_size, new_account_size).unwrap(),
+            rent.minimum_balance(new_account_size).abs_diff(rent.minimum_balance(old_account_size)),
+            "Invalid rent-exempt lamports delta."
+        );
+    }
+
+    // failure - overflowing 'u64'
+    for (rent, old_account_size, new_account_size) in [
+        (
+            Rent {
+                lamports_per_byte_year: u64::MAX,
+                ..Rent::default()
+            },
+            HEADER_LEN,
+            HEADER_LEN + 1
+        ),
+        (
+            Rent::default(),
+            HEADER_LEN,
+            usize::MAX
+        ),
+        (
+            Rent {
+                exemption_threshold: 1e30,
+                ..Rent::default()
+            },
+            HEADER_LEN,
+            HEADER_LEN + 1
+        )
+    ] {
+        assert_eq!(
+            rent_exempt_lamports_delta(&rent, old_account_size, new_account_size).unwrap_err(),
+            ProgramError::ArithmeticOverflow,
+            "Overflowing rent math must be rejected."
+        );
+    }
+}
+
+#[test]
+fn test_data_as_str() {
+    let authority = Pubkey::new_unique();
+    let data_storage_account_label: [u8; 30] = [65; 30];
+
+    // success - text data-field
+    {
+        let account_data = data_storage_account_data(
+            &authority,
+            &data_storage_account_label,
+            255,
+            "PooriaGG ✅".as_bytes()
+        );
+
+        assert_eq!(
+            data_as_str(&account_data),
+            Ok("PooriaGG ✅"),
+            "Invalid text data."
+        );
+    }
+    // success - text data-field
+
+    // failure - binary data-field
+    {
+        let account_data = data_storage_account_data(
+            &authority,
+            &data_storage_account_label,
+            255,
+            &[ 0xff, 0xfe, 0x00, 0x80 ]
+        );
+
+        assert_eq!(
+            data_as_str(&account_data),
+            Err(
+                ProgramError::Custom(
+                    DataStorageError::InvalidData as u32
+                )
+            )
+        );
+    }
+    // failure - binary data-field
+}
+
+// NOTE: A 65536 bytes data-field doesn't fit in a transaction, so the processor is invoked directly.
+#[test]
+fn test_data_length_must_fit_in_u16() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let authority = Pubkey::new_unique();
+    let funding_account = Pubkey::new_unique();
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(85);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            authority.as_ref(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    let too_long_data = vec![ 1; MAX_DATA_LEN + 1 ];
+
+    // failure - create
+    {
+        let (mut dsa_lamports, mut authority_lamports, mut funding_lamports, mut system_program_lamports) = (0, 0, sol_to_lamports(1.0), 1);
+        let (mut dsa_data, mut authority_data, mut funding_data, mut system_program_data) = (vec![], vec![], vec![], vec![]);
+
+        let accounts_info = [
+            AccountInfo::new(&dsa_addr, false, true, &mut dsa_lamports, &mut dsa_data, &SYSTEM_PROGRAM_ID, false, Epoch::default()),
+            AccountInfo::new(&authority, true, false, &mut authority_lamports, &mut authority_data, &SYSTEM_PROGRAM_ID, false, Epoch::default()),
+            AccountInfo::new(&funding_account, true, true, &mut funding_lamports, &mut funding_data, &SYSTEM_PROGRAM_ID, false, Epoch::default()),
+            AccountInfo::new(&SYSTEM_PROGRAM_ID, false, false, &mut system_program_lamports, &mut system_program_data, &SYSTEM_PROGRAM_ID, true, Epoch::default())
+        ];
+
+        let instruction_data = [
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            u32::to_le_bytes(100_000).as_slice(),
+            i64::to_le_bytes(0).as_slice(),
+            too_long_data.as_slice()
+        ].concat();
+
+        assert_eq!(
+            process_instruction(
+                &data_storage_program_id,
+                &accounts_info,
+                &instruction_data
+            ),
+            Err(ProgramError::Custom(DataStorageError::DataTooLarge as u32)),
+            "Data-fields longer than MAX_DATA_LEN must be rejected."
+        );
+    }
+    // failure - create
+
+    // failure - edit
+    {
+        let (mut dsa_lamports, mut authority_lamports) = (sol_to_lamports(1.0), 0);
+        let mut dsa_data = data_storage_account_data(
+            &authority,
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+        dsa_data[MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + 4].copy_from_slice(&u32::to_le_bytes(100_000));
+        let mut authority_data = vec![];
+
+        let accounts_info = [
+            AccountInfo::new(&dsa_addr, false, true, &mut dsa_lamports, &mut dsa_data, &data_storage_program_id, false, Epoch::default()),
+            AccountInfo::new(&authority, true, false, &mut authority_lamports, &mut authority_data, &SYSTEM_PROGRAM_ID, false, Epoch::default())
+        ];
+
+        let instruction_data = [
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            too_long_data.as_slice()
+        ].concat();
+
+        assert_eq!(
+            process_instruction(
+                &data_storage_program_id,
+                &accounts_info,
+                &instruction_data
+            ),
+            Err(ProgramError::Custom(DataStorageError::DataTooLarge as u32)),
+            "Data-fields longer than MAX_DATA_LEN must be rejected."
+        );
+    }
+    // failure - edit
+
+    // failure - append
+    {
+        let (mut dsa_lamports, mut authority_lamports) = (sol_to_lamports(1.0), 0);
+        let mut dsa_data = data_storage_account_data(
+            &authority,
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+        dsa_data[MAX_SIZE_OFFSET..MAX_SIZE_OFFSET + 4].copy_from_slice(&u32::to_le_bytes(100_000));
+        let mut authority_data = vec![];
+
+        let accounts_info = [
+            AccountInfo::new(&dsa_addr, false, true, &mut dsa_lamports, &mut dsa_data, &data_storage_program_id, false, Epoch::default()),
+            AccountInfo::new(&authority, true, false, &mut authority_lamports, &mut authority_data, &SYSTEM_PROGRAM_ID, false, Epoch::default())
+        ];
+
+        let instruction_data = [
+            encode_instruction_discriminator(APPEND_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            &too_long_data[b"Solana".len()..]
+        ].concat();
+
+        assert_eq!(
+            process_instruction(
+                &data_storage_program_id,
+                &accounts_info,
+                &instruction_data
+            ),
+            Err(ProgramError::Custom(DataStorageError::DataTooLarge as u32)),
+            "Appends past MAX_DATA_LEN must be rejected."
+        );
+    }
+    // failure - append
+}
+
+// NOTE: The crafted account is immutable (system-program authority), so any data-dependent check would fail differently.
+#[test]
+fn test_foreign_owned_account_is_rejected_before_reading_it() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let authority = Pubkey::new_unique();
+    let rent_receiver = Pubkey::new_unique();
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(85);
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            DATA_STORAGE_SEED_PREFIX,
+            SYSTEM_PROGRAM_ID.as_ref(),
+            &data_storage_account_label
+        ],
+        &data_storage_program_id
+    );
+
+    for instruction_data in [
+        [
+            encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            b"Rust".as_slice()
+        ].concat(),
+        encode_instruction_discriminator(CLOSE_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR)
+    ] {
+        let (mut dsa_lamports, mut authority_lamports, mut rent_receiver_lamports) = (sol_to_lamports(1.0), 0, 0);
+        let mut dsa_data = data_storage_account_data(
+            &SYSTEM_PROGRAM_ID,
+            &data_storage_account_label,
+            dsa_bump,
+            b"Solana"
+        );
+        let (mut authority_data, mut rent_receiver_data) = (vec![], vec![]);
+
+        let accounts_info = [
+            AccountInfo::new(&dsa_addr, false, true, &mut dsa_lamports, &mut dsa_data, &SYSTEM_PROGRAM_ID, false, Epoch::default()),
+            AccountInfo::new(&authority, true, false, &mut authority_lamports, &mut authority_data, &SYSTEM_PROGRAM_ID, false, Epoch::default()),
+            AccountInfo::new(&rent_receiver, false, true, &mut rent_receiver_lamports, &mut rent_receiver_data, &SYSTEM_PROGRAM_ID, false, Epoch::default())
+        ];
+
+        assert_eq!(
+            process_instruction(
+                &data_storage_program_id,
+                &accounts_info,
+                &instruction_data
+            ),
+            Err(ProgramError::InvalidAccountOwner),
+            "Accounts not owned by the program must be rejected before their data is read."
+        );
+    }
+}
+
+#[test]
+fn test_header_offsets() {
+    assert_eq!(HEADER_LEN, 456, "Invalid header length.");
+    assert_eq!(DATA_OFFSET, HEADER_LEN, "The data-field must start right after the header.");
+
+    let authority = Pubkey::new_unique();
+    let data_storage_account_label: [u8; 30] = [65; 30];
+
+    let account_data = data_storage_account_data(
+        &authority,
+        &data_storage_account_label,
+        254,
+        b"Solana"
+    );
+
+    assert_eq!(
+        &account_data[OWNER_OFFSET..OWNER_OFFSET + 32],
+        authority.as_ref(),
+        "Invalid authority."
+    );
+    assert_eq!(
+        &account_data[LABEL_OFFSET..LABEL_OFFSET + 30],
+        data_storage_account_label.as_slice(),
+        "Invalid label."
+    );
+    assert_eq!(
+        &account_data[LAST_UPDATED_OFFSET..LAST_UPDATED_OFFSET + 8],
+        &[ 0; 8 ],
+        "Invalid last-updated."
+    );
+    assert_eq!(
+        account_data[BUMP_OFFSET],
+        254,
+        "Invalid bump."
+    );
+    assert_eq!(
+        account_data[INIT_OFFSET],
+        true as u8,
+        "Invalid is-initialized."
+    );
+    assert_eq!(
+        &account_data[DATA_LEN_OFFSET..DATA_LEN_OFFSET + 2],
+        &u16::to_le_bytes(6),
+        "Invalid data length."
+    );
+    assert_eq!(
+        account_data[HEADER_VERSION_OFFSET],
+        HEADER_VERSION,
+        "Invalid header-version."
+    );
+    assert_eq!(
+        &account_data[LOCK_UNTIL_OFFSET..LOCK_UNTIL_OFFSET + 8],
+        &[ 0; 8 ],
+        "Invalid lock-until."
+    );
+    assert_eq!(
+        &account_data[DATA_OFFSET..],
+        b"Solana",
+        "Invalid data."
+    );
+}
+
+#[test]
+fn test_instruction_pack_unpack_round_trip() {
+    let label = [ 65; 30 ];
+    let pubkey = Pubkey::new_unique();
+
+    let instructions = [
+        DataStorageInstruction::Create { label, data: b"Solana".to_vec() },
+        DataStorageInstruction::CreateWithOptions { label, max_size: 64, expires_at: 1_000, data: b"Solana".to_vec() },
+        DataStorageInstruction::Edit {
+            data: b"Sol".to_vec()
+        },
+        DataStorageInstruction::EditWithOptions {
+            display_label: Some([ 66; 30 ]),
+            idempotency_key: Some(7),
+            expected_revision: Some(2),
+            compressed: true,
+            data: b"Solana Data Storage".to_vec()
+        },
+        DataStorageInstruction::Close,
+        DataStorageInstruction::Log,
+        DataStorageInstruction::Duplicate { label },
+        DataStorageInstruction::ReserveCapacity { additional_capacity: 10 },
+        DataStorageInstruction::Append { data: b"!".to_vec() },
+        DataStorageInstruction::Lock { lock_holder: pubkey, lock_expiry: 100 },
+        DataStorageInstruction::Unlock,
+        DataStorageInstruction::SelfTest,
+        DataStorageInstruction::TransferAuthority,
+        DataStorageInstruction::Seal,
+        DataStorageInstruction::CompareAndClose { expected_data_hash: [ 1; 32 ] },
+        DataStorageInstruction::Truncate { data_len: 3 },
+        DataStorageInstruction::Patch { offset: 2, data: b"la".to_vec() },
+        DataStorageInstruction::SetCpiAllowlist { allowlist: vec![ pubkey, Pubkey::new_unique() ] },
+        DataStorageInstruction::RepairAuthority,
+        DataStorageInstruction::IsImmutable,
+        DataStorageInstruction::FactoryCreate { label, max_size: 0, data: b"Solana".to_vec() },
+        DataStorageInstruction::FactoryCreateWithOptions { label, max_size: 64, expires_at: 1_000, data: b"Solana".to_vec() },
+        DataStorageInstruction::VerifyData,
+        DataStorageInstruction::AppendPatchLog { offset: 0, data: b"S".to_vec() },
+        DataStorageInstruction::CompactPatchLog,
+        DataStorageInstruction::SetDelegate { delegate: pubkey },
+        DataStorageInstruction::Attest,
+        DataStorageInstruction::CloseExpired,
+        DataStorageInstruction::ReadData,
+        DataStorageInstruction::CreateVariableLabel { label: b"variable".to_vec(), max_size: 0, expires_at: 0, data: b"Solana".to_vec() },
+        DataStorageInstruction::SoftClose,
+        DataStorageInstruction::Revive { max_size: 0, expires_at: 0, data: b"Solana".to_vec() },
+        DataStorageInstruction::BatchCreate { accounts: vec![ (label, b"Solana".to_vec()), ([ 66; 30 ], Vec::new()) ] },
+        DataStorageInstruction::MigrateLayout,
+        DataStorageInstruction::CreateCompressed { label, max_size: 0, expires_at: 0, data: b"Solana".to_vec() },
+        DataStorageInstruction::CompareAndSwap { expected_data_hash: [ 2; 32 ], data: b"Rust".to_vec() },
+        DataStorageInstruction::TopUp,
+        DataStorageInstruction::CreateKeyValue { label, max_size: 0, expires_at: 0, data: Vec::new() },
+        DataStorageInstruction::SetEntry { key: b"key".to_vec(), value: b"value".to_vec() },
+        DataStorageInstruction::GetEntry { key: b"key".to_vec() },
+        DataStorageInstruction::RemoveEntry { key: b"key".to_vec() },
+        DataStorageInstruction::Touch,
+        DataStorageInstruction::Rename { label },
+        DataStorageInstruction::AllocateChunks { total_len: 25 * 1024 },
+        DataStorageInstruction::WriteChunk { chunk_index: 2, offset: 900, data: b"Solana".to_vec() },
+        DataStorageInstruction::ReadChunk { chunk_index: 2, offset: 900 },
+        DataStorageInstruction::SetImmutableWindow { lock_until: 1_700_000_000 },
+        DataStorageInstruction::InitConfig { max_data_len: 1024, creation_fee: 5_000, treasury: Pubkey::new_from_array([ 3; 32 ]) },
+        DataStorageInstruction::UpdateConfig {
+            admin: Pubkey::new_from_array([ 4; 32 ]),
+            max_data_len: 0,
+            creation_fee: 0,
+            treasury: Pubkey::new_from_array([ 3; 32 ])
+        },
+        DataStorageInstruction::CreateIdempotent { label, max_size: 0, expires_at: 0, data: b"Solana".to_vec() },
+        DataStorageInstruction::GetMetadata,
+        DataStorageInstruction::CloseChunks
+    ];
+
+    for instruction in instructions {
+        let instruction_data = instruction.pack();
+
+        assert_eq!(
+            instruction_data[..encode_instruction_discriminator(0).len()],
+            encode_instruction_discriminator(instruction.discriminator()),
+            "Invalid packed discriminator."
+        );
+        assert_eq!(
+            DataStorageInstruction::unpack(&instruction_data).unwrap(),
+            instruction,
+            "Invalid pack/unpack round-trip."
+        );
+        assert_eq!(
+            DataStorageInstruction::try_from_slice(&borsh::to_vec(&instruction).unwrap()).unwrap(),
+            instruction,
+            "Invalid borsh round-trip."
+        );
+    }
+
+    // same wire format as the hand-built instruction data
+    assert_eq!(
+        DataStorageInstruction::CreateWithOptions { label, max_size: 64, expires_at: 1_000, data: b"Solana".to_vec() }.pack(),
+        [
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            label.as_slice(),
+            u32::to_le_bytes(64).as_slice(),
+            i64::to_le_bytes(1_000).as_slice(),
+            b"Solana"
+        ].concat(),
+        "Invalid packed instruction data."
+    );
+
+    // malformed instruction data
+    for instruction_data in [
+        Vec::new(),
+        vec![ u8::MAX ],
+        [ encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR), vec![ 65; 29 ] ].concat(),
+        [ encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR), vec![ 1 << 7 ] ].concat(),
+        [ encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS_INSTRUCTION_DISCRIMINATOR), vec![ EDIT_FLAG_IDEMPOTENCY_KEY, 1 ] ].concat(),
+        [ encode_instruction_discriminator(SET_DATA_STORAGE_ACCOUNT_CPI_ALLOWLIST_INSTRUCTION_DISCRIMINATOR), vec![ 1; 33 ] ].concat(),
+        [ encode_instruction_discriminator(BATCH_CREATE_DATA_STORAGE_ACCOUNTS_INSTRUCTION_DISCRIMINATOR), vec![ 1 ] ].concat()
+    ] {
+        assert_eq!(
+            DataStorageInstruction::unpack(&instruction_data),
+            Err(ProgramError::InvalidInstructionData),
+            "Malformed instruction data must be rejected."
+        );
+    }
+}
+
+#[test]
+fn test_data_storage_account_view() {
+    let authority = Pubkey::new_unique();
+    let label: [u8; 30] = [ 86; 30 ];
+
+    let account_data = data_storage_account_data(&authority, &label, 254, b"Solana");
+
+    let view = DataStorageAccountView(&account_data);
+    assert_eq!(view.owner(), Ok(authority), "Invalid owner.");
+    assert_eq!(view.label(), Ok(label.as_slice()), "Invalid label.");
+    assert_eq!(view.last_updated(), Ok(0), "Invalid last_updated.");
+    assert_eq!(view.bump(), Ok(254), "Invalid bump.");
+    assert_eq!(view.is_initialized(), Ok(true), "Invalid is_initialized.");
+    assert_eq!(view.data_len(), Ok(6), "Invalid data_len.");
+    assert_eq!(view.data(), Ok(b"Solana".as_slice()), "Invalid data.");
+
+    // truncated right before each field ends
+    let truncated = |len: usize| DataStorageAccountView(&account_data[..len]);
+    assert_eq!(truncated(OWNER_OFFSET + 31).owner(), Err(ProgramError::InvalidAccountData), "Owner must be bounds-checked.");
+    assert_eq!(truncated(LABEL_OFFSET + 29).label(), Err(ProgramError::InvalidAccountData), "Label must be bounds-checked.");
+    assert_eq!(truncated(LAST_UPDATED_OFFSET + 7).last_updated(), Err(ProgramError::InvalidAccountData), "Last-updated must be bounds-checked.");
+    assert_eq!(truncated(BUMP_OFFSET).bump(), Err(ProgramError::InvalidAccountData), "Bump must be bounds-checked.");
+    assert_eq!(truncated(INIT_OFFSET).is_initialized(), Err(ProgramError::InvalidAccountData), "Is-initialized must be bounds-checked.");
+    assert_eq!(truncated(DATA_LEN_OFFSET + 1).data_len(), Err(ProgramError::InvalidAccountData), "Data-length must be bounds-checked.");
+    assert_eq!(truncated(HEADER_LEN + 5).data(), Err(ProgramError::InvalidAccountData), "Data-field must be bounds-checked.");
+    assert_eq!(truncated(0).data(), Err(ProgramError::InvalidAccountData), "Data-field must be bounds-checked.");
+
+    // a field only needs it's own bytes
+    assert_eq!(truncated(DATA_LEN_OFFSET + 2).data_len(), Ok(6), "Invalid data_len.");
+}
+
+#[test]
+fn test_error_decoding() {
+    let expected = [
+        (70, DataStorageError::ImmutableDataStorage, "immutable data storage account."),
+        (71, DataStorageError::FailedToFindProgramAddress, "find_program_address failed!"),
+        (72, DataStorageError::InvalidLabel, "invalid account-label (invalid utf-8 or non-printable characters)"),
+        (73, DataStorageError::InvalidData, "invalid data"),
+        (74, DataStorageError::QuotaExceeded, "data length exceeds the account's max-size"),
+        (75, DataStorageError::AccountLocked, "data storage account is locked by another account"),
+        (76, DataStorageError::DataTooLarge, "data-field exceeds 'MAX_DATA_LEN' or solana's 10 MiB account limit"),
+        (77, DataStorageError::MalformedAccount, "stored data length exceeds the account's data-field"),
+        (78, DataStorageError::ConcurrentModification, "data-field has been modified since it was read"),
+        (79, DataStorageError::UnauthorizedCaller, "caller program is not in the account's cpi-allowlist"),
+        (80, DataStorageError::ReallocTooLarge, "accounts can only grow by 10 KiB per instruction"),
+        (81, DataStorageError::DataTooSmall, "data length is below the minimum data length"),
+        (82, DataStorageError::RevisionMismatch, "account's revision doesn't match the expected revision"),
+        (83, DataStorageError::HashMismatch, "data-field doesn't match it's stored hash"),
+        (84, DataStorageError::NotExpired, "data storage account hasn't expired"),
+        (85, DataStorageError::MissingFundingAccount, "funding account is missing"),
+        (86, DataStorageError::MissingRentReceiver, "rent-receiver account is missing"),
+        (87, DataStorageError::MissingSystemProgram, "system program account is missing"),
+        (88, DataStorageError::AccountTombstoned, "data storage account has been soft-closed, revive it instead"),
+        (89, DataStorageError::NotKeyValueAccount, "data storage account isn't a key-value account"),
+        (90, DataStorageError::EntryNotFound, "key-value entry not found"),
+        (91, DataStorageError::UnexpectedLamports, "data storage account holds lamports above it's rent-exempt minimum"),
+        (92, DataStorageError::NotChunkedAccount, "data storage account isn't a chunked account"),
+        (93, DataStorageError::TimeLocked, "data storage account's data-field is time-locked"),
+        (94, DataStorageError::MissingTreasury, "treasury account is missing"),
+        (95, DataStorageError::DataMismatch, "data storage account already exists with a different authority or data-field"),
+        (96, DataStorageError::LabelAlreadyUsed, "label is already used by another data storage account of the authority"),
+        (97, DataStorageError::MissingConfig, "config account is missing"),
+        (98, DataStorageError::NotBytesAccount, "data storage account isn't a bytes account"),
+        (99, DataStorageError::ChunkedAccount, "data storage account has chunks, close them first")
+    ];
+
+    for (code, error, message) in expected {
+        assert_eq!(ProgramError::from(error), ProgramError::Custom(code), "Invalid error code.");
+
+        let decoded = <DataStorageError as DecodeError<DataStorageError>>::decode_custom_error_to_enum(code)
+            .expect("Custom error must decode.");
+        assert_eq!(decoded, error, "Invalid decoded error.");
+        assert_eq!(decoded.to_string(), message, "Invalid error message.");
+    };
+
+    assert_eq!(<DataStorageError as DecodeError<DataStorageError>>::type_of(), "DataStorageError", "Invalid error type.");
+    assert!(<DataStorageError as DecodeError<DataStorageError>>::decode_custom_error_to_enum(69).is_none(), "Unknown code must not decode.");
+    assert!(<DataStorageError as DecodeError<DataStorageError>>::decode_custom_error_to_enum(100).is_none(), "Unknown code must not decode.");
+}
+
+#[test]
+#[cfg(feature = "client")]
+fn test_derive_addresses() {
+    use data_storage::client::{
+        derive_dsa_address,
+        derive_addresses
+    };
+
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let authority = Pubkey::new_unique();
+
+    let labels = (0..100u8)
+        .map(|i| {
+            let mut label: [u8; 30] = [0; 30];
+            label.fill(i);
+            label
+        })
+        .collect::<Vec<_>>();
+
+    let addresses = derive_addresses(
+        &data_storage_program_id,
+        &authority,
+        &labels
+    );
+
+    assert_eq!(
+        addresses.len(),
+        100,
+        "Invalid derived addresses count."
+    );
+
+    for i in [ 0, 42, 99 ] {
+        let expected = Pubkey::find_program_address(
+            &[
+                b"data_storage_account",
+                authority.to_bytes().as_slice(),
+                &labels[i]
+            ],
+            &data_storage_program_id
+        );
+
+        assert_eq!(
+            addresses[i],
+            expected,
+            "Invalid derived address."
+        );
+        assert_eq!(
+            derive_dsa_address(&data_storage_program_id, &authority, &labels[i]),
+            expected,
+            "Invalid derived address."
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    assert_eq!(
+        data_storage::client::derive_addresses_parallel(
+            &data_storage_program_id,
+            &authority,
+            &labels
+        ),
+        addresses,
+        "Parallel derivation must match the sequential one."
+    );
+}
+
+#[tokio::test]
+#[cfg(feature = "test-utils")]
+async fn test_scenario() {
+    use data_storage::test_utils::scenario;
+
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut ptc = setup(&data_storage_program_id).start_with_context().await;
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(83);
+
+    let states = scenario(
+        &mut ptc,
+        &data_storage_program_id,
+        &data_storage_account_label
+    ).await.unwrap();
+
+    assert_eq!(
+        data_as_str(&states.created.data).unwrap(),
+        "Solana",
+        "Invalid created data."
+    );
+    assert_eq!(
+        data_as_str(&states.grown.data).unwrap(),
+        "Solana Data Storage",
+        "Invalid grown data."
+    );
+    assert_eq!(
+        data_as_str(&states.shrunk.data).unwrap(),
+        "Sol",
+        "Invalid shrunk data."
+    );
+    assert_eq!(
+        states.closed,
+        None,
+        "Data storage account must be closed."
+    );
+}
+
+#[tokio::test]
+#[cfg(feature = "test-utils")]
+async fn test_legacy_program_id_accounts() {
+    use data_storage::test_utils::LEGACY_PROGRAM_ID;
+
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut pt = setup(&data_storage_program_id);
+
+    //? add authority account
+    let authority_keypair = Keypair::new();
+    pt.add_account(
+        authority_keypair.pubkey(),
+        SolanaAccount::new(
+            sol_to_lamports(1.0),
+            0,
+            &SYSTEM_PROGRAM_ID
+        )
+    );
+    //? add authority account
+
+    //? add data storage account (created by the legacy deployment)
+    let data_storage_account_label: [u8; 30] = [ 76; 30 ];
+
+    let (
+        dsa_addr,
+        dsa_bump
+    ) = Pubkey::find_program_address(
+        &[
+            b"data_storage_account",
+            authority_keypair.pubkey().to_bytes().as_slice(),
+            &data_storage_account_label
+        ],
+        &LEGACY_PROGRAM_ID
+    );
+
+    pt.add_account(
+        dsa_addr,
+        SolanaAccount {
+            lamports: sol_to_lamports(1.0),
+            data: data_storage_account_data(
+                &authority_keypair.pubkey(),
+                &data_storage_account_label,
+                dsa_bump,
+                b"Solana"
+            ),
+            owner: LEGACY_PROGRAM_ID,
+            rent_epoch: Epoch::default(),
+            executable: false
+        }
+    );
+    //? add data storage account (created by the legacy deployment)
+
+    let ptc = pt.start_with_context().await;
+
+    // success - read-only instructions accept the legacy account
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(dsa_addr, false)
+            ],
+            data: encode_instruction_discriminator(READ_DATA_STORAGE_ACCOUNT_DATA_INSTRUCTION_DISCRIMINATOR)
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[ &ptc.payer ],
+            ptc.last_blockhash
+        );
+
+        let return_data = ptc
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap()
+            .simulation_details
+            .unwrap()
+            .return_data
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            return_data,
+            [ &[ false as u8 ], b"Solana".as_slice() ].concat(),
+            "Invalid return-data."
+        );
+    }
+    // success - read-only instructions accept the legacy account
+
+    // failure - writes still require the current program's ownership
+    {
+        let instruction = Instruction {
+            program_id: data_storage_program_id,
+            accounts: vec![
+                AccountMeta::new(dsa_addr, false),
+                AccountMeta::new_readonly(authority_keypair.pubkey(), true)
+            ],
+            data: [
+                encode_instruction_discriminator(EDIT_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+                b"Rust".as_slice()
+            ].concat()
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&ptc.payer.pubkey()),
+            &[
+                &ptc.payer,
+                &authority_keypair
+            ],
+            ptc.last_blockhash
+        );
+
+        let error = ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidAccountOwner
+            ),
+            "Invalid error."
+        );
+    }
+    // failure - writes still require the current program's ownership
+}
+
+#[tokio::test]
+#[cfg(not(feature = "verbose_logs"))]
+#[cfg_attr(not(feature = "test-sbf"), ignore = "program logs are only collected by the sbf runtime")]
+async fn test_create_without_verbose_logs() {
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let pt = setup(&data_storage_program_id);
+
+    let ptc = pt.start_with_context().await;
+    let payer = ptc.payer.insecure_clone();
+
+    let data_storage_account_label: [u8; 30] = [ 77; 30 ];
+    let (
+        dsa_addr,
+        _
+    ) = find_data_storage_pda(
+        &payer.pubkey(),
+        &data_storage_account_label,
+        &data_storage_program_id
+    );
+
+    let instruction = Instruction {
+        program_id: data_storage_program_id,
+        accounts: vec![
+            AccountMeta::new(dsa_addr, false),
+            AccountMeta::new_readonly(payer.pubkey(), true),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false)
+        ],
+        data: DataStorageInstruction::Create {
+            label: data_storage_account_label,
+            data: b"Solana".to_vec()
+        }.pack()
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[ instruction ],
+        Some(&payer.pubkey()),
+        &[ &payer ],
+        ptc.last_blockhash
+    );
+
+    let simulation_result = ptc
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        simulation_result.result,
+        Some(Ok(())),
+        "Create failed!"
+    );
+
+    let logs = simulation_result
+        .simulation_details
+        .unwrap()
+        .logs;
+
+    for narrative_line in [
+        "Program log: ⚙️ Instruction: CreateNewDataStorageAccount",
+        "Program log: Mutable",
+        "Program log: New data storage account created."
+    ] {
+        assert!(
+            !logs.contains(&String::from(narrative_line)),
+            "Narrative log lines must not be logged without 'verbose_logs'."
+        );
+    };
+    assert_eq!(
+        emitted_events(&logs),
+        vec![
+            Events::NewDataStorageAccountCreated {
+                data_storage_account: dsa_addr,
+                authority_account: payer.pubkey(),
+                account_label: data_storage_account_label
+            }
+        ],
+        "The event must still be emitted."
+    );
+}
+
+#[tokio::test]
+#[cfg(feature = "client")]
+async fn test_create_with_memo() {
+    use data_storage::client::{
+        create_with_memo,
+        MEMO_PROGRAM_ID
+    };
+
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let ptc = setup(&data_storage_program_id).start_with_context().await;
+
+    let mut data_storage_account_label: [u8; 30] = [0; 30];
+    data_storage_account_label.fill(77);
+
+    let instructions = create_with_memo(
+        &data_storage_program_id,
+        &ptc.payer.pubkey(),
+        &ptc.payer.pubkey(),
+        &data_storage_account_label,
+        0,
+        0,
+        b"Solana",
+        "invoice #42"
+    );
+
+    assert_eq!(
+        instructions.len(),
+        2,
+        "Invalid instructions count."
+    );
+    assert_eq!(
+        instructions[0],
+        Instruction {
+            program_id: MEMO_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(ptc.payer.pubkey(), true)
+            ],
+            data: b"invoice #42".to_vec()
+        },
+        "Invalid memo instruction."
+    );
+    assert_eq!(
+        instructions[1].data,
+        [
+            encode_instruction_discriminator(CREATE_NEW_DATA_STORAGE_ACCOUNT_INSTRUCTION_DISCRIMINATOR).as_slice(),
+            data_storage_account_label.as_slice(),
+            b"Solana"
+        ].concat(),
+        "Invalid create instruction."
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&ptc.payer.pubkey()),
+        &[ &ptc.payer ],
+        ptc.last_blockhash
+    );
+
+    ptc
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let SolanaAccount { data, .. } = ptc
+        .banks_client
+        .get_account(instructions[1].accounts[0].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        data_as_str(&data).unwrap(),
+        "Solana",
+        "Invalid data."
+    );
+}
+
+#[tokio::test]
+#[cfg(feature = "client")]
+async fn test_client_instruction_builders() {
+    use data_storage::client::{
+        pad_label,
+        derive_dsa_address,
+        create_data_storage_account_ix,
+        edit_ix,
+        close_ix
+    };
+
+    let data_storage_program_id = Pubkey::new_from_array([1; 32]);
+    let mut ptc = setup(&data_storage_program_id).start_with_context().await;
+    let payer = ptc.payer.insecure_clone();
+
+    async fn process(
+        ptc: &mut solana_program_test::ProgramTestContext,
+        instruction: Instruction,
+        payer: &Keypair
+    ) {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ instruction ],
+            Some(&payer.pubkey()),
+            &[ payer ],
+            ptc.get_new_latest_blockhash().await.unwrap()
+        );
+
+        ptc
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+    }
+
+    let mut padded_label: [u8; 30] = [0; 30];
+    padded_label[..6].copy_from_slice(b"Notes!");
+    assert_eq!(
+        pad_label("Notes!").unwrap(),
+        padded_label,
+        "Invalid padded label."
+    );
+    assert_eq!(
+        pad_label(&"x".repeat(31)).unwrap_err(),
+        ProgramError::Custom(DataStorageError::InvalidLabel as u32),
+        "Labels longer than 30 bytes must be rejected."
+    );
+
+    let (dsa_addr, _) = derive_dsa_address(
+        &data_storage_program_id,
+        &payer.pubkey(),
+        &padded_label
+    );
+
+    // create
+    process(
+        &mut ptc,
+        create_data_storage_account_ix(
+            &data_storage_program_id,
+            &payer.pubkey(),
+            &payer.pubkey(),
+            "Notes!",
+            b"Solana"
+        ).unwrap(),
+        &payer
+    ).await;
+
+    let SolanaAccount { data, .. } = ptc
+        .banks_client
+        .get_account(dsa_addr)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        data_as_str(&data).unwrap(),
+        "Solana",
+        "Invalid created data."
+    );
+
+    // edit up, edit in place & edit down
+    for (old_data, new_data) in [
+        ("Solana", "Solana Data Storage"),
+        ("Solana Data Storage", "Solana Data Program"),
+        ("Solana Data Program", "Sol")
+    ] {
+        process(
+            &mut ptc,
+            edit_ix(
+                &data_storage_program_id,
+                &payer.pubkey(),
+                &payer.pubkey(),
+                "Notes!",
+                old_data.len(),
+                new_data.as_bytes()
+            ).unwrap(),
+            &payer
+        ).await;
+
+        let SolanaAccount { data, .. } = ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            data_as_str(&data).unwrap(),
+            new_data,
+            "Invalid edited data."
+        );
+    }
+
+    // close
+    process(
+        &mut ptc,
+        close_ix(
+            &data_storage_program_id,
+            &payer.pubkey(),
+            &payer.pubkey(),
+            "Notes!"
+        ).unwrap(),
+        &payer
+    ).await;
+
+    assert_eq!(
+        ptc
+            .banks_client
+            .get_account(dsa_addr)
+            .await
+            .unwrap(),
+        None,
+        "Data storage account must be closed."
+    );
+}
@@ -4,6 +4,8 @@ import {
 import {
     getStructEncoder,
     getU8Encoder,
+    getU32Encoder,
+    getI64Encoder,
     fixEncoderSize,
     getUtf8Encoder,
     getArrayEncoder,
@@ -15,7 +17,10 @@ import {
     getU8Decoder,
     getBooleanDecoder,
     getArrayDecoder,
-    getU16Decoder
+    getU16Decoder,
+    getU32Decoder,
+    getU64Encoder,
+    getU64Decoder
 } from "@solana/codecs";
 
 
@@ -24,6 +29,8 @@ export const getCreateDataStorageAccountInstructionDataEncoder = (data_size: num
         [
             [ "discriminator", getU8Encoder() ],
             [ "label", fixEncoderSize(getUtf8Encoder(), 30) ],
+            [ "maxSize", getU32Encoder() ],
+            [ "expiresAt", getI64Encoder() ],
             [ "data", getArrayEncoder(getU8Encoder(), { size: data_size }) ]
         ]
     );
@@ -31,14 +38,30 @@ export const getCreateDataStorageAccountInstructionDataEncoder = (data_size: num
     return transformEncoder(
         ix_create_new_dsa,
         // @ts-ignore
-        value => ({ ...value, discriminator: 0 })
+        value => ({ ...value, discriminator: 48 }) // "CREATE_NEW_DATA_STORAGE_ACCOUNT_WITH_OPTIONS"
     );
 }
 
-export const getEditDataStorageAccountInstructionDataEncoder = (data_size: number) => {
+export const EDIT_FLAG_DISPLAY_LABEL = 1 << 0;
+export const EDIT_FLAG_IDEMPOTENCY_KEY = 1 << 1;
+
+export const getEditDataStorageAccountInstructionDataEncoder = (
+    data_size: number,
+    has_display_label: boolean = false,
+    has_idempotency_key: boolean = false
+) => {
+    const flags = (has_display_label ? EDIT_FLAG_DISPLAY_LABEL : 0) | (has_idempotency_key ? EDIT_FLAG_IDEMPOTENCY_KEY : 0);
+
+    // "EDIT_DATA_STORAGE_ACCOUNT" (1) carries the raw data-field, the flags go with "EDIT_DATA_STORAGE_ACCOUNT_WITH_OPTIONS" (50)
     const ix_edit_dsa = getStructEncoder(
         [
             [ "discriminator", getU8Encoder() ],
+            // @ts-ignore
+            ...(flags !== 0 ? [ [ "flags", getU8Encoder() ] ] : []),
+            // @ts-ignore
+            ...(has_display_label ? [ [ "displayLabel", fixEncoderSize(getUtf8Encoder(), 30) ] ] : []),
+            // @ts-ignore
+            ...(has_idempotency_key ? [ [ "idempotencyKey", getU64Encoder() ] ] : []),
             [ "newData", getArrayEncoder(getU8Encoder(), { size: data_size }) ]
         ]
     );
@@ -46,7 +69,7 @@ export const getEditDataStorageAccountInstructionDataEncoder = (data_size: numbe
     return transformEncoder(
         ix_edit_dsa,
         // @ts-ignore
-        value => ({ ...value, discriminator: 1 })
+        value => ({ ...value, discriminator: flags !== 0 ? 50 : 1, flags })
     );
 };
 
@@ -72,7 +95,31 @@ export const getDataStorageAccountDecoder = () => {
             [ "lastUpdated", getI64Decoder() ],
             [ "canonicalBump", getU8Decoder() ],
             [ "isInitialized", getBooleanDecoder() ],
-            [ "data", getArrayDecoder(getU8Decoder(), { size: getU16Decoder() }) ]
+            [ "dataLength", getU16Decoder() ],
+            [ "maxSize", getU32Decoder() ],
+            [ "lockHolder", getAddressDecoder() ],
+            [ "lockExpiry", getI64Decoder() ],
+            [ "displayLabel", fixDecoderSize(getUtf8Decoder(), 30) ],
+            [ "idempotencyKey", getU64Decoder() ],
+            [ "cpiAllowlist", getArrayDecoder(getAddressDecoder(), { size: 3 }) ],
+            [ "hasBeenEdited", getBooleanDecoder() ],
+            [ "createdAt", getI64Decoder() ],
+            [ "revision", getU32Decoder() ],
+            [ "dataHash", getArrayDecoder(getU8Decoder(), { size: 32 }) ],
+            [ "delegate", getAddressDecoder() ],
+            [ "expiresAt", getI64Decoder() ],
+            [ "lastEditor", getAddressDecoder() ],
+            [ "layoutVersion", getU8Decoder() ],
+            [ "variableLabelLength", getU8Decoder() ],
+            [ "variableLabel", getArrayDecoder(getU8Decoder(), { size: 64 }) ],
+            [ "tombstone", getBooleanDecoder() ],
+            [ "closedAt", getI64Decoder() ],
+            [ "headerVersion", getU8Decoder() ],
+            [ "compressed", getBooleanDecoder() ],
+            [ "kind", getU8Decoder() ],
+            [ "lockUntil", getI64Decoder() ],
+            [ "accountDiscriminator", getU8Decoder() ],
+            [ "data", getArrayDecoder(getU8Decoder(), { size: "remainder" }) ]
         ]
     );
 };
\ No newline at end of file
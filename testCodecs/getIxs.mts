@@ -19,7 +19,11 @@ export const getCreateDataStorageAccountInstruction = (
         new_data_storage_pda: Address,
         data_storage_authority: Address,
         funding_account: Address,
+        config_pda: Address,
+        treasury?: Address,
         label: string,
+        max_size?: number,
+        expires_at?: bigint,
         data: Uint8Array
     }
 ): IInstruction => {
@@ -27,7 +31,11 @@ export const getCreateDataStorageAccountInstruction = (
         new_data_storage_pda,
         data_storage_authority,
         funding_account,
+        config_pda,
+        treasury,
         label,
+        max_size = 0,
+        expires_at = 0n,
         data
     } = params;
 
@@ -49,11 +57,19 @@ export const getCreateDataStorageAccountInstruction = (
             {
                 address: SYSTEM_PROGRAM_ID,
                 role: AccountRole.READONLY
-            }
+            },
+            {
+                address: config_pda,
+                role: AccountRole.READONLY
+            },
+            // only when the config has a creation-fee
+            ...(treasury !== undefined ? [ { address: treasury, role: AccountRole.WRITABLE } ] : [])
         ],
         data: getCreateDataStorageAccountInstructionDataEncoder(data.length).encode(
             {
                 label,
+                maxSize: max_size,
+                expiresAt: expires_at,
                 data
             }
         )
@@ -64,13 +80,17 @@ export const getEditDataStorageAccountWithNewDataLenIsEqualToOldDataLenInstructi
     params: {
         data_storage_pda: Address,
         data_storage_authority: Address,
-        new_data: Uint8Array
+        new_data: Uint8Array,
+        display_label?: string,
+        idempotency_key?: bigint
     }
 ): IInstruction => {
     const {
         data_storage_authority,
         data_storage_pda,
-        new_data
+        new_data,
+        display_label,
+        idempotency_key
     } = params;
 
     return {
@@ -85,8 +105,14 @@ export const getEditDataStorageAccountWithNewDataLenIsEqualToOldDataLenInstructi
                 role: AccountRole.READONLY_SIGNER
             }
         ],
-        data: getEditDataStorageAccountInstructionDataEncoder(new_data.length).encode(
-            {
+        data: getEditDataStorageAccountInstructionDataEncoder(
+            new_data.length,
+            display_label !== undefined,
+            idempotency_key !== undefined
+        ).encode(
+            {
+                displayLabel: display_label,
+                idempotencyKey: idempotency_key,
                 newData: new_data
             }
         )
@@ -98,14 +124,18 @@ export const getEditDataStorageAccountWithNewDataLenLessIsThanOldDataLenInstruct
         data_storage_pda: Address,
         data_storage_authority: Address,
         rent_receiver_account: Address,
-        new_data: Uint8Array
+        new_data: Uint8Array,
+        display_label?: string,
+        idempotency_key?: bigint
     }
 ): IInstruction => {
     const {
         data_storage_authority,
         data_storage_pda,
         rent_receiver_account,
-        new_data
+        new_data,
+        display_label,
+        idempotency_key
     } = params;
 
     return {
@@ -124,8 +154,14 @@ export const getEditDataStorageAccountWithNewDataLenLessIsThanOldDataLenInstruct
                 role: AccountRole.WRITABLE
             }
         ],
-        data: getEditDataStorageAccountInstructionDataEncoder(new_data.length).encode(
-            {
+        data: getEditDataStorageAccountInstructionDataEncoder(
+            new_data.length,
+            display_label !== undefined,
+            idempotency_key !== undefined
+        ).encode(
+            {
+                displayLabel: display_label,
+                idempotencyKey: idempotency_key,
                 newData: new_data
             }
         )
@@ -137,14 +173,18 @@ export const getEditDataStorageAccountWithNewDataLenIsBiggerThanOldDataLenInstru
         data_storage_pda: Address,
         data_storage_authority: Address,
         funding_account: Address,
-        new_data: Uint8Array
+        new_data: Uint8Array,
+        display_label?: string,
+        idempotency_key?: bigint
     }
 ): IInstruction => {
     const {
         data_storage_authority,
         data_storage_pda,
         funding_account,
-        new_data
+        new_data,
+        display_label,
+        idempotency_key
     } = params;
 
     return {
@@ -167,8 +207,14 @@ export const getEditDataStorageAccountWithNewDataLenIsBiggerThanOldDataLenInstru
                 role: AccountRole.READONLY
             }
         ],
-        data: getEditDataStorageAccountInstructionDataEncoder(new_data.length).encode(
-            {
+        data: getEditDataStorageAccountInstructionDataEncoder(
+            new_data.length,
+            display_label !== undefined,
+            idempotency_key !== undefined
+        ).encode(
+            {
+                displayLabel: display_label,
+                idempotencyKey: idempotency_key,
                 newData: new_data
             }
         )
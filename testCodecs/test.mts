@@ -56,6 +56,14 @@ import {
             programAddress: DATA_STORAGE_PROGRAM_ID
         }
     );
+    const [ config_pda_addr ] = await getProgramDerivedAddress(
+        {
+            seeds: [
+                getUtf8Encoder().encode("config")
+            ],
+            programAddress: DATA_STORAGE_PROGRAM_ID
+        }
+    );
 
     /// Create New Data-Storage PDA 
     {
@@ -65,6 +73,7 @@ import {
                 data,
                 data_storage_authority: USER_KEYPAIR.address,
                 funding_account: USER_KEYPAIR.address,
+                config_pda: config_pda_addr,
                 label,
                 new_data_storage_pda: dsa_pda_addr,
             }
@@ -114,6 +123,11 @@ import {
         expect(parsedAccountData.isInitialized).to.be.eq(true);
         expect(parsedAccountData.label).to.be.eq(label);
         expect(parsedAccountData.lastUpdated).to.be.eq(0n);
+        expect(parsedAccountData.maxSize).to.be.eq(0);
+        expect(parsedAccountData.lockExpiry).to.be.eq(0n);
+        expect(parsedAccountData.hasBeenEdited).to.be.false;
+        expect(parsedAccountData.createdAt > 0n).to.be.true;
+        expect(parsedAccountData.revision).to.be.eq(0);
     }
     /// Create New Data-Storage PDA
 